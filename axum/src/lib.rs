@@ -1,3 +1,4 @@
+use std::borrow::Borrow;
 use std::marker::PhantomData;
 use std::ops::Deref;
 use std::sync::Arc;
@@ -10,15 +11,27 @@ use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
 use axum::Extension;
 
-use ruice::{AsyncServices, ServiceContainer};
+use ruice::{
+    AsyncServices, KeyedContainer, ResolutionError, ServiceContainer, ServiceKey, Services,
+};
 
 #[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
 pub enum Error {
     #[error("Service container is not available in this context: {0}")]
     ServiceContainerNotAvailable(#[from] ExtensionRejection),
 
     #[error("Could not find the service in the container, or could not resolve the service.")]
     ServiceNotFound,
+
+    /// Rejected instead of [`Error::ServiceNotFound`] when enabled by [`ErrorConfig::verbose`].
+    #[error("Could not resolve the service: {0}")]
+    ServiceNotResolved(#[source] ResolutionError),
+
+    #[error(
+        "Could not find the service named {0} in the container, or could not resolve the service."
+    )]
+    NamedServiceNotFound(&'static str),
 }
 
 impl IntoResponse for Error {
@@ -27,6 +40,52 @@ impl IntoResponse for Error {
     }
 }
 
+/// Configures the rejections of the extractors, added to the router as an [`Extension`].
+///
+/// By default, a service failing to resolve is rejected by [`Error::ServiceNotFound`], which
+/// does not tell which one, so the responses never reveal the type names of the application.
+/// Enable the verbose errors during development to see the cause instead:
+///
+/// ```
+/// use std::sync::Arc;
+///
+/// use axum::{Extension, Router};
+/// use ruice::ServiceContainer;
+/// use ruice_axum::ErrorConfig;
+///
+/// let app: Router = Router::new()
+///     .layer(Extension(Arc::new(ServiceContainer::default())))
+///     .layer(Extension(ErrorConfig::new().verbose(cfg!(debug_assertions))));
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ErrorConfig {
+    verbose: bool,
+}
+
+impl ErrorConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Rejects the services failing to resolve by [`Error::ServiceNotResolved`], carrying the
+    /// [`ResolutionError`] with the type name of the service, e.g.
+    /// "The service dyn Database is not registered.", and the named ones by
+    /// [`Error::NamedServiceNotFound`]. Keep it disabled in production.
+    pub fn verbose(mut self, verbose: bool) -> Self {
+        self.verbose = verbose;
+        self
+    }
+}
+
+/// Rejects the service failing to resolve as configured by the [`ErrorConfig`] of the request,
+/// by the verbose error only if enabled.
+fn service_not_found(parts: &Parts, verbose: impl FnOnce() -> Error) -> Error {
+    match parts.extensions.get::<ErrorConfig>() {
+        Some(config) if config.verbose => verbose(),
+        _ => Error::ServiceNotFound,
+    }
+}
+
 /// Inject enables you to retrieve DI components from the controllers.
 ///
 /// ```
@@ -38,36 +97,101 @@ impl IntoResponse for Error {
 ///     // do something with db
 /// }
 /// ```
-pub struct Inject<I, C = ServiceContainer>
+///
+/// Behaviors bound as `dyn Fn` interfaces, such as policy functions, are injected the same way,
+/// and called through the extractor:
+///
+/// ```
+/// use std::sync::Arc;
+///
+/// use axum::routing::post;
+/// use axum::{Extension, Router};
+/// use ruice::{BindServices, ServiceContainer};
+/// use ruice_axum::Inject;
+///
+/// type Validate = dyn Fn(&str) -> bool + Send + Sync;
+///
+/// async fn register(validate: Inject<Validate>, name: String) -> &'static str {
+///     if validate(&name) {
+///         "registered"
+///     } else {
+///         "invalid name"
+///     }
+/// }
+///
+/// let mut container = ServiceContainer::default();
+/// container.bind::<Validate>(Arc::new(|name: &str| !name.is_empty()));
+///
+/// let app: Router = Router::new()
+///     .route("/users", post(register))
+///     .layer(Extension(Arc::new(container)));
+/// ```
+///
+/// The rejection can be customized by any type convertible from [`Error`], e.g. for responding
+/// with the same error envelope as the rest of the API:
+///
+/// ```
+/// use axum::http::StatusCode;
+/// use axum::response::{IntoResponse, Response};
+/// use axum::Json;
+/// use ruice::ServiceContainer;
+/// use ruice_axum::{Error, Inject};
+///
+/// struct JsonError(Error);
+///
+/// impl From<Error> for JsonError {
+///     fn from(value: Error) -> Self {
+///         Self(value)
+///     }
+/// }
+///
+/// impl IntoResponse for JsonError {
+///     fn into_response(self) -> Response {
+///         let body = Json(serde_json::json!({ "error": self.0.to_string() }));
+///         (StatusCode::INTERNAL_SERVER_ERROR, body).into_response()
+///     }
+/// }
+///
+/// # trait Database {}
+/// async fn get_foo(db: Inject<dyn Database, ServiceContainer, JsonError>) {
+///     // do something with db
+/// }
+/// ```
+pub struct Inject<I, C = ServiceContainer, E = Error>
 where
     I: ?Sized,
     C: AsyncServices,
 {
     interface: Arc<I>,
-    _phantom: PhantomData<fn() -> C>,
+    _phantom: PhantomData<fn() -> (C, E)>,
 }
 
 #[async_trait]
-impl<I, C, B> FromRequestParts<B> for Inject<I, C>
+impl<I, C, E, B> FromRequestParts<B> for Inject<I, C, E>
 where
     I: ?Sized + Send + Sync + 'static,
-    C: AsyncServices + 'static,
+    C: AsyncServices + Services + 'static,
+    E: From<Error> + IntoResponse,
     B: Send + Sync,
 {
-    type Rejection = Error;
+    type Rejection = E;
 
     async fn from_request_parts(parts: &mut Parts, state: &B) -> Result<Self, Self::Rejection> {
-        let Extension(services): Extension<Arc<C>> =
-            Extension::from_request_parts(parts, state).await?;
+        let Extension(services): Extension<Arc<C>> = Extension::from_request_parts(parts, state)
+            .await
+            .map_err(Error::from)?;
 
         Ok(Inject {
-            interface: services.get_async().await.ok_or(Error::ServiceNotFound)?,
+            interface: services
+                .try_get_async()
+                .await
+                .map_err(|e| service_not_found(parts, || Error::ServiceNotResolved(e)))?,
             _phantom: PhantomData,
         })
     }
 }
 
-impl<I, C> Deref for Inject<I, C>
+impl<I, C, E> Deref for Inject<I, C, E>
 where
     I: ?Sized + Send + Sync,
     C: AsyncServices,
@@ -78,3 +202,326 @@ where
         self.interface.as_ref()
     }
 }
+
+/// InjectSync retrieves DI components resolved synchronously, skipping the asynchronous lookup
+/// done by [`Inject`]. Use this for services that are registered without asynchronous resolvers.
+///
+/// ```
+/// use ruice_axum::InjectSync;
+///
+/// # trait Config {}
+/// async fn get_foo(config: InjectSync<dyn Config>) {
+///     // do something with config
+/// }
+/// ```
+pub struct InjectSync<I, C = ServiceContainer, E = Error>
+where
+    I: ?Sized,
+    C: Services,
+{
+    interface: Arc<I>,
+    _phantom: PhantomData<fn() -> (C, E)>,
+}
+
+#[async_trait]
+impl<I, C, E, B> FromRequestParts<B> for InjectSync<I, C, E>
+where
+    I: ?Sized + Send + Sync + 'static,
+    C: Services + 'static,
+    E: From<Error> + IntoResponse,
+    B: Send + Sync,
+{
+    type Rejection = E;
+
+    async fn from_request_parts(parts: &mut Parts, state: &B) -> Result<Self, Self::Rejection> {
+        let Extension(services): Extension<Arc<C>> = Extension::from_request_parts(parts, state)
+            .await
+            .map_err(Error::from)?;
+
+        Ok(InjectSync {
+            interface: services
+                .try_get()
+                .map_err(|e| service_not_found(parts, || Error::ServiceNotResolved(e)))?,
+            _phantom: PhantomData,
+        })
+    }
+}
+
+impl<I, C, E> Deref for InjectSync<I, C, E>
+where
+    I: ?Sized + Send + Sync,
+    C: Services,
+{
+    type Target = I;
+
+    fn deref(&self) -> &Self::Target {
+        self.interface.as_ref()
+    }
+}
+
+/// The name of a service resolved by [`InjectNamed`], declared by [`service_name!`].
+///
+/// Strings can not be generic parameters on stable Rust, so the names are carried by types.
+pub trait ServiceName {
+    const NAME: &'static str;
+}
+
+/// Declares types implementing [`ServiceName`].
+///
+/// ```
+/// ruice_axum::service_name! {
+///     pub Primary = "primary";
+///     pub Replica = "replica";
+/// }
+/// ```
+#[macro_export]
+macro_rules! service_name {
+    ($($vis: vis $ty: ident = $name: literal;)*) => {
+        $(
+            $vis struct $ty;
+
+            impl $crate::ServiceName for $ty {
+                const NAME: &'static str = $name;
+            }
+        )*
+    };
+}
+
+/// InjectNamed retrieves the service registered with the name in a [`KeyedContainer`], e.g. for
+/// picking a replica database instead of the primary one.
+///
+/// ```
+/// use std::sync::Arc;
+///
+/// use axum::routing::get;
+/// use axum::{Extension, Router};
+/// use ruice::bind::Bound;
+/// use ruice::KeyedContainer;
+/// use ruice_axum::{service_name, InjectNamed};
+///
+/// trait Database: Send + Sync {}
+///
+/// struct Postgres;
+///
+/// impl Database for Postgres {}
+///
+/// service_name! {
+///     Replica = "replica";
+/// }
+///
+/// async fn list_users(db: InjectNamed<dyn Database, Replica>) {
+///     // do something with the replica
+/// }
+///
+/// let mut container = KeyedContainer::<String>::new();
+/// container.put_keyed::<dyn Database, _>(
+///     "replica".to_string(),
+///     Bound::from(Arc::new(Postgres) as Arc<dyn Database>),
+/// );
+///
+/// let app: Router = Router::new()
+///     .route("/users", get(list_users))
+///     .layer(Extension(Arc::new(container)));
+/// ```
+pub struct InjectNamed<I, N, K = String, E = Error>
+where
+    I: ?Sized,
+{
+    interface: Arc<I>,
+    #[allow(clippy::type_complexity)]
+    _phantom: PhantomData<fn() -> (N, K, E)>,
+}
+
+#[async_trait]
+impl<I, N, K, E, B> FromRequestParts<B> for InjectNamed<I, N, K, E>
+where
+    I: ?Sized + Send + Sync + 'static,
+    N: ServiceName,
+    K: ServiceKey + Borrow<str>,
+    E: From<Error> + IntoResponse,
+    B: Send + Sync,
+{
+    type Rejection = E;
+
+    async fn from_request_parts(parts: &mut Parts, state: &B) -> Result<Self, Self::Rejection> {
+        let Extension(services): Extension<Arc<KeyedContainer<K>>> =
+            Extension::from_request_parts(parts, state)
+                .await
+                .map_err(Error::from)?;
+
+        Ok(InjectNamed {
+            interface: services
+                .get_keyed::<I, str>(N::NAME)
+                .ok_or_else(|| service_not_found(parts, || Error::NamedServiceNotFound(N::NAME)))?,
+            _phantom: PhantomData,
+        })
+    }
+}
+
+impl<I, N, K, E> Deref for InjectNamed<I, N, K, E>
+where
+    I: ?Sized + Send + Sync,
+{
+    type Target = I;
+
+    fn deref(&self) -> &Self::Target {
+        self.interface.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::any::type_name;
+
+    use axum::http::Request;
+    use ruice::bind::AsyncBindServices;
+    use ruice::{BindServices, Bound};
+
+    use super::*;
+
+    trait Database: Send + Sync {}
+
+    async fn reject(config: Option<ErrorConfig>) -> Error {
+        let mut request = Request::builder().extension(Arc::new(ServiceContainer::default()));
+        if let Some(config) = config {
+            request = request.extension(config);
+        }
+
+        let (mut parts, _) = request.body(()).unwrap().into_parts();
+        match Inject::<dyn Database>::from_request_parts(&mut parts, &()).await {
+            Ok(_) => panic!("resolved an unregistered service"),
+            Err(error) => error,
+        }
+    }
+
+    #[tokio::test]
+    async fn verbose_errors() {
+        assert!(matches!(reject(None).await, Error::ServiceNotFound));
+        assert!(matches!(
+            reject(Some(ErrorConfig::new())).await,
+            Error::ServiceNotFound
+        ));
+
+        let error = reject(Some(ErrorConfig::new().verbose(true))).await;
+        assert!(error.to_string().contains(type_name::<dyn Database>()));
+        assert!(matches!(
+            error,
+            Error::ServiceNotResolved(ResolutionError::NotRegistered { .. })
+        ));
+    }
+
+    #[derive(Debug, thiserror::Error)]
+    #[error("connection refused")]
+    struct ConnectionRefused;
+
+    #[tokio::test]
+    async fn verbose_resolution_errors() {
+        let mut container = ServiceContainer::default();
+        container.bind_by_async(|_| async {
+            ResolutionError::custom::<Postgres>(ConnectionRefused).report();
+            None::<Arc<Postgres>>
+        });
+
+        let request = Request::builder()
+            .extension(Arc::new(container))
+            .extension(ErrorConfig::new().verbose(true));
+        let (mut parts, _) = request.body(()).unwrap().into_parts();
+
+        // The error reported by the resolver is carried as is.
+        let error = match Inject::<Postgres>::from_request_parts(&mut parts, &()).await {
+            Ok(_) => panic!("resolved a failing service"),
+            Err(error) => error,
+        };
+        let Error::ServiceNotResolved(error) = error else {
+            panic!("rejected by another error: {error}");
+        };
+        assert!(matches!(error, ResolutionError::Custom { .. }));
+        assert_eq!(
+            "connection refused",
+            std::error::Error::source(&error).unwrap().to_string(),
+        );
+    }
+
+    #[tokio::test]
+    async fn inject_sync() {
+        let mut container = ServiceContainer::default();
+        container.bind::<dyn Database>(Arc::new(Postgres));
+        container.bind_by_async(|_| async { Some(Arc::new(Postgres)) });
+
+        let request = Request::builder()
+            .extension(Arc::new(container))
+            .extension(ErrorConfig::new().verbose(true));
+        let (mut parts, _) = request.body(()).unwrap().into_parts();
+
+        assert!(
+            InjectSync::<dyn Database>::from_request_parts(&mut parts, &())
+                .await
+                .is_ok()
+        );
+
+        // Registered only asynchronously, so resolvable only by Inject.
+        assert!(matches!(
+            InjectSync::<Postgres>::from_request_parts(&mut parts, &()).await,
+            Err(Error::ServiceNotResolved(
+                ResolutionError::WrongResolverKind { .. }
+            ))
+        ));
+        assert!(Inject::<Postgres>::from_request_parts(&mut parts, &())
+            .await
+            .is_ok());
+    }
+
+    service_name! {
+        Replica = "replica";
+    }
+
+    async fn reject_named(config: Option<ErrorConfig>) -> Error {
+        let mut request = Request::builder().extension(Arc::new(KeyedContainer::<String>::new()));
+        if let Some(config) = config {
+            request = request.extension(config);
+        }
+
+        let (mut parts, _) = request.body(()).unwrap().into_parts();
+        match InjectNamed::<dyn Database, Replica>::from_request_parts(&mut parts, &()).await {
+            Ok(_) => panic!("resolved an unregistered service"),
+            Err(error) => error,
+        }
+    }
+
+    struct Postgres;
+
+    impl Database for Postgres {}
+
+    #[tokio::test]
+    async fn inject_named() {
+        let mut container = KeyedContainer::<String>::new();
+        container.put_keyed::<dyn Database, _>(
+            "replica".to_string(),
+            Bound::from(Arc::new(Postgres) as Arc<dyn Database>),
+        );
+        container.put_keyed::<Postgres, _>("primary".to_string(), Bound::from(Arc::new(Postgres)));
+
+        let request = Request::builder().extension(Arc::new(container));
+        let (mut parts, _) = request.body(()).unwrap().into_parts();
+
+        assert!(
+            InjectNamed::<dyn Database, Replica>::from_request_parts(&mut parts, &())
+                .await
+                .is_ok()
+        );
+
+        // Registered under another name only.
+        assert!(matches!(
+            InjectNamed::<Postgres, Replica>::from_request_parts(&mut parts, &()).await,
+            Err(Error::ServiceNotFound)
+        ));
+    }
+
+    #[tokio::test]
+    async fn verbose_named_errors() {
+        assert!(matches!(reject_named(None).await, Error::ServiceNotFound));
+
+        let error = reject_named(Some(ErrorConfig::new().verbose(true))).await;
+        assert!(matches!(error, Error::NamedServiceNotFound("replica")));
+    }
+}
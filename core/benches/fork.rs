@@ -0,0 +1,54 @@
+//! Compares forking a populated container before and after diverging from it.
+
+use std::sync::Arc;
+
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use ruice::{ServiceContainer, Services, Singleton};
+
+/// Distinct service types to populate the container with.
+struct Service<const N: usize>;
+
+macro_rules! populate {
+    ($container: expr, $($n: literal),*) => {
+        $( $container.put(Singleton::new(Service::<$n>)); )*
+    };
+}
+
+fn container() -> ServiceContainer {
+    let mut container = ServiceContainer::default();
+    populate!(
+        container, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21,
+        22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44,
+        45, 46, 47, 48, 49, 50, 51, 52, 53, 54, 55, 56, 57, 58, 59, 60, 61, 62, 63
+    );
+
+    container
+}
+
+fn fork(c: &mut Criterion) {
+    let container = container();
+
+    // Forking shares the map of the resolvers.
+    c.bench_function("fork", |b| b.iter(|| black_box(container.fork())));
+
+    // The first mutation of the fork copies the map, as cloning a plain map would.
+    c.bench_function("fork and put", |b| {
+        b.iter_batched(
+            || container.fork(),
+            |mut fork| {
+                fork.put(Singleton::from(Arc::new(0u8)));
+                fork
+            },
+            BatchSize::SmallInput,
+        )
+    });
+
+    // Resolutions on a fork are as fast as on the original.
+    let forked = container.fork();
+    c.bench_function("get on fork", |b| {
+        b.iter(|| black_box(forked.get::<Service<63>>()))
+    });
+}
+
+criterion_group!(benches, fork);
+criterion_main!(benches);
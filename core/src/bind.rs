@@ -1,10 +1,13 @@
 use std::future::Future;
 use std::pin::Pin;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex, PoisonError};
 
 use async_trait::async_trait;
 
-use crate::{AsyncResolve, AsyncResolver, AsyncServices, Resolve, ServiceContainer, Services};
+use crate::{
+    AsyncLazySingleton, AsyncResolve, AsyncResolver, AsyncServices, ResolutionKind, Resolve,
+    ServiceContainer, Services, WeakSingleton,
+};
 
 pub struct Bound<Interface>
 where
@@ -29,6 +32,59 @@ where
     fn resolve(&self, _container: &C) -> Option<Arc<Interface>> {
         Some(Arc::clone(&self.service))
     }
+
+    fn resolve_ref(&self) -> Option<&Arc<Interface>> {
+        Some(&self.service)
+    }
+
+    fn kind(&self) -> ResolutionKind {
+        ResolutionKind::Bound
+    }
+
+    fn is_pure(&self) -> bool {
+        true
+    }
+}
+
+/// Resolves the service deliberately left unset as `None`, while it is still registered.
+impl<Interface, C> Resolve<Interface, C> for Option<Arc<Interface>>
+where
+    Interface: ?Sized + Send + Sync,
+{
+    fn resolve(&self, _container: &C) -> Option<Arc<Interface>> {
+        self.clone()
+    }
+
+    fn resolve_ref(&self) -> Option<&Arc<Interface>> {
+        self.as_ref()
+    }
+
+    fn kind(&self) -> ResolutionKind {
+        ResolutionKind::Optional
+    }
+
+    fn is_pure(&self) -> bool {
+        true
+    }
+}
+
+#[async_trait]
+impl<Interface, C> AsyncResolve<Interface, C> for Bound<Interface>
+where
+    Interface: ?Sized + Send + Sync,
+    C: Send + Sync,
+{
+    async fn async_resolve(&self, _container: &C) -> Option<Arc<Interface>> {
+        Some(Arc::clone(&self.service))
+    }
+
+    fn is_resolved(&self) -> bool {
+        true
+    }
+
+    fn is_pure(&self) -> bool {
+        true
+    }
 }
 
 pub struct BindBy<Interface, C = ServiceContainer>
@@ -56,6 +112,10 @@ where
     fn resolve(&self, container: &C) -> Option<Arc<Interface>> {
         (self.f)(container)
     }
+
+    fn kind(&self) -> ResolutionKind {
+        ResolutionKind::BindBy
+    }
 }
 
 pub struct AsyncBindBy<Interface, C = ServiceContainer>
@@ -94,6 +154,65 @@ where
 }
 
 pub trait BindServices: Services {
+    /// Binds the service onto the interface.
+    ///
+    /// Any `'static` trait object can be an interface, including ones with associated types
+    /// (`dyn Source<Item = Event>`) and closures (`dyn Fn(&str) -> bool`), as long as the
+    /// associated types are fully specified and the object is `Send + Sync`.
+    /// Interfaces borrowing non-`'static` data (`dyn Trait + 'a`) can not be bound, because
+    /// services are keyed by their [`TypeId`](std::any::TypeId).
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use ruice::{BindServices, ServiceContainer, Services};
+    ///
+    /// type Validator = dyn Fn(&str) -> bool + Send + Sync;
+    ///
+    /// let mut container = ServiceContainer::default();
+    /// container.bind::<Validator>(Arc::new(|s: &str| !s.is_empty()));
+    ///
+    /// let validate = container.get::<Validator>().unwrap();
+    /// assert!(validate("Taro"));
+    /// ```
+    ///
+    /// Unsized data such as `str` or `[u8]` can be bound too, sharing a large immutable blob
+    /// without copying it per service. They are keyed by their exact type, so `str` is distinct
+    /// from `String`, and `[u8]` from `Vec<u8>` or `[u8; N]`. As only one service is bound per
+    /// type, wrap the blobs in newtypes to bind more than one of them:
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use ruice::{BindServices, ServiceContainer, Services, SingletonServices};
+    ///
+    /// struct Template(Arc<str>);
+    ///
+    /// let mut container = ServiceContainer::default();
+    /// container.bind::<[u8]>(Arc::from(&b"schema"[..]));
+    /// container.singleton(Template(Arc::from("Hello, {name}!")));
+    ///
+    /// assert_eq!(b"schema", &*container.get::<[u8]>().unwrap());
+    /// assert_eq!("Hello, {name}!", &*container.get::<Template>().unwrap().0);
+    /// assert!(container.get::<Vec<u8>>().is_none());
+    /// ```
+    ///
+    /// Traits requiring `&mut self` such as `Iterator` or `Stream` can not be used through an
+    /// [`Arc`]. Wrap them in a newtype guarding the object with a lock instead:
+    ///
+    /// ```
+    /// use std::sync::{Arc, Mutex};
+    ///
+    /// use ruice::{BindServices, ServiceContainer, Services};
+    ///
+    /// struct Events(Mutex<Box<dyn Iterator<Item = u32> + Send>>);
+    ///
+    /// let mut container = ServiceContainer::default();
+    /// container.bind(Arc::new(Events(Mutex::new(Box::new(0..3)))));
+    ///
+    /// let events = container.get::<Events>().unwrap();
+    /// assert_eq!(Some(0), events.0.lock().unwrap().next());
+    /// ```
     fn bind<Interface>(&mut self, service: Arc<Interface>)
     where
         Interface: ?Sized + Send + Sync + 'static,
@@ -101,6 +220,20 @@ pub trait BindServices: Services {
         self.put(Bound::from(service));
     }
 
+    /// Binds an optional service, e.g. an optional dependency left unset by the configuration.
+    /// The service is registered even if absent, so [`Services::try_get`] tells it apart from
+    /// the services never registered: it fails by [`ResolutionError::ResolverReturnedNone`]
+    /// rather than [`ResolutionError::NotRegistered`].
+    ///
+    /// [`ResolutionError::ResolverReturnedNone`]: crate::ResolutionError::ResolverReturnedNone
+    /// [`ResolutionError::NotRegistered`]: crate::ResolutionError::NotRegistered
+    fn bind_optional<Interface>(&mut self, service: Option<Arc<Interface>>)
+    where
+        Interface: ?Sized + Send + Sync + 'static,
+    {
+        self.put(service);
+    }
+
     fn bind_by<Interface, F>(&mut self, f: F)
     where
         Interface: ?Sized + Send + Sync + 'static,
@@ -109,11 +242,84 @@ pub trait BindServices: Services {
     {
         self.put(BindBy::from(f))
     }
+
+    /// Binds a service created by the function and shared while it is in use, e.g. a large cache
+    /// reclaimed when idle. The container holds the service weakly, recreating it by the function
+    /// once all of its [`Arc`]s outside the container are dropped. See [`WeakSingleton`].
+    fn bind_weak_cached<Interface, F>(&mut self, f: F)
+    where
+        Interface: ?Sized + Send + Sync + 'static,
+        F: (Fn(&Self) -> Option<Arc<Interface>>) + Send + Sync + 'static,
+        Self: 'static,
+    {
+        self.put(WeakSingleton::from(f))
+    }
+
+    /// Binds the service resolved as `Sub` onto `Super` too, casting it on every resolution.
+    ///
+    /// Trait objects are distinct services even if one trait is a subtrait of the other, so
+    /// `dyn Sub` is not resolved as `dyn Super` unless bound by this method. `cast` can be the
+    /// identity closure `|s| s` on Rust 1.86 or later, which coerces trait objects into their
+    /// supertraits. Below that, including the minimum supported version of this crate, cast
+    /// through a method of the subtrait instead:
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use ruice::{BindServices, ServiceContainer, Services};
+    ///
+    /// trait Named: Send + Sync {
+    ///     fn name(&self) -> String;
+    /// }
+    ///
+    /// trait Greet: Named {
+    ///     fn as_named(self: Arc<Self>) -> Arc<dyn Named>;
+    ///
+    ///     fn greet(&self) -> String {
+    ///         format!("Hello, {}!", self.name())
+    ///     }
+    /// }
+    ///
+    /// struct Taro;
+    ///
+    /// impl Named for Taro {
+    ///     fn name(&self) -> String {
+    ///         "Taro".to_string()
+    ///     }
+    /// }
+    ///
+    /// impl Greet for Taro {
+    ///     fn as_named(self: Arc<Self>) -> Arc<dyn Named> {
+    ///         self
+    ///     }
+    /// }
+    ///
+    /// let mut container = ServiceContainer::default();
+    /// container.bind::<dyn Greet>(Arc::new(Taro));
+    /// container.bind_upcast::<dyn Greet, dyn Named>(|s| s.as_named());
+    ///
+    /// assert_eq!("Taro", container.get::<dyn Named>().unwrap().name());
+    /// ```
+    fn bind_upcast<Sub, Super>(&mut self, cast: fn(Arc<Sub>) -> Arc<Super>)
+    where
+        Sub: ?Sized + Send + Sync + 'static,
+        Super: ?Sized + Send + Sync + 'static,
+        Self: 'static,
+    {
+        self.bind_by(move |c| c.get::<Sub>().map(cast));
+    }
 }
 
 impl<C> BindServices for C where C: Services {}
 
 pub trait AsyncBindServices: AsyncServices {
+    /// Binds a service resolved asynchronously by the function on every resolution.
+    ///
+    /// The future returned by the function must be `'static`, as it is boxed and type-erased, so
+    /// it can not borrow the container passed to the function; awaiting `c.get_async()` in an
+    /// `async move` block does not compile. Get the services available synchronously before the
+    /// block and move them into it, or use [`AsyncBindServices::bind_by_async_owned`] to await
+    /// the dependencies within the future.
     fn bind_by_async<Interface, F, Fut>(&mut self, f: F)
     where
         Interface: ?Sized + Send + Sync + 'static,
@@ -123,14 +329,152 @@ pub trait AsyncBindServices: AsyncServices {
     {
         self.put_async(AsyncResolver::new(AsyncBindBy::from(f)))
     }
+
+    /// Binds a service resolved asynchronously by the function, which owns a clone of the
+    /// container, so the returned future can resolve the dependencies asynchronously too.
+    ///
+    /// ```
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() {
+    /// use std::sync::Arc;
+    ///
+    /// use ruice::bind::AsyncBindServices;
+    /// use ruice::{AsyncServices, ServiceContainer};
+    ///
+    /// struct Connection;
+    ///
+    /// struct Repository {
+    ///     connection: Arc<Connection>,
+    /// }
+    ///
+    /// let mut container = ServiceContainer::default();
+    /// container.bind_by_async(|_| async { Some(Arc::new(Connection)) });
+    /// container.bind_by_async_owned(|c| async move {
+    ///     Some(Arc::new(Repository {
+    ///         connection: c.get_async().await?,
+    ///     }))
+    /// });
+    ///
+    /// assert!(container.get_async::<Repository>().await.is_some());
+    /// # }
+    /// ```
+    ///
+    /// The container is cloned on every resolution, which is cheap for [`ServiceContainer`] as
+    /// the clones share the resolvers.
+    fn bind_by_async_owned<Interface, F, Fut>(&mut self, f: F)
+    where
+        Interface: ?Sized + Send + Sync + 'static,
+        F: (Fn(Arc<Self>) -> Fut) + Send + Sync + 'static,
+        Fut: Future<Output = Option<Arc<Interface>>> + Send + 'static,
+        Self: Clone + Send + Sync + 'static,
+    {
+        self.bind_by_async(move |c: &Self| f(Arc::new(c.clone())))
+    }
+
+    /// Binds a service resolved only asynchronously, never falling back to nor being resolved by
+    /// [`Services::get`]. See [`AsyncResolver::async_only`].
+    fn bind_by_async_only<Interface, F, Fut>(&mut self, f: F)
+    where
+        Interface: ?Sized + Send + Sync + 'static,
+        F: (Fn(&Self) -> Fut) + Send + Sync + 'static,
+        Fut: Future<Output = Option<Arc<Interface>>> + Send + 'static,
+        Self: Send + Sync + 'static,
+    {
+        self.put_async(AsyncResolver::new(AsyncBindBy::from(f)).async_only())
+    }
+
+    /// Binds a service resolved asynchronously on the first resolution, and shared afterwards.
+    /// Whether it is resolved yet can be checked by [`AsyncServices::is_resolved`].
+    fn bind_by_async_once<Interface, F, Fut>(&mut self, f: F)
+    where
+        Interface: ?Sized + Send + Sync + 'static,
+        F: (Fn(&Self) -> Fut) + Send + Sync + 'static,
+        Fut: Future<Output = Option<Arc<Interface>>> + Send + 'static,
+        Self: Send + Sync + 'static,
+    {
+        self.put_async(AsyncResolver::new(AsyncLazySingleton::new(
+            AsyncBindBy::from(f),
+        )))
+    }
+
+    /// Binds a service onto the interface with separate branches for synchronous and
+    /// asynchronous resolution, so the same registration works for both [`Services::get`] and
+    /// [`AsyncServices::get_async`].
+    fn bind_either<Interface, F, G, Fut>(&mut self, sync_branch: F, async_branch: G)
+    where
+        Interface: ?Sized + Send + Sync + 'static,
+        F: (Fn(&Self) -> Option<Arc<Interface>>) + Send + Sync + 'static,
+        G: (Fn(&Self) -> Fut) + Send + Sync + 'static,
+        Fut: Future<Output = Option<Arc<Interface>>> + Send + 'static,
+        Self: Services + 'static,
+    {
+        self.bind_by(sync_branch);
+        self.bind_by_async(async_branch);
+    }
+
+    /// Binds a service loaded asynchronously once, and available synchronously afterwards, e.g.
+    /// a configuration fetched on startup.
+    ///
+    /// [`AsyncServices::get_async`] returns the cached service, or loads it by `async_slow_path`
+    /// and caches it. [`Services::get`] returns the cached service, or falls back to
+    /// `sync_fast_path`, which usually returns `None` until the service is loaded; a service it
+    /// returns is cached too. Concurrent first loads may all run, but share the service cached
+    /// first.
+    fn bind_dual<Interface, F, G, Fut>(&mut self, sync_fast_path: F, async_slow_path: G)
+    where
+        Interface: ?Sized + Send + Sync + 'static,
+        F: (Fn(&Self) -> Option<Arc<Interface>>) + Send + Sync + 'static,
+        G: (Fn(&Self) -> Fut) + Send + Sync + 'static,
+        Fut: Future<Output = Option<Arc<Interface>>> + Send + 'static,
+        Self: Services + 'static,
+    {
+        let cache = Arc::new(Mutex::new(None::<Arc<Interface>>));
+
+        let cached = Arc::clone(&cache);
+        self.bind_by(move |c| {
+            if let Some(s) = cached
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .as_ref()
+            {
+                return Some(Arc::clone(s));
+            }
+
+            // Not locked meanwhile, as the fast path may resolve other services through the
+            // container.
+            let resolved = sync_fast_path(c)?;
+            let mut cached = cached.lock().unwrap_or_else(PoisonError::into_inner);
+
+            Some(Arc::clone(cached.get_or_insert(resolved)))
+        });
+
+        self.bind_by_async(move |c| {
+            let cached = cache.lock().unwrap_or_else(PoisonError::into_inner).clone();
+            let loading = cached.is_none().then(|| async_slow_path(c));
+            let cache = Arc::clone(&cache);
+
+            async move {
+                if cached.is_some() {
+                    return cached;
+                }
+
+                let resolved = loading?.await?;
+                let mut cached = cache.lock().unwrap_or_else(PoisonError::into_inner);
+
+                Some(Arc::clone(cached.get_or_insert(resolved)))
+            }
+        });
+    }
 }
 
 impl<C> AsyncBindServices for C where C: AsyncServices {}
 
 #[cfg(test)]
 mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
     use super::*;
-    use crate::ServiceContainer;
+    use crate::{ResolutionError, ServiceContainer, SingletonServices};
 
     trait Greet: Send + Sync {
         fn greet(&self) -> String;
@@ -160,6 +504,94 @@ mod tests {
         assert_eq!("Hello, Taro!".to_string(), name_getter.greet());
     }
 
+    trait Source: Send + Sync {
+        type Item;
+
+        fn next_item(&self) -> Option<Self::Item>;
+    }
+
+    struct Numbers;
+
+    impl Source for Numbers {
+        type Item = u32;
+
+        fn next_item(&self) -> Option<u32> {
+            Some(42)
+        }
+    }
+
+    #[test]
+    fn bind_associated_type() {
+        let mut container = ServiceContainer::default();
+
+        // Interfaces with different associated types are distinct services.
+        container.bind::<dyn Source<Item = u32>>(Arc::new(Numbers));
+
+        let source = container.get::<dyn Source<Item = u32>>().unwrap();
+        assert_eq!(Some(42), source.next_item());
+        assert!(!container.has::<dyn Source<Item = String>>());
+    }
+
+    #[test]
+    fn bind_fn() {
+        let mut container = ServiceContainer::default();
+
+        container.bind::<dyn Fn(&str) -> bool + Send + Sync>(Arc::new(|s: &str| s.len() > 3));
+
+        let validate = container
+            .get::<dyn Fn(&str) -> bool + Send + Sync>()
+            .unwrap();
+        assert!(validate("Taro"));
+        assert!(!validate("Jo"));
+    }
+
+    #[test]
+    fn bind_optional() {
+        let mut container = ServiceContainer::default();
+
+        container.bind_optional::<dyn Greet>(Some(Arc::new(Greeter {
+            name: "Taro".to_string(),
+        })));
+        container.bind_optional::<str>(None);
+
+        // Registered and present.
+        assert!(container.has::<dyn Greet>());
+        assert!(container.try_get::<dyn Greet>().is_ok());
+
+        // Registered, but intentionally absent.
+        assert!(container.has::<str>());
+        assert_eq!(
+            Err(ResolutionError::ResolverReturnedNone { type_name: "str" }),
+            container.try_get::<str>(),
+        );
+
+        // Never registered.
+        assert!(!container.has::<[u8]>());
+        assert_eq!(
+            Err(ResolutionError::NotRegistered { type_name: "[u8]" }),
+            container.try_get::<[u8]>(),
+        );
+    }
+
+    #[test]
+    fn get_ref() {
+        let mut container = ServiceContainer::default();
+
+        let greeter: Arc<dyn Greet> = Arc::new(Greeter {
+            name: "Taro".to_string(),
+        });
+        container.bind(Arc::clone(&greeter));
+
+        // Bound services can be borrowed without touching the reference count.
+        let borrowed = container.get_ref::<dyn Greet>().unwrap();
+        assert!(Arc::ptr_eq(&greeter, borrowed));
+        assert_eq!(2, Arc::strong_count(&greeter));
+
+        // Services created on resolution can not be borrowed.
+        container.bind_by(|_| -> Option<Arc<dyn Greet>> { None });
+        assert!(container.get_ref::<dyn Greet>().is_none());
+    }
+
     #[test]
     fn bind_by() {
         let mut container = ServiceContainer::default();
@@ -194,4 +626,192 @@ mod tests {
         let name_getter = container.get_async::<dyn Greet>().await.unwrap();
         assert_eq!("Hello, Taro!".to_string(), name_getter.greet());
     }
+
+    #[tokio::test]
+    async fn bind_either() {
+        let mut container = ServiceContainer::default();
+
+        container.bind_either(
+            |_| -> Option<Arc<dyn Greet>> {
+                Some(Arc::new(Greeter {
+                    name: "in-memory".to_string(),
+                }))
+            },
+            |_| async {
+                Some(Arc::new(Greeter {
+                    name: "remote".to_string(),
+                }) as Arc<dyn Greet>)
+            },
+        );
+
+        let greeter = container.get::<dyn Greet>().unwrap();
+        assert_eq!("Hello, in-memory!".to_string(), greeter.greet());
+
+        let greeter = container.get_async::<dyn Greet>().await.unwrap();
+        assert_eq!("Hello, remote!".to_string(), greeter.greet());
+    }
+
+    #[test]
+    fn bind_weak_cached() {
+        let mut container = ServiceContainer::default();
+
+        container.bind_weak_cached(|_| -> Option<Arc<dyn Greet>> {
+            Some(Arc::new(Greeter {
+                name: "cached".to_string(),
+            }))
+        });
+
+        let greeter = container.get::<dyn Greet>().unwrap();
+        assert!(Arc::ptr_eq(
+            &greeter,
+            &container.get::<dyn Greet>().unwrap()
+        ));
+
+        // The container does not keep the service alive.
+        let weak = Arc::downgrade(&greeter);
+        drop(greeter);
+        assert!(weak.upgrade().is_none());
+        assert_eq!(
+            "Hello, cached!",
+            container.get::<dyn Greet>().unwrap().greet()
+        );
+    }
+
+    #[tokio::test]
+    async fn bind_by_async_owned() {
+        let mut container = ServiceContainer::default();
+
+        container.bind_by_async(|_| async { Some(Arc::new("Taro".to_string())) });
+
+        // The container is owned by the future, so the dependencies can be awaited.
+        container.bind_by_async_owned(|c| async move {
+            Some(Arc::new(Greeter {
+                name: c.get_async::<String>().await?.to_string(),
+            }) as Arc<dyn Greet>)
+        });
+
+        assert_eq!(
+            "Hello, Taro!",
+            container.get_async::<dyn Greet>().await.unwrap().greet()
+        );
+    }
+
+    #[tokio::test]
+    async fn bind_by_async_only() {
+        let mut container = ServiceContainer::default();
+
+        container.bind(Arc::new("sync".to_string()));
+        container.bind_by_async_only(|_| async { None::<Arc<String>> });
+
+        // Neither resolved synchronously nor falling back to the synchronous resolver.
+        assert!(container.get::<String>().is_none());
+        assert!(container.get_async::<String>().await.is_none());
+        assert_eq!(
+            Err(ResolutionError::WrongResolverKind {
+                type_name: std::any::type_name::<String>(),
+            }),
+            container.try_get::<String>().map(|_| ()),
+        );
+
+        // Other asynchronous services still fall back.
+        container.bind(Arc::new(42u32));
+        container.bind_by_async(|_| async { None::<Arc<u32>> });
+        assert_eq!(42, *container.get_async::<u32>().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn bind_dual() {
+        let mut container = ServiceContainer::default();
+        let loads = Arc::new(AtomicUsize::new(0));
+
+        let counter = Arc::clone(&loads);
+        container.bind_dual(
+            |_| None,
+            move |_| {
+                counter.fetch_add(1, Ordering::SeqCst);
+                async {
+                    Some(Arc::new(Greeter {
+                        name: "remote".to_string(),
+                    }) as Arc<dyn Greet>)
+                }
+            },
+        );
+
+        // Not loaded yet.
+        assert!(container.get::<dyn Greet>().is_none());
+
+        let loaded = container.get_async::<dyn Greet>().await.unwrap();
+        assert_eq!("Hello, remote!".to_string(), loaded.greet());
+
+        // Cached for both afterwards.
+        assert!(Arc::ptr_eq(&loaded, &container.get::<dyn Greet>().unwrap()));
+        assert!(Arc::ptr_eq(
+            &loaded,
+            &container.get_async::<dyn Greet>().await.unwrap()
+        ));
+        assert_eq!(1, loads.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn bind_dual_fast_path() {
+        let mut container = ServiceContainer::default();
+        let loads = Arc::new(AtomicUsize::new(0));
+
+        let counter = Arc::clone(&loads);
+        container.bind_dual(
+            |c| {
+                Some(Arc::new(Greeter {
+                    name: c.get::<String>()?.to_string(),
+                }) as Arc<dyn Greet>)
+            },
+            move |_| {
+                counter.fetch_add(1, Ordering::SeqCst);
+                async { None }
+            },
+        );
+        assert!(container.get::<dyn Greet>().is_none());
+
+        // Resolved through the container by the fast path, and cached for both.
+        container.singleton("local".to_string());
+        let resolved = container.get::<dyn Greet>().unwrap();
+        assert_eq!("Hello, local!".to_string(), resolved.greet());
+        assert!(Arc::ptr_eq(
+            &resolved,
+            &container.get_async::<dyn Greet>().await.unwrap()
+        ));
+        assert_eq!(0, loads.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn bind_by_async_once() {
+        let mut container = ServiceContainer::default();
+
+        container.bind_by_async_once(|_| async { Some(Arc::new("connected".to_string())) });
+        container.bind_by_async(|_| async { Some(Arc::new(42u32)) });
+        assert!(!container.is_resolved::<String>());
+
+        let connection = container.get_async::<String>().await.unwrap();
+        assert!(container.is_resolved::<String>());
+        assert!(Arc::ptr_eq(
+            &connection,
+            &container.get_async::<String>().await.unwrap(),
+        ));
+
+        // Resolvers without a cache are never reported as resolved.
+        container.get_async::<u32>().await.unwrap();
+        assert!(!container.is_resolved::<u32>());
+    }
+
+    #[test]
+    fn bind_unsized() {
+        let mut container = ServiceContainer::default();
+
+        let blob: Arc<str> = Arc::from("a large template");
+        container.bind::<str>(Arc::clone(&blob));
+
+        // The same allocation is shared, not copied.
+        assert!(Arc::ptr_eq(&blob, &container.get::<str>().unwrap()));
+        assert!(Arc::ptr_eq(&blob, container.get_ref::<str>().unwrap()));
+        assert!(container.get::<String>().is_none());
+    }
 }
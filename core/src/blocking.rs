@@ -0,0 +1,124 @@
+use std::any::type_name;
+use std::sync::Arc;
+
+use tokio::runtime::{Handle, RuntimeFlavor};
+
+use crate::{AsyncServices, ResolutionError};
+
+pub trait BlockingServices: AsyncServices {
+    /// Gets the service by blocking the current thread on its asynchronous resolution, for the
+    /// synchronous code which can not avoid depending on asynchronous services.
+    ///
+    /// Blocking requires a task of a multi-thread Tokio runtime, including the ones spawned by
+    /// `spawn_blocking`. The worker thread is handed over to the other tasks while blocking.
+    /// Otherwise, an error is returned instead of panicking or hanging:
+    ///
+    /// - [`ResolutionError::RuntimeNotAvailable`] outside of any runtime.
+    /// - [`ResolutionError::BlockingInAsyncContext`] within the runtime context but outside of a
+    ///   task, e.g. directly in the future passed to `Runtime::block_on`, which can not block
+    ///   on another future.
+    /// - [`ResolutionError::BlockingInAsyncContext`] on a current-thread runtime, which deadlocks
+    ///   when blocked within its task, and can not drive the IO and the timers of the
+    ///   resolution from another thread.
+    fn get_blocking<S>(&self) -> Result<Arc<S>, ResolutionError>
+    where
+        S: ?Sized + Send + Sync + 'static,
+    {
+        let handle = Handle::try_current().map_err(|_| ResolutionError::RuntimeNotAvailable {
+            type_name: type_name::<S>(),
+        })?;
+
+        if tokio::task::try_id().is_none() || handle.runtime_flavor() != RuntimeFlavor::MultiThread
+        {
+            return Err(ResolutionError::BlockingInAsyncContext {
+                type_name: type_name::<S>(),
+            });
+        }
+
+        tokio::task::block_in_place(|| handle.block_on(self.get_async::<S>()))
+            .ok_or_else(ResolutionError::returned_none::<S>)
+    }
+}
+
+impl<C> BlockingServices for C where C: AsyncServices {}
+
+#[cfg(test)]
+mod tests {
+    use tokio::runtime::Builder;
+
+    use super::*;
+    use crate::bind::AsyncBindServices;
+    use crate::ServiceContainer;
+
+    struct Connection;
+
+    fn container() -> Arc<ServiceContainer> {
+        let mut container = ServiceContainer::default();
+        container.bind_by_async(|_| async { Some(Arc::new(Connection)) });
+
+        Arc::new(container)
+    }
+
+    #[test]
+    fn get_blocking() {
+        let container = container();
+
+        assert!(matches!(
+            container.get_blocking::<Connection>(),
+            Err(ResolutionError::RuntimeNotAvailable { .. }),
+        ));
+
+        // Within the runtime context, but not in a task.
+        let runtime = Builder::new_multi_thread().build().unwrap();
+        let _guard = runtime.enter();
+        assert!(matches!(
+            container.get_blocking::<Connection>(),
+            Err(ResolutionError::BlockingInAsyncContext { .. }),
+        ));
+
+        let c = Arc::clone(&container);
+        let result = runtime.block_on(async move { c.get_blocking::<Connection>() });
+        assert!(matches!(
+            result,
+            Err(ResolutionError::BlockingInAsyncContext { .. }),
+        ));
+    }
+
+    #[test]
+    fn get_blocking_in_task() {
+        let container = container();
+
+        let runtime = Builder::new_current_thread().build().unwrap();
+        let c = Arc::clone(&container);
+        let result = runtime.block_on(runtime.spawn(async move { c.get_blocking::<Connection>() }));
+        assert!(matches!(
+            result.unwrap(),
+            Err(ResolutionError::BlockingInAsyncContext { .. }),
+        ));
+
+        let c = Arc::clone(&container);
+        let result =
+            runtime.block_on(runtime.spawn_blocking(move || c.get_blocking::<Connection>()));
+        assert!(matches!(
+            result.unwrap(),
+            Err(ResolutionError::BlockingInAsyncContext { .. }),
+        ));
+
+        let runtime = Builder::new_multi_thread().build().unwrap();
+        let c = Arc::clone(&container);
+        let result = runtime.block_on(runtime.spawn(async move { c.get_blocking::<Connection>() }));
+        assert!(result.unwrap().is_ok());
+
+        let c = Arc::clone(&container);
+        let result =
+            runtime.block_on(runtime.spawn_blocking(move || {
+                (c.get_blocking::<Connection>(), c.get_blocking::<String>())
+            }));
+        let (connection, missing) = result.unwrap();
+        assert!(connection.is_ok());
+        assert!(matches!(
+            missing,
+            Err(ResolutionError::ResolverReturnedNone { .. }),
+        ));
+    }
+}
@@ -0,0 +1,239 @@
+use std::future::Future;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::{AsyncResolve, Resolve};
+
+struct Map<R, F, S>
+where
+    S: ?Sized,
+{
+    inner: R,
+    f: F,
+    _phantom: PhantomData<fn() -> Arc<S>>,
+}
+
+struct AndThen<R, F, S>
+where
+    S: ?Sized,
+{
+    inner: R,
+    f: F,
+    _phantom: PhantomData<fn() -> Arc<S>>,
+}
+
+impl<R, F, S, T, C> Resolve<T, C> for Map<R, F, S>
+where
+    R: Resolve<S, C>,
+    F: (Fn(Arc<S>) -> Arc<T>) + Send + Sync,
+    S: ?Sized,
+    T: ?Sized,
+{
+    fn resolve(&self, container: &C) -> Option<Arc<T>> {
+        self.inner.resolve(container).map(&self.f)
+    }
+}
+
+impl<R, F, S, T, C> Resolve<T, C> for AndThen<R, F, S>
+where
+    R: Resolve<S, C>,
+    F: (Fn(Arc<S>, &C) -> Option<Arc<T>>) + Send + Sync,
+    S: ?Sized,
+    T: ?Sized,
+{
+    fn resolve(&self, container: &C) -> Option<Arc<T>> {
+        (self.f)(self.inner.resolve(container)?, container)
+    }
+}
+
+/// Combinators post-processing the service resolved by a resolver.
+pub trait ResolveExt<S, C>: Resolve<S, C> + Sized
+where
+    S: ?Sized,
+{
+    /// Transforms the resolved service, e.g. wrapping it in an adapter.
+    fn map<T, F>(self, f: F) -> impl Resolve<T, C>
+    where
+        T: ?Sized,
+        F: (Fn(Arc<S>) -> Arc<T>) + Send + Sync,
+    {
+        Map {
+            inner: self,
+            f,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Transforms the resolved service with the other services in the container.
+    /// The resolution fails if `f` returns `None`.
+    fn and_then<T, F>(self, f: F) -> impl Resolve<T, C>
+    where
+        T: ?Sized,
+        F: (Fn(Arc<S>, &C) -> Option<Arc<T>>) + Send + Sync,
+    {
+        AndThen {
+            inner: self,
+            f,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<R, S, C> ResolveExt<S, C> for R
+where
+    R: Resolve<S, C>,
+    S: ?Sized,
+{
+}
+
+#[async_trait]
+impl<R, F, S, T, C> AsyncResolve<T, C> for Map<R, F, S>
+where
+    R: AsyncResolve<S, C>,
+    F: (Fn(Arc<S>) -> Arc<T>) + Send + Sync,
+    S: ?Sized + Send + Sync,
+    T: ?Sized + Send + Sync,
+    C: Send + Sync,
+{
+    async fn async_resolve(&self, container: &C) -> Option<Arc<T>> {
+        self.inner.async_resolve(container).await.map(&self.f)
+    }
+
+    fn is_resolved(&self) -> bool {
+        self.inner.is_resolved()
+    }
+}
+
+#[async_trait]
+impl<R, F, Fut, S, T, C> AsyncResolve<T, C> for AndThen<R, F, S>
+where
+    R: AsyncResolve<S, C>,
+    F: (Fn(Arc<S>, &C) -> Fut) + Send + Sync,
+    Fut: Future<Output = Option<Arc<T>>> + Send,
+    S: ?Sized + Send + Sync,
+    T: ?Sized + Send + Sync,
+    C: Send + Sync,
+{
+    async fn async_resolve(&self, container: &C) -> Option<Arc<T>> {
+        (self.f)(self.inner.async_resolve(container).await?, container).await
+    }
+}
+
+/// Combinators post-processing the service resolved by an async resolver.
+pub trait AsyncResolveExt<S, C>: AsyncResolve<S, C> + Sized
+where
+    S: ?Sized + Send + Sync,
+    C: Send + Sync,
+{
+    /// Transforms the resolved service, e.g. wrapping it in an adapter.
+    fn map<T, F>(self, f: F) -> impl AsyncResolve<T, C>
+    where
+        T: ?Sized + Send + Sync,
+        F: (Fn(Arc<S>) -> Arc<T>) + Send + Sync,
+    {
+        Map {
+            inner: self,
+            f,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Transforms the resolved service asynchronously with the other services in the container.
+    /// The resolution fails if the future returned by `f` resolves to `None`.
+    fn and_then<T, F, Fut>(self, f: F) -> impl AsyncResolve<T, C>
+    where
+        T: ?Sized + Send + Sync,
+        F: (Fn(Arc<S>, &C) -> Fut) + Send + Sync,
+        Fut: Future<Output = Option<Arc<T>>> + Send,
+    {
+        AndThen {
+            inner: self,
+            f,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<R, S, C> AsyncResolveExt<S, C> for R
+where
+    R: AsyncResolve<S, C>,
+    S: ?Sized + Send + Sync,
+    C: Send + Sync,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bind::{AsyncBindBy, BindBy};
+    use crate::{AsyncResolver, AsyncServices, ServiceContainer, Services, SingletonServices};
+
+    struct Connection {
+        url: String,
+    }
+
+    struct Pooled {
+        connection: Arc<Connection>,
+        size: usize,
+    }
+
+    struct PoolSize(usize);
+
+    #[test]
+    fn map_and_then() {
+        let mut container = ServiceContainer::default();
+
+        container.singleton(PoolSize(4));
+        container.put(
+            BindBy::from(|_: &ServiceContainer| {
+                Some(Arc::new(Connection {
+                    url: "postgres://localhost".to_string(),
+                }))
+            })
+            .and_then(|connection, c: &ServiceContainer| {
+                Some(Arc::new(Pooled {
+                    connection,
+                    size: c.get::<PoolSize>()?.0,
+                }))
+            }),
+        );
+        container.put(
+            BindBy::from(|_: &ServiceContainer| Some(Arc::new(4u8)))
+                .map(|n| Arc::new(u32::from(*n))),
+        );
+
+        let pooled = container.get::<Pooled>().unwrap();
+        assert_eq!("postgres://localhost", pooled.connection.url);
+        assert_eq!(4, pooled.size);
+        assert_eq!(4, *container.get::<u32>().unwrap());
+    }
+
+    #[tokio::test]
+    async fn map_and_then_async() {
+        let mut container = ServiceContainer::default();
+
+        container.singleton(PoolSize(4));
+        container.put_async(AsyncResolver::new(
+            AsyncBindBy::from(|_: &ServiceContainer| async {
+                Some(Arc::new(Connection {
+                    url: "postgres://localhost".to_string(),
+                }))
+            })
+            .and_then(|connection, c: &ServiceContainer| {
+                let size = c.get::<PoolSize>().map(|s| s.0);
+                async move {
+                    Some(Arc::new(Pooled {
+                        connection,
+                        size: size?,
+                    }))
+                }
+            }),
+        ));
+
+        let pooled = container.get_async::<Pooled>().await.unwrap();
+        assert_eq!("postgres://localhost", pooled.connection.url);
+        assert_eq!(4, pooled.size);
+    }
+}
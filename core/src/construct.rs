@@ -1,8 +1,19 @@
+use std::any::type_name;
 use std::marker::PhantomData;
 use std::sync::Arc;
 
-use crate::{Resolve, ServiceContainer, Services};
+use async_trait::async_trait;
 
+use crate::{
+    cycle, report, AsyncResolve, AsyncResolver, AsyncServices, ResolutionError, ResolutionKind,
+    Resolve, ServiceContainer, Services, Singleton,
+};
+
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` can not be constructed from `{C}`",
+    label = "`Construct` is not implemented for `{Self}`",
+    note = "implement `Construct<{S}, {C}>` for `{Self}`, or implement it generically over `C: Services`"
+)]
 pub trait Construct<S = Self, C = ServiceContainer>: Send + Sync {
     fn construct(container: &C) -> Option<S>;
 }
@@ -27,11 +38,17 @@ impl<S> Default for Constructor<S> {
 
 impl<S, C> Resolve<S, C> for Constructor<S>
 where
-    S: Construct<S, C>,
+    S: Construct<S, C> + 'static,
 {
     fn resolve(&self, container: &C) -> Option<Arc<S>> {
+        let _guard = cycle::enter::<S>()?;
+
         Some(Arc::new(S::construct(container)?))
     }
+
+    fn kind(&self) -> ResolutionKind {
+        ResolutionKind::Constructor
+    }
 }
 
 pub trait ConstructServices: Services {
@@ -41,17 +58,215 @@ pub trait ConstructServices: Services {
     {
         self.put(Constructor::<S>::new());
     }
+
+    /// Gets the service, or constructs it from the container if it is not resolvable, e.g. for
+    /// optional services bootstrapping themselves from whatever dependencies are present.
+    ///
+    /// The service constructed on the fly is neither registered nor cached, so it is
+    /// constructed again on every call until the service is registered.
+    fn get_or_construct<S>(&self) -> Option<Arc<S>>
+    where
+        S: Construct<S, Self> + Send + Sync + 'static,
+    {
+        self.get::<S>().or_else(|| {
+            let _guard = cycle::enter::<S>()?;
+
+            Some(Arc::new(S::construct(self)?))
+        })
+    }
+
+    /// Gets the service, or constructs it from the container, or falls back to its default.
+    /// See [`ConstructServices::get_or_construct`]; the default is not cached either.
+    fn get_or_construct_or_default<S>(&self) -> Arc<S>
+    where
+        S: Construct<S, Self> + Default + Send + Sync + 'static,
+    {
+        self.get_or_construct::<S>()
+            .unwrap_or_else(|| Arc::new(S::default()))
+    }
+
+    /// Gets the service, or constructs it from the container right away and registers the
+    /// instance as a singleton, e.g. for bootstrapping code needing the service now and reusing
+    /// it afterwards. Unlike [`ConstructServices::construct`], the service is not resolved lazily.
+    ///
+    /// The registered services are resolved as usual, failing with their error. Otherwise, if the
+    /// construction fails, nothing is registered and the error is returned as by
+    /// [`ConstructServices::resolve_bundle`]: for services implementing [`Construct`] by
+    /// `#[derive(Construct)]` (`derive` feature), it names the missing dependency, e.g.
+    /// [`ResolutionError::NotRegistered`] for the first field not registered. Hand-written
+    /// implementations only tell that the service was not constructed.
+    fn ensure<S>(&mut self) -> Result<Arc<S>, ResolutionError>
+    where
+        S: Construct<S, Self> + Send + Sync + 'static,
+    {
+        if self.has::<S>() {
+            return self.try_get::<S>();
+        }
+
+        let service = report::scope(type_name::<S>(), || {
+            cycle::enter::<S>().and_then(|_guard| S::construct(self))
+        })?;
+
+        let service = Arc::new(service);
+        self.put(Singleton::from(Arc::clone(&service)));
+
+        Ok(service)
+    }
+
+    /// Constructs the bundle of services right away without registering it, e.g. for gathering
+    /// everything a handler or a job needs in one call.
+    ///
+    /// For bundles implementing [`Construct`] by `#[derive(Construct)]` (`derive` feature), the
+    /// error names the first field failed to resolve. Otherwise, it only tells that the bundle
+    /// was not constructed.
+    fn resolve_bundle<B>(&self) -> Result<B, ResolutionError>
+    where
+        B: Construct<B, Self>,
+    {
+        report::scope(type_name::<B>(), || B::construct(self))
+    }
 }
 
 impl<C> ConstructServices for C where C: Services {}
 
+/// Resolves a field of the bundle constructed by `#[derive(Construct)]`, recording the error for
+/// [`ConstructServices::resolve_bundle`] and [`Services::try_get`] on failure.
+#[doc(hidden)]
+pub fn resolve_field<S, C>(container: &C) -> Option<Arc<S>>
+where
+    S: ?Sized + Send + Sync + 'static,
+    C: Services,
+{
+    container.try_get::<S>().map_err(report::record).ok()
+}
+
+/// Dispatches [`register_if_constructable!`](crate::register_if_constructable) by autoref
+/// specialization: [`ConstructIfConstructable`] is implemented on the value and takes precedence
+/// when the service is constructable, while [`SkipIfNotConstructable`] is implemented on the
+/// reference and is picked otherwise.
+#[doc(hidden)]
+pub struct MaybeConstructor<S> {
+    _phantom: PhantomData<fn() -> S>,
+}
+
+impl<S> MaybeConstructor<S> {
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        Self {
+            _phantom: PhantomData,
+        }
+    }
+}
+
+#[doc(hidden)]
+pub trait ConstructIfConstructable<C> {
+    fn register(&self, container: &mut C) -> bool;
+}
+
+impl<S, C> ConstructIfConstructable<C> for MaybeConstructor<S>
+where
+    S: Construct<S, C> + 'static,
+    C: Services,
+{
+    fn register(&self, container: &mut C) -> bool {
+        container.construct::<S>();
+        true
+    }
+}
+
+#[doc(hidden)]
+pub trait SkipIfNotConstructable<C> {
+    fn register(&self, container: &mut C) -> bool;
+}
+
+impl<S, C> SkipIfNotConstructable<C> for &MaybeConstructor<S> {
+    fn register(&self, _container: &mut C) -> bool {
+        false
+    }
+}
+
+/// Constructs a service asynchronously from the services in the container.
+///
+/// # Cancellation safety
+///
+/// The future returned by [`AsyncConstruct::construct_async`] may be dropped at any `.await`
+/// point, e.g. when the client of an HTTP request disconnects. Implementations must not leave
+/// anything behind in that case: do not spawn detached tasks, and release acquired resources on
+/// drop. Sub-tasks that must run concurrently with the construction should be spawned with
+/// `scope::spawn` and resolved via `ScopedServices::scoped_get_async` (`tokio` feature), which
+/// aborts them when the resolution is cancelled.
+///
+/// # Reentrancy
+///
+/// Resolve the dependencies by [`AsyncServices::get_async`] within the construction. The
+/// synchronous [`Services::get`] can not resolve the services registered only asynchronously,
+/// and returns `None` for them; debug builds warn about it. Never block on the asynchronous
+/// resolution of a dependency there, e.g. by `BlockingServices::get_blocking` (`tokio`
+/// feature), as it may deadlock the runtime driving the construction.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` can not be constructed asynchronously from `{C}`",
+    label = "`AsyncConstruct` is not implemented for `{Self}`",
+    note = "implement `AsyncConstruct<{S}, {C}>` for `{Self}`, or implement it generically over `C: AsyncServices`"
+)]
+#[async_trait]
+pub trait AsyncConstruct<S = Self, C = ServiceContainer>: Send + Sync {
+    async fn construct_async(container: &C) -> Option<S>;
+}
+
+pub struct AsyncConstructor<S> {
+    _phantom: PhantomData<fn() -> S>,
+}
+
+impl<S> AsyncConstructor<S> {
+    pub fn new() -> Self {
+        Self {
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<S> Default for AsyncConstructor<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl<S, C> AsyncResolve<S, C> for AsyncConstructor<S>
+where
+    S: AsyncConstruct<S, C>,
+    C: Send + Sync,
+{
+    async fn async_resolve(&self, container: &C) -> Option<Arc<S>> {
+        Some(Arc::new(S::construct_async(container).await?))
+    }
+}
+
+pub trait AsyncConstructServices: AsyncServices {
+    fn construct_async<S>(&mut self)
+    where
+        S: AsyncConstruct<S, Self> + 'static,
+    {
+        self.put_async(AsyncResolver::new(AsyncConstructor::<S>::new()));
+    }
+}
+
+impl<C> AsyncConstructServices for C where C: AsyncServices {}
+
 #[cfg(test)]
 mod tests {
     use std::sync::Arc;
 
-    use crate::construct::{Construct, ConstructServices};
+    use async_trait::async_trait;
+
+    use crate::construct::{
+        resolve_field, AsyncConstruct, AsyncConstructServices, Construct, ConstructServices,
+        Constructor,
+    };
     use crate::singleton::SingletonServices;
-    use crate::{ServiceContainer, Services};
+    use crate::{
+        AsyncServices, KeyedContainer, ResolutionError, ResolutionKind, ServiceContainer, Services,
+    };
 
     struct Foo {
         name: String,
@@ -94,4 +309,293 @@ mod tests {
         let bar = container.get::<Bar>().unwrap();
         assert_eq!("Hello, Taro!".to_string(), bar.greet());
     }
+
+    struct Qux {
+        bar: Arc<Bar>,
+    }
+
+    impl<C> Construct<Self, C> for Qux
+    where
+        C: Services,
+    {
+        fn construct(container: &C) -> Option<Self> {
+            Some(Self {
+                bar: container.get()?,
+            })
+        }
+    }
+
+    #[test]
+    fn freeze_as_singletons() {
+        let mut container = ServiceContainer::default();
+
+        container.construct::<Qux>();
+        container.construct::<Bar>();
+        container.singleton(Foo {
+            name: "Taro".to_string(),
+        });
+
+        // Constructors create a new instance on every resolution.
+        let bar = container.get::<Bar>().unwrap();
+        assert!(!Arc::ptr_eq(&bar, &container.get::<Bar>().unwrap()));
+
+        container.freeze_as_singletons().unwrap();
+
+        // After freezing, the instances are shared, including the ones in the dependents.
+        let bar = container.get::<Bar>().unwrap();
+        assert!(Arc::ptr_eq(&bar, &container.get::<Bar>().unwrap()));
+        assert!(Arc::ptr_eq(&bar, &container.get::<Qux>().unwrap().bar));
+        assert_eq!(Some(ResolutionKind::Singleton), container.kind_of::<Bar>());
+    }
+
+    #[test]
+    fn freeze_as_singletons_other_container_type() {
+        let mut container = ServiceContainer::default();
+
+        container.construct::<Bar>();
+        container.put_for::<Qux, KeyedContainer<String>, _>(Constructor::<Qux>::new());
+        container.singleton(Foo {
+            name: "Taro".to_string(),
+        });
+
+        // Constructed by the container it is registered against, not frozen.
+        container.freeze_as_singletons().unwrap();
+        assert_eq!(Some(ResolutionKind::Singleton), container.kind_of::<Bar>());
+        assert_eq!(
+            Some(ResolutionKind::Constructor),
+            container.kind_of::<Qux>()
+        );
+    }
+
+    #[test]
+    fn freeze_as_singletons_missing_dependency() {
+        let mut container = ServiceContainer::default();
+
+        container.construct::<Bar>();
+
+        assert_eq!(
+            Err(ResolutionError::ResolverReturnedNone {
+                type_name: std::any::type_name::<Bar>(),
+            }),
+            container.freeze_as_singletons(),
+        );
+    }
+
+    struct Chicken {
+        _egg: Arc<Egg>,
+    }
+
+    struct Egg {
+        _chicken: Arc<Chicken>,
+    }
+
+    impl Construct for Chicken {
+        fn construct(container: &ServiceContainer) -> Option<Self> {
+            Some(Self {
+                _egg: container.get()?,
+            })
+        }
+    }
+
+    impl Construct for Egg {
+        fn construct(container: &ServiceContainer) -> Option<Self> {
+            Some(Self {
+                _chicken: container.get()?,
+            })
+        }
+    }
+
+    #[test]
+    fn freeze_as_singletons_circular_dependency() {
+        let mut container = ServiceContainer::default();
+
+        container.construct::<Chicken>();
+        container.construct::<Egg>();
+
+        // Circular dependencies do not overflow the stack, but fail to resolve.
+        assert!(container.get::<Chicken>().is_none());
+
+        let Err(ResolutionError::CircularDependency(cycle)) = container.freeze_as_singletons()
+        else {
+            panic!("the circular dependency must be detected");
+        };
+
+        assert_eq!(3, cycle.len());
+        assert_eq!(cycle.first(), cycle.last());
+    }
+
+    struct Baz {
+        bar: Arc<Bar>,
+    }
+
+    #[async_trait]
+    impl<C> AsyncConstruct<Self, C> for Baz
+    where
+        C: AsyncServices,
+    {
+        async fn construct_async(container: &C) -> Option<Self> {
+            Some(Self {
+                bar: container.get_async().await?,
+            })
+        }
+    }
+
+    #[derive(Default)]
+    struct Greeting {
+        message: String,
+    }
+
+    impl Construct for Greeting {
+        fn construct(container: &ServiceContainer) -> Option<Self> {
+            Some(Self {
+                message: container.get::<Bar>()?.greet(),
+            })
+        }
+    }
+
+    #[test]
+    fn get_or_construct() {
+        let mut container = ServiceContainer::default();
+
+        // Neither registered nor constructable, so falls back to the default.
+        assert!(container.get_or_construct::<Greeting>().is_none());
+        assert_eq!(
+            "",
+            container.get_or_construct_or_default::<Greeting>().message
+        );
+
+        container.construct::<Bar>();
+        container.singleton(Foo {
+            name: "Taro".to_string(),
+        });
+
+        // Constructed on the fly, without being cached.
+        let greeting = container.get_or_construct::<Greeting>().unwrap();
+        assert_eq!("Hello, Taro!", greeting.message);
+        assert!(!container.has::<Greeting>());
+        assert!(!Arc::ptr_eq(
+            &greeting,
+            &container.get_or_construct::<Greeting>().unwrap()
+        ));
+
+        // Registered services are resolved as usual.
+        container.singleton(Greeting {
+            message: "Hi!".to_string(),
+        });
+        assert_eq!(
+            "Hi!",
+            container.get_or_construct_or_default::<Greeting>().message
+        );
+    }
+
+    struct Welcome {
+        _foo: Arc<Foo>,
+    }
+
+    /// Resolves the fields as `#[derive(Construct)]` does.
+    impl Construct for Welcome {
+        fn construct(container: &ServiceContainer) -> Option<Self> {
+            Some(Self {
+                _foo: resolve_field(container)?,
+            })
+        }
+    }
+
+    #[test]
+    fn ensure() {
+        let mut container = ServiceContainer::default();
+
+        // The missing dependency is named, rather than the service.
+        container.construct::<Bar>();
+        assert_eq!(
+            Err(ResolutionError::NotRegistered {
+                type_name: std::any::type_name::<Foo>(),
+            }),
+            container.ensure::<Welcome>().map(|_| ()),
+        );
+        assert!(!container.has::<Welcome>());
+        assert!(container.ensure::<Greeting>().is_err());
+        assert!(!container.has::<Greeting>());
+
+        container.singleton(Foo {
+            name: "Taro".to_string(),
+        });
+
+        // Constructed once, and registered as a singleton.
+        let greeting = container.ensure::<Greeting>().unwrap();
+        assert_eq!("Hello, Taro!", greeting.message);
+        assert!(container.same_instance(&greeting));
+        assert!(Arc::ptr_eq(&greeting, &container.ensure().unwrap()));
+        assert_eq!(
+            Some(ResolutionKind::Singleton),
+            container.kind_of::<Greeting>()
+        );
+    }
+
+    #[test]
+    fn resolve_bundle() {
+        let mut container = ServiceContainer::default();
+
+        container.construct::<Bar>();
+        assert_eq!(
+            Err(ResolutionError::ResolverReturnedNone {
+                type_name: std::any::type_name::<Qux>(),
+            }),
+            container.resolve_bundle::<Qux>().map(|_| ()),
+        );
+
+        container.singleton(Foo {
+            name: "Taro".to_string(),
+        });
+
+        // The bundle is constructed, but not registered.
+        let qux = container.resolve_bundle::<Qux>().unwrap();
+        assert_eq!("Hello, Taro!".to_string(), qux.bar.greet());
+        assert!(!container.has::<Qux>());
+    }
+
+    struct Session {
+        in_async_resolution: bool,
+    }
+
+    #[async_trait]
+    impl AsyncConstruct for Session {
+        async fn construct_async(container: &ServiceContainer) -> Option<Self> {
+            // Resolved synchronously by mistake, which is warned about in debug builds.
+            assert!(container.get::<Baz>().is_none());
+
+            Some(Self {
+                in_async_resolution: crate::reentrancy::in_async_resolution(),
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn construct_async_resolving_sync() {
+        let mut container = ServiceContainer::default();
+
+        container.construct_async::<Baz>();
+        container.construct_async::<Session>();
+
+        let session = container.get_async::<Session>().await.unwrap();
+        assert!(session.in_async_resolution);
+        assert!(!crate::reentrancy::in_async_resolution());
+    }
+
+    #[tokio::test]
+    async fn construct_async() {
+        let mut container = ServiceContainer::default();
+
+        // Baz is constructed asynchronously, resolving Bar through the async path.
+        container.construct_async::<Baz>();
+        container.construct::<Bar>();
+        container.singleton(Foo {
+            name: "Taro".to_string(),
+        });
+
+        assert!(container.get::<Baz>().is_none());
+
+        let baz = container.get_async::<Baz>().await.unwrap();
+        assert_eq!("Hello, Taro!".to_string(), baz.bar.greet());
+    }
 }
@@ -0,0 +1,80 @@
+//! Detects circular dependencies between services being resolved on the current thread.
+
+use std::any::{type_name, TypeId};
+use std::cell::RefCell;
+
+use crate::{report, ResolutionError};
+
+thread_local! {
+    static RESOLVING: RefCell<Vec<Resolving>> = const { RefCell::new(vec![]) };
+}
+
+/// A service being resolved, by a resolver wrapping another one if `wrapping`.
+struct Resolving {
+    id: TypeId,
+    type_name: &'static str,
+    wrapping: bool,
+}
+
+/// Marks the service as being resolved until dropped.
+pub(crate) struct Guard;
+
+impl Drop for Guard {
+    fn drop(&mut self) {
+        RESOLVING.with(|r| r.borrow_mut().pop());
+    }
+}
+
+/// Enters the resolution of the service.
+/// Returns `None` and records the cycle if the service is already being resolved.
+///
+/// Entering it right within the wrapping resolver of the same service, i.e. as its inner
+/// resolver, is not a cycle. See [`enter_wrapping`].
+pub(crate) fn enter<S>() -> Option<Guard>
+where
+    S: ?Sized + 'static,
+{
+    push::<S>(false)
+}
+
+/// Enters the resolution of the service by a resolver delegating to an inner one, such as a
+/// lazy singleton, which may enter the service itself. Unlike [`enter`], entering it again within
+/// is always a cycle, e.g. when the wrapping resolver is resolved reentrantly.
+pub(crate) fn enter_wrapping<S>() -> Option<Guard>
+where
+    S: ?Sized + 'static,
+{
+    push::<S>(true)
+}
+
+fn push<S>(wrapping: bool) -> Option<Guard>
+where
+    S: ?Sized + 'static,
+{
+    let id = TypeId::of::<S>();
+
+    RESOLVING.with(|r| {
+        let mut resolving = r.borrow_mut();
+        let inner = !wrapping && resolving.last().is_some_and(|r| r.wrapping && r.id == id);
+
+        if let Some(i) = resolving.iter().position(|r| r.id == id).filter(|_| !inner) {
+            // Named once even if entered by both a wrapping resolver and its inner one.
+            let mut cycle = resolving[i..]
+                .iter()
+                .map(|r| r.type_name)
+                .collect::<Vec<_>>();
+            cycle.dedup();
+            cycle.push(type_name::<S>());
+            report::record(ResolutionError::CircularDependency(cycle));
+
+            return None;
+        }
+
+        resolving.push(Resolving {
+            id,
+            type_name: type_name::<S>(),
+            wrapping,
+        });
+        Some(Guard)
+    })
+}
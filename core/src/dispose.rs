@@ -0,0 +1,470 @@
+//! Cleanup of the services tied to the lifecycle of the container, e.g. flushing a buffer or
+//! closing a file, which `Drop` of the service can not express when it needs to be asynchronous
+//! or ordered.
+
+use std::any::{type_name, TypeId};
+use std::cmp::Reverse;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, PoisonError};
+
+use crate::{Bound, ResolutionKind, Resolve, ServiceContainer, ServiceId};
+
+type DisposeFuture = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+enum DisposeFn<S>
+where
+    S: ?Sized,
+{
+    Sync(Box<dyn FnOnce(&S) + Send>),
+    Async(Box<dyn FnOnce(Arc<S>) -> DisposeFuture + Send>),
+}
+
+/// The instance of the service and its cleanup, shared by the resolver and the container.
+struct Disposal<S>
+where
+    S: ?Sized,
+{
+    service: Mutex<Option<Arc<S>>>,
+    dispose: Mutex<Option<DisposeFn<S>>>,
+}
+
+impl<S> Disposal<S>
+where
+    S: ?Sized,
+{
+    /// Takes the service and its cleanup, if the service is created and not disposed yet.
+    fn take(&self) -> Option<(Arc<S>, DisposeFn<S>)> {
+        let service = self
+            .service
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take()?;
+        let dispose = self
+            .dispose
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take()?;
+
+        Some((service, dispose))
+    }
+}
+
+impl<S> Drop for Disposal<S>
+where
+    S: ?Sized,
+{
+    fn drop(&mut self) {
+        match self.take() {
+            Some((service, DisposeFn::Sync(dispose))) => dispose(&service),
+            Some((_, DisposeFn::Async(_))) => warn!(
+                "The service {} is dropped without its asynchronous cleanup; \
+                 call ServiceContainer::shutdown_async to run it",
+                type_name::<S>(),
+            ),
+            None => {}
+        }
+    }
+}
+
+/// A type-erased [`Disposal`] held by the container in the order of registration.
+trait Dispose: Send + Sync {
+    fn dispose(&self) -> Option<DisposeFuture>;
+}
+
+impl<S> Dispose for Disposal<S>
+where
+    S: ?Sized + Send + Sync + 'static,
+{
+    fn dispose(&self) -> Option<DisposeFuture> {
+        match self.take()? {
+            (service, DisposeFn::Sync(dispose)) => {
+                dispose(&service);
+                None
+            }
+            (service, DisposeFn::Async(dispose)) => Some(dispose(service)),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct Disposer {
+    id: TypeId,
+    phase: Option<u32>,
+    disposal: Arc<dyn Dispose>,
+}
+
+impl fmt::Debug for Disposer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Disposer")
+    }
+}
+
+/// Resolves the service by the inner resolver on the first resolution, and shares it
+/// afterwards, like [`LazySingleton`](crate::LazySingleton), keeping it for the cleanup.
+struct Disposable<S>
+where
+    S: ?Sized,
+{
+    inner: Box<dyn Resolve<S>>,
+    disposal: Arc<Disposal<S>>,
+}
+
+impl<S> Resolve<S> for Disposable<S>
+where
+    S: ?Sized + Send + Sync,
+{
+    fn resolve(&self, container: &ServiceContainer) -> Option<Arc<S>> {
+        if let Some(s) = &*self
+            .disposal
+            .service
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+        {
+            return Some(Arc::clone(s));
+        }
+
+        let resolved = self.inner.resolve(container)?;
+        let mut service = self
+            .disposal
+            .service
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        Some(Arc::clone(service.get_or_insert(resolved)))
+    }
+
+    fn kind(&self) -> ResolutionKind {
+        ResolutionKind::LazySingleton
+    }
+}
+
+/// The services are disposed when they are removed by [`ServiceContainer::remove`], replaced by
+/// another resolver, or when the container is dropped, but only once no clone of the container
+/// shares them anymore. Only the services created by then are disposed.
+///
+/// Dropping the container disposes the services in arbitrary order, and can not run the
+/// asynchronous cleanups. Use [`ServiceContainer::shutdown`] or
+/// [`ServiceContainer::shutdown_async`] to dispose them in the reverse order of registration,
+/// or in the phases assigned by [`ServiceContainer::set_shutdown_phase`].
+impl ServiceContainer {
+    /// Puts a service with a cleanup run on its disposal. The service is resolved by the
+    /// resolver on the first resolution and shared afterwards, so there is a single instance to
+    /// clean up.
+    pub fn put_with_dispose<S, R, F>(&mut self, resolver: R, dispose: F)
+    where
+        S: ?Sized + Send + Sync + 'static,
+        R: Resolve<S> + 'static,
+        F: FnOnce(&S) + Send + 'static,
+    {
+        self.put_disposable(resolver, DisposeFn::Sync(Box::new(dispose)));
+    }
+
+    /// Puts a service with an asynchronous cleanup, run by [`ServiceContainer::shutdown_async`].
+    /// See [`ServiceContainer::put_with_dispose`].
+    pub fn put_with_dispose_async<S, R, F, Fut>(&mut self, resolver: R, dispose: F)
+    where
+        S: ?Sized + Send + Sync + 'static,
+        R: Resolve<S> + 'static,
+        F: FnOnce(Arc<S>) -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.put_disposable(
+            resolver,
+            DisposeFn::Async(Box::new(move |s| Box::pin(dispose(s)))),
+        );
+    }
+
+    fn put_disposable<S, R>(&mut self, resolver: R, dispose: DisposeFn<S>)
+    where
+        S: ?Sized + Send + Sync + 'static,
+        R: Resolve<S> + 'static,
+    {
+        let disposal = Arc::new(Disposal {
+            service: Mutex::new(None),
+            dispose: Mutex::new(Some(dispose)),
+        });
+
+        // Put first, as putting drops the cleanup of the service replaced.
+        self.put_for::<S, Self, _>(Disposable {
+            inner: Box::new(resolver),
+            disposal: Arc::clone(&disposal),
+        });
+
+        self.disposers.push(Disposer {
+            id: TypeId::of::<S>(),
+            phase: None,
+            disposal,
+        });
+    }
+
+    /// Drops the cleanup of the service, once its resolver is replaced by one without it.
+    pub(crate) fn drop_disposer(&mut self, id: ServiceId) {
+        self.disposers.retain(|d| d.id != id);
+    }
+
+    /// Adds the cleanups registered by another container, replacing the ones of the same
+    /// services.
+    pub(crate) fn extend_disposers(&mut self, disposers: Vec<Disposer>) {
+        for disposer in disposers {
+            self.drop_disposer(disposer.id);
+            self.disposers.push(disposer);
+        }
+    }
+
+    /// Binds the service onto the interface, dropped in the shutdown phase. See
+    /// [`ServiceContainer::set_shutdown_phase`].
+    pub fn bind_in_phase<S>(&mut self, service: Arc<S>, phase: u32)
+    where
+        S: ?Sized + Send + Sync + 'static,
+    {
+        self.put_with_dispose(Bound::from(service), |_| {});
+        self.set_shutdown_phase::<S>(phase);
+    }
+
+    /// Assigns the service put with a cleanup to the shutdown phase, for sequencing the shutdown
+    /// precisely regardless of the order of registration, e.g. flushing the buffers in phase 1,
+    /// then closing the database in phase 2. Returns `false` if the service has no cleanup.
+    ///
+    /// [`ServiceContainer::shutdown`] and [`ServiceContainer::shutdown_async`] dispose the
+    /// services in ascending order of their phases, awaiting each asynchronous cleanup before the
+    /// next one, and the services of the same phase in the reverse order of registration. The
+    /// services without a phase are disposed after all the phased ones.
+    ///
+    /// ```
+    /// use std::sync::{Arc, Mutex};
+    ///
+    /// use ruice::{ServiceContainer, Services, Singleton};
+    ///
+    /// struct Buffer;
+    /// struct Database;
+    ///
+    /// let log = Arc::new(Mutex::new(vec![]));
+    /// let mut container = ServiceContainer::default();
+    ///
+    /// let l = Arc::clone(&log);
+    /// container.put_with_dispose(Singleton::new(Database), move |_| l.lock().unwrap().push("database"));
+    /// container.set_shutdown_phase::<Database>(2);
+    ///
+    /// let l = Arc::clone(&log);
+    /// container.put_with_dispose(Singleton::new(Buffer), move |_| l.lock().unwrap().push("buffer"));
+    /// container.set_shutdown_phase::<Buffer>(1);
+    ///
+    /// container.get::<Database>();
+    /// container.get::<Buffer>();
+    /// container.shutdown();
+    /// assert_eq!(vec!["buffer", "database"], *log.lock().unwrap());
+    /// ```
+    pub fn set_shutdown_phase<S>(&mut self, phase: u32) -> bool
+    where
+        S: ?Sized + 'static,
+    {
+        let id = TypeId::of::<S>();
+        let Some(disposer) = self.disposers.iter_mut().find(|d| d.id == id) else {
+            return false;
+        };
+
+        disposer.phase = Some(phase);
+        true
+    }
+
+    /// Orders the disposers to be popped in the order of disposal.
+    fn sort_disposers(&mut self) {
+        // Stable, so the disposers of the same phase are kept in the order of registration.
+        self.disposers
+            .sort_by_key(|d| Reverse((d.phase.is_none(), d.phase)));
+    }
+
+    /// Removes the service, both the synchronous and asynchronous resolvers of it. Returns
+    /// `false` if the service is not registered.
+    pub fn remove<S>(&mut self) -> bool
+    where
+        S: ?Sized + 'static,
+    {
+        let id = TypeId::of::<S>();
+        self.drop_disposer(id);
+        #[cfg(feature = "tokio")]
+        self.spawned.remove(&id);
+
+        let removed = self.services.remove(&id).is_some();
+        self.async_services.remove(&id).is_some() || removed
+    }
+
+    /// Drops the container, disposing the services in the reverse order of registration.
+    pub fn shutdown(mut self) {
+        self.sort_disposers();
+        while let Some(disposer) = self.disposers.pop() {
+            self.services.remove(&disposer.id);
+        }
+    }
+
+    /// Drops the container, disposing the services in the reverse order of registration, and
+    /// running the asynchronous cleanups too.
+    pub async fn shutdown_async(mut self) {
+        self.sort_disposers();
+        while let Some(disposer) = self.disposers.pop() {
+            self.services.remove(&disposer.id);
+
+            // Clones of the container still sharing the service dispose it later instead.
+            if Arc::strong_count(&disposer.disposal) > 1 {
+                continue;
+            }
+
+            if let Some(dispose) = disposer.disposal.dispose() {
+                dispose.await;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Construct, ConstructServices, Constructor, Services, Singleton};
+
+    type Log = Arc<Mutex<Vec<&'static str>>>;
+
+    struct Buffer;
+
+    struct Writer {
+        _buffer: Arc<Buffer>,
+    }
+
+    impl Construct for Writer {
+        fn construct(container: &ServiceContainer) -> Option<Self> {
+            Some(Self {
+                _buffer: container.get()?,
+            })
+        }
+    }
+
+    fn container(log: &Log) -> ServiceContainer {
+        let mut container = ServiceContainer::default();
+
+        let l = Arc::clone(log);
+        container.put_with_dispose(Singleton::new(Buffer), move |_| {
+            l.lock().unwrap().push("buffer")
+        });
+
+        let l = Arc::clone(log);
+        container.put_with_dispose(Constructor::<Writer>::new(), move |_| {
+            l.lock().unwrap().push("writer")
+        });
+
+        container
+    }
+
+    #[test]
+    fn shutdown() {
+        let log = Log::default();
+        let container = container(&log);
+
+        let writer = container.get::<Writer>().unwrap();
+        assert!(Arc::ptr_eq(&writer, &container.get::<Writer>().unwrap()));
+
+        // Disposed in the reverse order of registration, once no clone shares them.
+        let cloned = container.clone();
+        container.shutdown();
+        assert!(log.lock().unwrap().is_empty());
+
+        cloned.shutdown();
+        assert_eq!(vec!["writer", "buffer"], *log.lock().unwrap());
+    }
+
+    #[test]
+    fn remove() {
+        let log = Log::default();
+        let mut container = container(&log);
+
+        // Services not created are not disposed.
+        assert!(container.remove::<Writer>());
+        assert!(!container.remove::<Writer>());
+        assert!(log.lock().unwrap().is_empty());
+
+        container.get::<Buffer>().unwrap();
+        container.remove::<Buffer>();
+        assert_eq!(vec!["buffer"], *log.lock().unwrap());
+        assert!(!container.has::<Buffer>());
+    }
+
+    #[tokio::test]
+    async fn shutdown_async() {
+        let log = Log::default();
+        let mut container = container(&log);
+
+        let l = Arc::clone(&log);
+        container.put_with_dispose_async(Singleton::new("connection"), move |_| async move {
+            tokio::task::yield_now().await;
+            l.lock().unwrap().push("connection");
+        });
+
+        container.get::<Writer>().unwrap();
+        container.get::<&str>().unwrap();
+
+        container.shutdown_async().await;
+        assert_eq!(vec!["connection", "writer", "buffer"], *log.lock().unwrap());
+    }
+
+    struct Flusher(&'static str, Log);
+
+    impl Drop for Flusher {
+        fn drop(&mut self) {
+            self.1.lock().unwrap().push(self.0);
+        }
+    }
+
+    #[tokio::test]
+    async fn shutdown_in_phases() {
+        let log = Log::default();
+        let mut container = container(&log);
+
+        let l = Arc::clone(&log);
+        container.put_with_dispose_async(Singleton::new("connection"), move |_| async move {
+            tokio::task::yield_now().await;
+            l.lock().unwrap().push("connection");
+        });
+        container.set_shutdown_phase::<&str>(2);
+        container.bind_in_phase(Arc::new(Flusher("flusher", Arc::clone(&log))), 1);
+        assert!(container.set_shutdown_phase::<Buffer>(2));
+        assert!(!container.set_shutdown_phase::<String>(1));
+
+        container.get::<Writer>().unwrap();
+        container.get::<&str>().unwrap();
+
+        // Phase 1, phase 2 in the reverse order of registration, then the rest.
+        container.shutdown_async().await;
+        assert_eq!(
+            vec!["flusher", "connection", "buffer", "writer"],
+            *log.lock().unwrap()
+        );
+    }
+
+    #[test]
+    fn replaced() {
+        let log = Log::default();
+        let mut container = container(&log);
+
+        // The replaced service is disposed right away, and its cleanup is not kept.
+        container.get::<Writer>().unwrap();
+        container.construct::<Writer>();
+        assert_eq!(vec!["writer"], *log.lock().unwrap());
+        assert!(!container.set_shutdown_phase::<Writer>(1));
+
+        container.get::<Buffer>().unwrap();
+        let l = Arc::clone(&log);
+        container.register_group("group", move |c| {
+            c.put_with_dispose(Singleton::new(Buffer), move |_| {
+                l.lock().unwrap().push("grouped buffer")
+            });
+        });
+        assert_eq!(vec!["writer", "buffer"], *log.lock().unwrap());
+
+        container.get::<Buffer>().unwrap();
+        container.shutdown();
+        assert_eq!(
+            vec!["writer", "buffer", "grouped buffer"],
+            *log.lock().unwrap()
+        );
+    }
+}
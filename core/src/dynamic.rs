@@ -0,0 +1,89 @@
+use std::any::{Any, TypeId};
+use std::sync::Arc;
+
+use crate::ServiceContainer;
+
+/// An object-safe facade of the container, for passing it around as `&dyn DynServices` without
+/// depending on the concrete container type.
+pub trait DynServices: Send + Sync {
+    /// Returns whether the service with the type ID is registered or not.
+    fn has_any(&self, id: TypeId) -> bool;
+
+    /// Gets the service with the type ID, boxing the resolved `Arc<S>`.
+    /// Asynchronous services can not be retrieved using this method.
+    fn get_any(&self, id: TypeId) -> Option<Box<dyn Any + Send + Sync>>;
+}
+
+impl dyn DynServices + '_ {
+    /// Returns whether the service container has the specified service or not.
+    pub fn has<S>(&self) -> bool
+    where
+        S: ?Sized + 'static,
+    {
+        self.has_any(TypeId::of::<S>())
+    }
+
+    /// Gets the service from the service container.
+    pub fn get<S>(&self) -> Option<Arc<S>>
+    where
+        S: ?Sized + Send + Sync + 'static,
+    {
+        self.get_any(TypeId::of::<S>())?
+            .downcast::<Arc<S>>()
+            .ok()
+            .map(|s| *s)
+    }
+}
+
+impl ServiceContainer {
+    /// Gets the service with the type ID, resolving it against the given container.
+    /// This is the building block of [`DynServices`] for custom containers wrapping a
+    /// [`ServiceContainer`]; `#[derive(Services)]` generates the delegation under the `derive`
+    /// feature.
+    pub fn get_any_for(
+        &self,
+        id: TypeId,
+        container: &(dyn Any + Send + Sync),
+    ) -> Option<Box<dyn Any + Send + Sync>> {
+        self.services.get(&id)?.resolve_any(container)
+    }
+}
+
+impl DynServices for ServiceContainer {
+    fn has_any(&self, id: TypeId) -> bool {
+        self.services.contains_key(&id) || self.async_services.contains_key(&id)
+    }
+
+    fn get_any(&self, id: TypeId) -> Option<Box<dyn Any + Send + Sync>> {
+        self.get_any_for(id, self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SingletonServices;
+
+    struct Greeter {
+        message: String,
+    }
+
+    fn greet(services: &dyn DynServices) -> Option<String> {
+        services.get::<Greeter>().map(|g| g.message.clone())
+    }
+
+    #[test]
+    fn dyn_services() {
+        let mut container = ServiceContainer::default();
+
+        container.singleton(Greeter {
+            message: "Hello, world!".to_string(),
+        });
+
+        let services: &dyn DynServices = &container;
+        assert!(services.has::<Greeter>());
+        assert!(!services.has::<String>());
+        assert_eq!(Some("Hello, world!".to_string()), greet(services));
+        assert!(services.get::<String>().is_none());
+    }
+}
@@ -0,0 +1,229 @@
+use std::any::type_name;
+use std::error::Error;
+use std::fmt;
+use std::sync::Arc;
+
+/// An error occurred while resolving a service.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum ResolutionError {
+    #[error("The service {type_name} is not registered.")]
+    NotRegistered { type_name: &'static str },
+
+    #[error("The resolver for {type_name} returned no service.")]
+    ResolverReturnedNone { type_name: &'static str },
+
+    #[error("Circular dependency detected: {}", .0.join(" -> "))]
+    CircularDependency(Vec<&'static str>),
+
+    #[error("The service {type_name} is registered only asynchronously; resolve it by get_async.")]
+    WrongResolverKind { type_name: &'static str },
+
+    #[error("No Tokio runtime is available for blocking on the resolution of {type_name}.")]
+    RuntimeNotAvailable { type_name: &'static str },
+
+    #[error("Can not block on the resolution of {type_name} outside of a task of a multi-thread runtime.")]
+    BlockingInAsyncContext { type_name: &'static str },
+
+    #[error("The resolution of {type_name} timed out.")]
+    TimedOut { type_name: &'static str },
+
+    #[cfg(feature = "anyhow")]
+    #[error("Failed to construct {type_name}: {source}")]
+    ConstructionFailed {
+        type_name: &'static str,
+        source: crate::fallible::ConstructionError,
+    },
+
+    #[error("Failed to resolve {type_name}.")]
+    Custom {
+        type_name: &'static str,
+        source: CustomError,
+    },
+}
+
+impl ResolutionError {
+    /// Creates an error resolving the service, caused by the error, e.g. one returned by the
+    /// fallible function resolving it. The error is chained as the [`Error::source`].
+    pub fn custom<S>(error: impl Into<Box<dyn Error + Send + Sync>>) -> Self
+    where
+        S: ?Sized,
+    {
+        Self::Custom {
+            type_name: type_name::<S>(),
+            source: CustomError(Arc::from(error.into())),
+        }
+    }
+
+    /// Reports the error as the cause of the failure of the resolution in progress, for the
+    /// resolvers returning `None` on failure, so [`Services::try_get`] and
+    /// [`AsyncServices::try_get_async`] return it instead of
+    /// [`ResolutionError::ResolverReturnedNone`].
+    ///
+    /// The first error reported during a resolution wins, as it causes the others. Nothing is
+    /// reported outside of these resolutions, e.g. by [`Services::get`].
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use ruice::{BindServices, ResolutionError, ServiceContainer, Services};
+    ///
+    /// struct Config {
+    ///     port: u16,
+    /// }
+    ///
+    /// let mut container = ServiceContainer::default();
+    /// container.bind_by(|_| match "eighty".parse() {
+    ///     Ok(port) => Some(Arc::new(Config { port })),
+    ///     Err(error) => {
+    ///         ResolutionError::custom::<Config>(error).report();
+    ///         None
+    ///     }
+    /// });
+    ///
+    /// assert!(matches!(
+    ///     container.try_get::<Config>(),
+    ///     Err(ResolutionError::Custom { .. }),
+    /// ));
+    /// ```
+    ///
+    /// [`Services::try_get`]: crate::Services::try_get
+    /// [`Services::get`]: crate::Services::get
+    /// [`AsyncServices::try_get_async`]: crate::AsyncServices::try_get_async
+    pub fn report(self) {
+        crate::report::record(self);
+    }
+
+    pub(crate) fn returned_none<S>() -> Self
+    where
+        S: ?Sized,
+    {
+        Self::ResolverReturnedNone {
+            type_name: type_name::<S>(),
+        }
+    }
+}
+
+/// The error causing [`ResolutionError::Custom`], shared to keep [`ResolutionError`] cloneable.
+/// Errors are equal only if they are the same instance.
+#[derive(Clone)]
+pub struct CustomError(Arc<dyn Error + Send + Sync>);
+
+impl CustomError {
+    /// Returns the error causing the failure.
+    pub fn inner(&self) -> &(dyn Error + Send + Sync + 'static) {
+        self.0.as_ref()
+    }
+}
+
+impl fmt::Debug for CustomError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+impl fmt::Display for CustomError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl Error for CustomError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.0.source()
+    }
+}
+
+impl PartialEq for CustomError {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for CustomError {}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::bind::AsyncBindServices;
+    use crate::{AsyncServices, BindServices, ServiceContainer, Services};
+
+    #[derive(Debug, thiserror::Error)]
+    #[error("connection refused")]
+    struct ConnectionRefused;
+
+    struct Database;
+
+    #[test]
+    fn custom() {
+        let error = ResolutionError::custom::<Database>(ConnectionRefused);
+
+        // The source is chained rather than repeated.
+        assert_eq!(
+            format!("Failed to resolve {}.", type_name::<Database>()),
+            error.to_string(),
+        );
+        assert!(error
+            .source()
+            .is_some_and(|e| e.to_string() == "connection refused"));
+        assert_eq!(error, error.clone());
+        assert_ne!(
+            error,
+            ResolutionError::custom::<Database>(ConnectionRefused)
+        );
+    }
+
+    #[tokio::test]
+    async fn report() {
+        let mut container = ServiceContainer::default();
+        container.bind_by_async(|_| async {
+            tokio::task::yield_now().await;
+            ResolutionError::custom::<Database>(ConnectionRefused).report();
+            None::<Arc<Database>>
+        });
+        container.bind_by(|_| {
+            ResolutionError::custom::<u32>(ConnectionRefused).report();
+            None::<Arc<u32>>
+        });
+
+        // Reported across the suspension points of the resolution.
+        let error = container.try_get_async::<Database>().await.err().unwrap();
+        assert!(
+            matches!(error, ResolutionError::Custom { type_name: name, .. } if name == type_name::<Database>())
+        );
+        assert!(error.source().is_some());
+
+        assert_eq!(
+            Err(ResolutionError::NotRegistered {
+                type_name: type_name::<String>(),
+            }),
+            container.try_get_async::<String>().await.map(|_| ()),
+        );
+
+        // Reported by the synchronous resolvers too, whichever way they are resolved.
+        assert!(container.get::<u32>().is_none());
+        assert!(matches!(
+            container.try_get::<u32>(),
+            Err(ResolutionError::Custom { .. }),
+        ));
+        assert!(matches!(
+            container.try_get_async::<u32>().await,
+            Err(ResolutionError::Custom { .. }),
+        ));
+    }
+
+    #[test]
+    fn wrong_resolver_kind() {
+        let mut container = ServiceContainer::default();
+        container.bind_by_async(|_| async { Some(Arc::new(Database)) });
+
+        assert_eq!(
+            Err(ResolutionError::WrongResolverKind {
+                type_name: type_name::<Database>(),
+            }),
+            container.try_get::<Database>().map(|_| ()),
+        );
+    }
+}
@@ -0,0 +1,243 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::{ServiceContainer, Services, SingletonServices};
+
+/// Creates services from the container and arguments provided by the caller.
+///
+/// A factory taking multiple arguments receives them as a tuple, e.g. `(UserId, Locale)`.
+/// Factories without arguments take `()`.
+pub struct Factory<S, Args, C = ServiceContainer>
+where
+    S: ?Sized,
+{
+    #[allow(clippy::type_complexity)]
+    f: Arc<dyn Fn(&C, Args) -> Option<Arc<S>> + Send + Sync>,
+}
+
+impl<S, Args, C, F> From<F> for Factory<S, Args, C>
+where
+    S: ?Sized,
+    F: (Fn(&C, Args) -> Option<Arc<S>>) + Send + Sync + 'static,
+{
+    fn from(value: F) -> Self {
+        Self { f: Arc::new(value) }
+    }
+}
+
+impl<S, Args, C> Factory<S, Args, C>
+where
+    S: ?Sized,
+{
+    pub fn create(&self, container: &C, args: Args) -> Option<Arc<S>> {
+        (self.f)(container, args)
+    }
+}
+
+/// Creates services asynchronously from the container and arguments provided by the caller.
+///
+/// The arguments follow the conventions of [`Factory`]. Like [`AsyncBindBy`](crate::bind::AsyncBindBy),
+/// the function resolves the dependencies from the container before returning the future, which
+/// owns whatever it needs.
+pub struct AsyncFactory<S, Args, C = ServiceContainer>
+where
+    S: ?Sized,
+{
+    #[allow(clippy::type_complexity)]
+    f: Arc<dyn Fn(&C, Args) -> Pin<Box<dyn Future<Output = Option<Arc<S>>> + Send>> + Send + Sync>,
+}
+
+impl<S, Args, C, F, Fut> From<F> for AsyncFactory<S, Args, C>
+where
+    S: ?Sized,
+    F: (Fn(&C, Args) -> Fut) + Send + Sync + 'static,
+    Fut: Future<Output = Option<Arc<S>>> + Send + 'static,
+{
+    fn from(value: F) -> Self {
+        Self {
+            f: Arc::new(move |c, args| Box::pin(value(c, args))),
+        }
+    }
+}
+
+impl<S, Args, C> AsyncFactory<S, Args, C>
+where
+    S: ?Sized,
+{
+    pub async fn create(&self, container: &C, args: Args) -> Option<Arc<S>> {
+        (self.f)(container, args).await
+    }
+}
+
+fn create<S, Args, C>(container: &C, args: Args) -> Option<Arc<S>>
+where
+    S: ?Sized + Send + Sync + 'static,
+    Args: 'static,
+    C: Services + 'static,
+{
+    container
+        .get::<Factory<S, Args, C>>()?
+        .create(container, args)
+}
+
+async fn create_async<S, Args, C>(container: &C, args: Args) -> Option<Arc<S>>
+where
+    S: ?Sized + Send + Sync + 'static,
+    Args: 'static,
+    C: Services + 'static,
+{
+    container
+        .get::<AsyncFactory<S, Args, C>>()?
+        .create(container, args)
+        .await
+}
+
+#[async_trait]
+pub trait FactoryServices: Services {
+    /// Binds a factory creating the service from the container and caller-provided arguments.
+    fn bind_factory<S, Args, F>(&mut self, f: F)
+    where
+        S: ?Sized + Send + Sync + 'static,
+        Args: 'static,
+        F: (Fn(&Self, Args) -> Option<Arc<S>>) + Send + Sync + 'static,
+        Self: 'static,
+    {
+        self.singleton(Factory::<S, Args, Self>::from(f));
+    }
+
+    /// Creates the service using the factory bound for the type of `args`.
+    fn create<S>(&self, args: impl Sized + 'static) -> Option<Arc<S>>
+    where
+        S: ?Sized + Send + Sync + 'static,
+        Self: 'static,
+    {
+        create(self, args)
+    }
+
+    /// Binds a factory creating the service asynchronously from the container and
+    /// caller-provided arguments, e.g. a request-scoped session combining the injected
+    /// dependencies with the user ID of the request.
+    fn bind_factory_async<S, Args, F, Fut>(&mut self, f: F)
+    where
+        S: ?Sized + Send + Sync + 'static,
+        Args: 'static,
+        F: (Fn(&Self, Args) -> Fut) + Send + Sync + 'static,
+        Fut: Future<Output = Option<Arc<S>>> + Send + 'static,
+        Self: 'static,
+    {
+        self.singleton(AsyncFactory::<S, Args, Self>::from(f));
+    }
+
+    /// Creates the service asynchronously using the factory bound for the type of `args`.
+    /// Each call creates a new service.
+    async fn create_async<S>(&self, args: impl Sized + Send + 'static) -> Option<Arc<S>>
+    where
+        S: ?Sized + Send + Sync + 'static,
+        Self: 'static,
+    {
+        create_async(self, args).await
+    }
+}
+
+impl<C> FactoryServices for C where C: Services {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ServiceContainer;
+
+    struct Greeter {
+        prefix: String,
+    }
+
+    #[derive(Clone, Copy)]
+    struct UserId(u32);
+
+    struct UserSession {
+        greeting: String,
+    }
+
+    #[test]
+    fn create() {
+        let mut container = ServiceContainer::default();
+
+        container.singleton(Greeter {
+            prefix: "Hello".to_string(),
+        });
+
+        // Factories resolve their own dependencies from the container.
+        container.bind_factory::<UserSession, UserId, _>(|c, user_id| {
+            Some(Arc::new(UserSession {
+                greeting: format!("{}, user {}!", c.get::<Greeter>()?.prefix, user_id.0),
+            }))
+        });
+
+        // Multiple arguments are passed as a tuple.
+        container.bind_factory::<UserSession, (UserId, &'static str), _>(|_, (user_id, name)| {
+            Some(Arc::new(UserSession {
+                greeting: format!("Hi, {} ({})!", name, user_id.0),
+            }))
+        });
+
+        let session = container.create::<UserSession>(UserId(42)).unwrap();
+        assert_eq!("Hello, user 42!", session.greeting);
+
+        let session = container
+            .create::<UserSession>((UserId(42), "Taro"))
+            .unwrap();
+        assert_eq!("Hi, Taro (42)!", session.greeting);
+
+        // No factory is bound for these arguments.
+        assert!(container.create::<UserSession>("42").is_none());
+    }
+
+    #[tokio::test]
+    async fn create_async() {
+        let mut container = ServiceContainer::default();
+
+        container.singleton(Greeter {
+            prefix: "Hello".to_string(),
+        });
+
+        container.bind_factory_async::<UserSession, UserId, _, _>(|c, user_id| {
+            let greeter = c.get::<Greeter>();
+
+            async move {
+                tokio::task::yield_now().await;
+
+                Some(Arc::new(UserSession {
+                    greeting: format!("{}, user {}!", greeter?.prefix, user_id.0),
+                }))
+            }
+        });
+
+        let container = Arc::new(container);
+        let tasks = (0..8)
+            .map(|i| {
+                let c = Arc::clone(&container);
+                tokio::spawn(async move { c.create_async::<UserSession>(UserId(i)).await })
+            })
+            .collect::<Vec<_>>();
+
+        let mut sessions = vec![];
+        for task in tasks {
+            sessions.push(task.await.unwrap().unwrap());
+        }
+
+        // Each call creates an independent session.
+        for (i, session) in sessions.iter().enumerate() {
+            assert_eq!(format!("Hello, user {}!", i), session.greeting);
+        }
+        assert!(!Arc::ptr_eq(&sessions[0], &sessions[1]));
+
+        // The sync and async factories are bound separately.
+        assert!(container.create::<UserSession>(UserId(42)).is_none());
+        assert!(container
+            .create_async::<UserSession>((UserId(42), "Taro"))
+            .await
+            .is_none());
+    }
+}
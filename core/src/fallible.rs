@@ -0,0 +1,227 @@
+//! Fallible construction of services, reporting why a service could not be constructed.
+
+use std::any::type_name;
+use std::error::Error;
+use std::fmt;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use crate::{cycle, report, ResolutionError, ResolutionKind, Resolve, ServiceContainer, Services};
+
+/// The error returned by [`TryConstruct::try_construct`], shared to keep [`ResolutionError`]
+/// cloneable. Errors are equal only if they are the same instance.
+#[derive(Clone)]
+pub struct ConstructionError(Arc<anyhow::Error>);
+
+impl ConstructionError {
+    /// Returns the error returned by the constructor, with its full chain of causes.
+    pub fn inner(&self) -> &anyhow::Error {
+        &self.0
+    }
+}
+
+impl fmt::Debug for ConstructionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+impl fmt::Display for ConstructionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl Error for ConstructionError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.0.source()
+    }
+}
+
+impl PartialEq for ConstructionError {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for ConstructionError {}
+
+/// Constructs a service from the services in the container, like [`Construct`](crate::Construct),
+/// but with an error describing the failure.
+///
+/// The error is reported by [`Services::try_get`] as [`ResolutionError::ConstructionFailed`],
+/// keeping the context attached to it. Resolving the service by [`Services::get`] returns `None`
+/// on failure as usual.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` can not be constructed from `{C}`",
+    label = "`TryConstruct` is not implemented for `{Self}`",
+    note = "implement `TryConstruct<{S}, {C}>` for `{Self}`, or implement it generically over `C: Services`"
+)]
+pub trait TryConstruct<S = Self, C = ServiceContainer>: Send + Sync {
+    fn try_construct(container: &C) -> anyhow::Result<S>;
+}
+
+pub struct TryConstructor<S> {
+    _phantom: PhantomData<fn() -> S>,
+}
+
+impl<S> TryConstructor<S> {
+    pub fn new() -> Self {
+        Self {
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<S> Default for TryConstructor<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S, C> Resolve<S, C> for TryConstructor<S>
+where
+    S: TryConstruct<S, C> + 'static,
+{
+    fn resolve(&self, container: &C) -> Option<Arc<S>> {
+        let _guard = cycle::enter::<S>()?;
+
+        match S::try_construct(container) {
+            Ok(service) => Some(Arc::new(service)),
+            Err(error) => {
+                warn!("Failed to construct {}: {:#}", type_name::<S>(), error);
+
+                let error = ResolutionError::ConstructionFailed {
+                    type_name: type_name::<S>(),
+                    source: ConstructionError(Arc::new(error)),
+                };
+                report::record(error);
+
+                None
+            }
+        }
+    }
+
+    fn kind(&self) -> ResolutionKind {
+        ResolutionKind::Constructor
+    }
+}
+
+pub trait TryConstructServices: Services {
+    fn try_construct<S>(&mut self)
+    where
+        S: TryConstruct<S, Self> + 'static,
+    {
+        self.put(TryConstructor::<S>::new());
+    }
+}
+
+impl<C> TryConstructServices for C where C: Services {}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Context;
+
+    use super::*;
+    use crate::{Construct, ConstructServices, SingletonServices};
+
+    struct Config {
+        url: String,
+    }
+
+    struct Connection {
+        port: u16,
+    }
+
+    impl TryConstruct for Connection {
+        fn try_construct(container: &ServiceContainer) -> anyhow::Result<Self> {
+            let config = container.try_get::<Config>()?;
+            let (_, port) = config
+                .url
+                .rsplit_once(':')
+                .context("missing port in the connection string")?;
+
+            Ok(Self {
+                port: port.parse().context("failed to parse connection string")?,
+            })
+        }
+    }
+
+    struct Repository {
+        connection: Arc<Connection>,
+    }
+
+    impl Construct for Repository {
+        fn construct(container: &ServiceContainer) -> Option<Self> {
+            Some(Self {
+                connection: container.get()?,
+            })
+        }
+    }
+
+    #[test]
+    fn try_construct() {
+        let mut container = ServiceContainer::default();
+
+        container.try_construct::<Connection>();
+        container.construct::<Repository>();
+        container.singleton(Config {
+            url: "postgres://localhost:5432".to_string(),
+        });
+
+        assert_eq!(5432, container.get::<Repository>().unwrap().connection.port);
+    }
+
+    #[test]
+    fn try_construct_failure() {
+        let mut container = ServiceContainer::default();
+
+        container.try_construct::<Connection>();
+        container.construct::<Repository>();
+        container.singleton(Config {
+            url: "postgres://localhost:port".to_string(),
+        });
+
+        // The failure is reported for the dependents too.
+        let Err(ResolutionError::ConstructionFailed { type_name, source }) =
+            container.try_get::<Repository>()
+        else {
+            panic!("the construction failure must be reported");
+        };
+
+        assert_eq!(std::any::type_name::<Connection>(), type_name);
+        assert_eq!(
+            vec![
+                "failed to parse connection string",
+                "invalid digit found in string",
+            ],
+            source
+                .inner()
+                .chain()
+                .map(|e| e.to_string())
+                .collect::<Vec<_>>(),
+        );
+        assert!(container.get::<Connection>().is_none());
+    }
+
+    #[test]
+    fn try_construct_missing_dependency() {
+        let mut container = ServiceContainer::default();
+
+        container.try_construct::<Connection>();
+
+        let Err(ResolutionError::ConstructionFailed { source, .. }) =
+            container.try_get::<Connection>()
+        else {
+            panic!("the construction failure must be reported");
+        };
+
+        // The resolution error of the dependency is kept in the chain.
+        assert_eq!(
+            Some(&ResolutionError::NotRegistered {
+                type_name: std::any::type_name::<Config>(),
+            }),
+            source.inner().downcast_ref::<ResolutionError>(),
+        );
+    }
+}
@@ -0,0 +1,161 @@
+use std::sync::Arc;
+
+use crate::{
+    AsyncServices, AsyncTaggedServices, ResolutionError, ServiceContainer, ServiceVisitor,
+    Services, TaggedServices,
+};
+
+/// A container frozen after the wiring, resolving the services but no longer accepting them.
+///
+/// Only the read side of the container is available, so sharing it by an [`Arc`], e.g. among
+/// the request handlers, guarantees at compile time that nothing is registered while serving.
+/// The services still behave as registered: lazy singletons are created on their first
+/// resolution, and one-shot services are consumed.
+///
+/// ```
+/// use std::sync::Arc;
+///
+/// use ruice::{FrozenContainer, ServiceContainer, SingletonServices};
+///
+/// struct Config;
+///
+/// let mut container = ServiceContainer::default();
+/// container.singleton(Config);
+///
+/// let container: Arc<FrozenContainer> = Arc::new(container.freeze());
+/// assert!(container.get::<Config>().is_some());
+/// ```
+#[derive(Debug, Default)]
+pub struct FrozenContainer {
+    inner: ServiceContainer,
+}
+
+impl FrozenContainer {
+    /// Returns whether the service is registered. See [`Services::has`].
+    pub fn has<S>(&self) -> bool
+    where
+        S: ?Sized + 'static,
+    {
+        self.inner.has::<S>()
+    }
+
+    /// Gets the service. See [`Services::get`].
+    pub fn get<S>(&self) -> Option<Arc<S>>
+    where
+        S: ?Sized + Send + Sync + 'static,
+    {
+        self.inner.get::<S>()
+    }
+
+    /// Gets the service, reporting why it could not be resolved. See [`Services::try_get`].
+    pub fn try_get<S>(&self) -> Result<Arc<S>, ResolutionError>
+    where
+        S: ?Sized + Send + Sync + 'static,
+    {
+        self.inner.try_get::<S>()
+    }
+
+    /// Borrows the singleton service. See [`ServiceContainer::get_ref`].
+    pub fn get_ref<S>(&self) -> Option<&Arc<S>>
+    where
+        S: ?Sized + Send + Sync + 'static,
+    {
+        self.inner.get_ref::<S>()
+    }
+
+    /// Gets the service asynchronously. See [`AsyncServices::get_async`].
+    pub async fn get_async<S>(&self) -> Option<Arc<S>>
+    where
+        S: ?Sized + Send + Sync + 'static,
+    {
+        self.inner.get_async::<S>().await
+    }
+
+    /// Gets all the services put under the tag. See [`TaggedServices::get_tagged`].
+    pub fn get_tagged<Tag>(&self) -> Vec<Arc<Tag>>
+    where
+        Tag: ?Sized + Send + Sync + 'static,
+    {
+        self.inner.get_tagged::<Tag>()
+    }
+
+    /// Gets all the services put under the tag asynchronously.
+    /// See [`AsyncTaggedServices::get_tagged_async`].
+    pub async fn get_tagged_async<Tag>(&self) -> Vec<Arc<Tag>>
+    where
+        Tag: ?Sized + Send + Sync + 'static,
+    {
+        self.inner.get_tagged_async::<Tag>().await
+    }
+
+    /// Returns the type names of the registered services.
+    /// See [`ServiceContainer::registered_type_names`].
+    pub fn registered_type_names(&self) -> Vec<&'static str> {
+        self.inner.registered_type_names()
+    }
+
+    /// Calls the visitor for every registration. See [`Services::visit`].
+    pub fn visit(&self, visitor: &mut impl ServiceVisitor) {
+        self.inner.visit(visitor);
+    }
+}
+
+impl ServiceContainer {
+    /// Freezes the container after the wiring, so it can only resolve the services from now on.
+    /// See [`FrozenContainer`].
+    pub fn freeze(self) -> FrozenContainer {
+        FrozenContainer { inner: self }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Bound, Construct, SingletonServices};
+
+    trait Plugin: Send + Sync {}
+
+    struct Auth;
+
+    impl Plugin for Auth {}
+
+    struct Config;
+
+    struct Repository {
+        _config: Arc<Config>,
+    }
+
+    impl Construct for Repository {
+        fn construct(container: &ServiceContainer) -> Option<Self> {
+            Some(Self {
+                _config: container.get()?,
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn freeze() {
+        let mut container = ServiceContainer::default();
+
+        container.singleton(Config);
+        container.lazy_singleton::<Repository>();
+        container.put_tagged::<dyn Plugin>(Arc::new(Auth));
+        container.put_tagged_async::<dyn Plugin, _>(Bound::from(Arc::new(Auth) as Arc<dyn Plugin>));
+
+        let container = Arc::new(container.freeze());
+
+        assert!(container.has::<Repository>());
+        let repository = container.get::<Repository>().unwrap();
+        assert!(Arc::ptr_eq(
+            &repository,
+            &container.get_async::<Repository>().await.unwrap()
+        ));
+        assert!(container.get_ref::<Config>().is_some());
+        assert_eq!(1, container.get_tagged::<dyn Plugin>().len());
+        assert_eq!(1, container.get_tagged_async::<dyn Plugin>().await.len());
+        assert!(matches!(
+            container.try_get::<String>(),
+            Err(ResolutionError::NotRegistered { .. })
+        ));
+    }
+}
@@ -0,0 +1,73 @@
+use std::sync::Arc;
+
+use crate::{BindServices, Services};
+
+/// The service type of the behaviors bound by [`FnServices::bind_boxed_fn`].
+///
+/// Functions taking multiple arguments receive them as a tuple, e.g. `(&str, usize)`, and ones
+/// without arguments take `()`. Outputs which can not be named, such as `impl Iterator` or
+/// `impl Future`, are boxed into trait objects by the function, e.g.
+/// `Pin<Box<dyn Future<Output = T> + Send>>`.
+pub type ServiceFn<Args, Out> = dyn Fn(Args) -> Out + Send + Sync;
+
+pub trait FnServices: Services {
+    /// Binds a behavior, such as a validation function or a formatter, resolvable by
+    /// [`FnServices::get_fn`] with the same argument and output types.
+    fn bind_boxed_fn<Args, Out, F>(&mut self, f: F)
+    where
+        Args: 'static,
+        Out: 'static,
+        F: Fn(Args) -> Out + Send + Sync + 'static,
+    {
+        self.bind::<ServiceFn<Args, Out>>(Arc::new(f));
+    }
+
+    /// Gets the behavior bound by [`FnServices::bind_boxed_fn`].
+    fn get_fn<Args, Out>(&self) -> Option<Arc<ServiceFn<Args, Out>>>
+    where
+        Args: 'static,
+        Out: 'static,
+    {
+        self.get::<ServiceFn<Args, Out>>()
+    }
+}
+
+impl<C> FnServices for C where C: Services {}
+
+#[cfg(test)]
+mod tests {
+    use std::future::Future;
+    use std::pin::Pin;
+
+    use super::*;
+    use crate::ServiceContainer;
+
+    type BoxIterator = Box<dyn Iterator<Item = String>>;
+    type BoxFuture<T> = Pin<Box<dyn Future<Output = T> + Send>>;
+
+    #[tokio::test]
+    async fn bind_boxed_fn() {
+        let mut container = ServiceContainer::default();
+
+        container.bind_boxed_fn(|name: String| !name.is_empty());
+        container.bind_boxed_fn(|(name, times): (String, usize)| -> BoxIterator {
+            Box::new(std::iter::repeat(name).take(times))
+        });
+        container.bind_boxed_fn(|name: String| -> BoxFuture<String> {
+            Box::pin(async move { format!("Hello, {}!", name) })
+        });
+
+        let validate = container.get_fn::<String, bool>().unwrap();
+        assert!(validate("Taro".to_string()));
+        assert!(!validate(String::new()));
+
+        let repeat = container.get_fn::<(String, usize), BoxIterator>().unwrap();
+        assert_eq!(2, repeat(("Taro".to_string(), 2)).count());
+
+        let greet = container.get_fn::<String, BoxFuture<String>>().unwrap();
+        assert_eq!("Hello, Taro!", greet("Taro".to_string()).await);
+
+        // Behaviors are distinguished by their argument and output types.
+        assert!(container.get_fn::<String, String>().is_none());
+    }
+}
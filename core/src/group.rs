@@ -0,0 +1,303 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::{AsyncEntry, Entry, ServiceContainer, ServiceId};
+
+/// The services registered by a group, kept for enabling and disabling them later.
+#[derive(Debug, Clone)]
+pub(crate) struct Group {
+    name: String,
+    enabled: bool,
+    services: HashMap<ServiceId, Arc<dyn Entry>>,
+    async_services: HashMap<ServiceId, Arc<dyn AsyncEntry>>,
+}
+
+/// Removes the entries of the group from the container, if they were not overwritten since then.
+/// The entries of the other groups are restored in their place, the last enabled one winning.
+/// The collections merged with the ones of the group lose the members of the group instead.
+fn remove<'a, E>(
+    current: &mut HashMap<ServiceId, Arc<E>>,
+    own: &HashMap<ServiceId, Arc<E>>,
+    others: impl Iterator<Item = &'a HashMap<ServiceId, Arc<E>>> + Clone,
+    unmerged: impl Fn(&E, &E) -> Option<Arc<E>>,
+) where
+    E: ?Sized + 'a,
+{
+    for (id, entry) in own {
+        match current.get(id) {
+            Some(e) if Arc::as_ptr(e) as *const () == Arc::as_ptr(entry) as *const () => {}
+            Some(e) => {
+                if let Some(e) = unmerged(e, entry) {
+                    current.insert(*id, e);
+                }
+                continue;
+            }
+            None => continue,
+        }
+
+        match others.clone().filter_map(|o| o.get(id)).last() {
+            Some(e) => current.insert(*id, Arc::clone(e)),
+            None => current.remove(id),
+        };
+    }
+}
+
+impl ServiceContainer {
+    /// Registers the services into a named group, which can be disabled and enabled later as a
+    /// whole. Registering a group with the same name again replaces it.
+    ///
+    /// When multiple groups register the same service, the last enabled one wins. Disabling a
+    /// group restores the service of the other group, while services registered outside of the
+    /// groups are never restored once overwritten by a group. Tagged collections are merged
+    /// instead: the members put by the group are added to the ones put outside of it or by the
+    /// other groups, and removed when disabling the group.
+    ///
+    /// The group is registered with the configuration of the container, such as its
+    /// [`ResolutionPolicy`](crate::ResolutionPolicy) and the registration hook. The cleanups,
+    /// the casts, and the eager resolution failures registered by the group are kept after
+    /// disabling it.
+    ///
+    /// The services are registered into a [`ServiceContainer`], so they are resolved against
+    /// it. For custom containers wrapping one, register them by [`ServiceContainer::put_for`]
+    /// with the custom container type instead.
+    pub fn register_group<F>(&mut self, name: impl Into<String>, f: F)
+    where
+        F: FnOnce(&mut ServiceContainer),
+    {
+        let name = name.into();
+        self.disable_group(&name);
+        self.groups.retain(|g| g.name != name);
+
+        let mut container = ServiceContainer {
+            registration_hook: self.registration_hook.clone(),
+            policy: self.policy,
+            #[cfg(feature = "tokio")]
+            default_async_timeout: self.default_async_timeout,
+            #[cfg(feature = "tokio")]
+            runtime_handle: self.runtime_handle.clone(),
+            ..Default::default()
+        };
+        f(&mut container);
+        self.extend_disposers(container.disposers);
+        self.casts.extend(container.casts);
+        #[cfg(feature = "tokio")]
+        self.spawned.extend(container.spawned);
+        for id in container.eager_failures {
+            if !self.eager_failures.contains(&id) {
+                self.eager_failures.push(id);
+            }
+        }
+
+        self.groups.push(Group {
+            name: name.clone(),
+            enabled: false,
+            services: container.services.into_inner(),
+            async_services: container.async_services.into_inner(),
+        });
+
+        self.enable_group(&name);
+    }
+
+    /// Enables the group, putting its services back to the container.
+    /// Returns `false` if the group is not registered or already enabled.
+    pub fn enable_group(&mut self, name: &str) -> bool {
+        let Some(i) = self
+            .groups
+            .iter()
+            .position(|g| g.name == name && !g.enabled)
+        else {
+            return false;
+        };
+
+        let mut group = self.groups.remove(i);
+        group.enabled = true;
+
+        for (id, entry) in &group.services {
+            let merged = self
+                .services
+                .get(id)
+                .and_then(|e| e.merged(entry.as_ref(), false));
+            self.services
+                .insert(*id, merged.unwrap_or_else(|| Arc::clone(entry)));
+        }
+        self.async_services.extend(
+            group
+                .async_services
+                .iter()
+                .map(|(id, e)| (*id, Arc::clone(e))),
+        );
+
+        self.groups.push(group);
+        true
+    }
+
+    /// Disables the group, removing its services from the container.
+    /// Returns `false` if the group is not registered or already disabled.
+    pub fn disable_group(&mut self, name: &str) -> bool {
+        let Some(i) = self.groups.iter().position(|g| g.name == name && g.enabled) else {
+            return false;
+        };
+
+        self.groups[i].enabled = false;
+
+        let group = &self.groups[i];
+        let others = self.groups.iter().filter(|g| g.enabled);
+
+        remove(
+            &mut self.services,
+            &group.services,
+            others.clone().map(|g| &g.services),
+            |e, own| e.merged(own, true),
+        );
+        remove(
+            &mut self.async_services,
+            &group.async_services,
+            others.map(|g| &g.async_services),
+            |_, _| None,
+        );
+
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::{
+        Construct, ConstructServices, ResolutionPolicy, ServiceContainer, Services,
+        SingletonServices, TaggedServices,
+    };
+
+    struct Greeter {
+        message: &'static str,
+    }
+
+    fn greet(container: &ServiceContainer) -> Option<&'static str> {
+        container.get::<Greeter>().map(|g| g.message)
+    }
+
+    #[test]
+    fn groups() {
+        let mut container = ServiceContainer::default();
+
+        container.register_group("billing", |c| {
+            c.singleton(Greeter { message: "billing" });
+            c.singleton(42_u32);
+        });
+        container.register_group("analytics", |c| {
+            c.singleton(Greeter {
+                message: "analytics",
+            });
+        });
+
+        // The last enabled group wins.
+        assert_eq!(Some("analytics"), greet(&container));
+
+        // Disabling a group restores the binding of the other group.
+        assert!(container.disable_group("analytics"));
+        assert!(!container.disable_group("analytics"));
+        assert_eq!(Some("billing"), greet(&container));
+
+        assert!(container.disable_group("billing"));
+        assert!(!container.has::<Greeter>());
+        assert!(!container.has::<u32>());
+
+        assert!(container.enable_group("billing"));
+        assert_eq!(Some("billing"), greet(&container));
+        assert_eq!(42, *container.get::<u32>().unwrap());
+
+        assert!(container.enable_group("analytics"));
+        assert_eq!(Some("analytics"), greet(&container));
+
+        // Disabling the group does not remove bindings overwritten outside the group.
+        container.singleton(Greeter { message: "custom" });
+        container.disable_group("analytics");
+        assert_eq!(Some("custom"), greet(&container));
+
+        assert!(!container.enable_group("unknown"));
+    }
+
+    trait Plugin: Send + Sync {
+        fn name(&self) -> &'static str;
+    }
+
+    struct Named(&'static str);
+
+    impl Plugin for Named {
+        fn name(&self) -> &'static str {
+            self.0
+        }
+    }
+
+    fn plugins(container: &ServiceContainer) -> Vec<&'static str> {
+        container
+            .get_tagged::<dyn Plugin>()
+            .iter()
+            .map(|p| p.name())
+            .collect()
+    }
+
+    #[test]
+    fn tagged_in_groups() {
+        let mut container = ServiceContainer::default();
+
+        container.put_tagged::<dyn Plugin>(Arc::new(Named("core")));
+        container.register_group("billing", |c| {
+            c.put_tagged::<dyn Plugin>(Arc::new(Named("billing")));
+        });
+        container.register_group("analytics", |c| {
+            c.put_tagged::<dyn Plugin>(Arc::new(Named("analytics")));
+        });
+        container.put_tagged::<dyn Plugin>(Arc::new(Named("audit")));
+
+        // Merged with the members put outside of the groups.
+        assert_eq!(
+            vec!["core", "billing", "analytics", "audit"],
+            plugins(&container)
+        );
+
+        assert!(container.disable_group("billing"));
+        assert_eq!(vec!["core", "analytics", "audit"], plugins(&container));
+
+        assert!(container.disable_group("analytics"));
+        assert_eq!(vec!["core", "audit"], plugins(&container));
+
+        assert!(container.enable_group("billing"));
+        assert_eq!(vec!["core", "audit", "billing"], plugins(&container));
+    }
+
+    #[test]
+    fn configuration_in_groups() {
+        struct Config;
+        struct Read;
+
+        let mut container = ServiceContainer::default().with_policy(ResolutionPolicy::Eager);
+
+        container.register_group("replica", |c| {
+            // Resolved eagerly, failing without the dependency outside of the group.
+            c.construct::<Greeting>();
+            c.singleton_tagged::<_, Read>(Config);
+            c.register_cast::<Named, dyn Plugin>(|n| n);
+        });
+
+        assert!(container.get_tagged_type::<Config, Read>().is_some());
+        assert!(container.resolve_eager().is_err());
+
+        container.singleton(Named("greeting"));
+        assert!(container.resolve_eager().is_ok());
+        assert_eq!(Some("greeting"), container.get::<Greeting>().map(|g| g.0));
+        assert_eq!(1, container.get_all_capabilities::<dyn Plugin>().len());
+
+        assert!(container.disable_group("replica"));
+        assert!(!container.has_tagged_type::<Config, Read>());
+    }
+
+    struct Greeting(&'static str);
+
+    impl Construct for Greeting {
+        fn construct(container: &ServiceContainer) -> Option<Self> {
+            Some(Self(container.get::<Named>()?.0))
+        }
+    }
+}
@@ -0,0 +1,269 @@
+use std::any::TypeId;
+use std::fmt;
+use std::sync::{Arc, Weak};
+
+use crate::{Resolve, ServiceContainer, Services, SingletonServices};
+
+/// A weak reference to the container, for services resolving their dependencies at method-call
+/// time rather than construction time.
+///
+/// The handle does not keep the container alive, so a service holding it does not form a
+/// reference cycle with the container. Failing to upgrade means the container was dropped, which
+/// usually happens during shutdown and should be handled gracefully.
+pub struct ContainerHandle<C = ServiceContainer> {
+    container: Weak<C>,
+}
+
+impl<C> ContainerHandle<C> {
+    /// Upgrades to a strong reference, or returns `None` if the container was dropped.
+    pub fn upgrade(&self) -> Option<Arc<C>> {
+        self.container.upgrade()
+    }
+}
+
+impl<C> ContainerHandle<C>
+where
+    C: Services,
+{
+    /// Gets the service from the container, or returns `None` if the container was dropped.
+    pub fn get<S>(&self) -> Option<Arc<S>>
+    where
+        S: ?Sized + Send + Sync + 'static,
+    {
+        self.upgrade()?.get::<S>()
+    }
+}
+
+impl<C> Clone for ContainerHandle<C> {
+    fn clone(&self) -> Self {
+        Self {
+            container: Weak::clone(&self.container),
+        }
+    }
+}
+
+impl<C> fmt::Debug for ContainerHandle<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ContainerHandle")
+            .field("alive", &(self.container.strong_count() > 0))
+            .finish()
+    }
+}
+
+/// Resolves the container itself by upgrading the weak reference to it.
+struct SelfBound<C> {
+    container: Weak<C>,
+}
+
+impl<C> Resolve<C, C> for SelfBound<C>
+where
+    C: Send + Sync,
+{
+    fn resolve(&self, _container: &C) -> Option<Arc<C>> {
+        self.container.upgrade()
+    }
+}
+
+impl ServiceContainer {
+    /// Creates a container wired by the function, then bound to itself. See
+    /// [`HandleServices::bind_self`].
+    ///
+    /// The container can not be mutated once bound, so the services are registered by the
+    /// function beforehand:
+    ///
+    /// ```
+    /// use ruice::{ServiceContainer, Services, SingletonServices};
+    ///
+    /// let container = ServiceContainer::new_self_bound(|c| c.singleton("Taro"));
+    ///
+    /// let this = container.get::<ServiceContainer>().unwrap();
+    /// assert_eq!("Taro", *this.get::<&str>().unwrap());
+    /// ```
+    pub fn new_self_bound<F>(f: F) -> Arc<Self>
+    where
+        F: FnOnce(&mut Self),
+    {
+        let mut container = Self::default();
+        f(&mut container);
+
+        container.bind_self()
+    }
+
+    /// Gets the service, ensuring that none of the services created by the resolution holds a
+    /// [`ContainerHandle`] or the container itself, so the returned service is independent of
+    /// the container and can be moved to a background task outliving it, e.g. by
+    /// `tokio::spawn`.
+    ///
+    /// The service is resolved against a copy of the container without them. Returns `None` if
+    /// the service or any of its dependencies requires them. Note that the services already
+    /// created and shared by singleton resolvers are returned as is.
+    pub fn resolve_detached<S>(&self) -> Option<Arc<S>>
+    where
+        S: ?Sized + Send + Sync + 'static,
+    {
+        let mut detached = self.clone();
+        for id in [TypeId::of::<ContainerHandle>(), TypeId::of::<Self>()] {
+            detached.services.remove(&id);
+            detached.async_services.remove(&id);
+        }
+
+        detached.get::<S>()
+    }
+}
+
+pub trait HandleServices: Services {
+    /// Moves the container into an [`Arc`], registering a [`ContainerHandle`] to it as a
+    /// singleton service.
+    fn bind_self_weak(self) -> Arc<Self>
+    where
+        Self: 'static,
+    {
+        Arc::new_cyclic(|container| {
+            let mut this = self;
+            this.singleton(ContainerHandle {
+                container: Weak::clone(container),
+            });
+            this
+        })
+    }
+
+    /// Moves the container into an [`Arc`], registering the container itself as a service, so
+    /// constructors can resolve it for late-bound lookups.
+    ///
+    /// The container holds only a weak reference to itself, so it is dropped as usual. The
+    /// resolved container keeps it alive though, so do not store it in singleton services, as
+    /// that forms a reference cycle; prefer [`ContainerHandle`] for them.
+    fn bind_self(self) -> Arc<Self>
+    where
+        Self: 'static,
+    {
+        Arc::new_cyclic(|container| {
+            let mut this = self;
+            this.put(SelfBound {
+                container: Weak::clone(container),
+            });
+            this
+        })
+    }
+}
+
+impl<C> HandleServices for C where C: Services {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Construct, ConstructServices};
+
+    struct Clock {
+        _scheduler: Arc<Scheduler>,
+    }
+
+    impl Clock {
+        fn now(&self) -> u64 {
+            42
+        }
+    }
+
+    impl Construct for Clock {
+        fn construct(container: &ServiceContainer) -> Option<Self> {
+            Some(Self {
+                _scheduler: container.get()?,
+            })
+        }
+    }
+
+    struct Scheduler {
+        container: Arc<ContainerHandle>,
+    }
+
+    impl Scheduler {
+        fn now(&self) -> Option<u64> {
+            Some(self.container.get::<Clock>()?.now())
+        }
+    }
+
+    impl Construct for Scheduler {
+        fn construct(container: &ServiceContainer) -> Option<Self> {
+            Some(Self {
+                container: container.get()?,
+            })
+        }
+    }
+
+    struct Calendar;
+
+    #[test]
+    fn bind_self_weak() {
+        let mut container = ServiceContainer::default();
+
+        // The clock depends on the scheduler, which resolves the clock only when called.
+        container.construct::<Clock>();
+        container.construct::<Scheduler>();
+        let container = container.bind_self_weak();
+
+        let scheduler = container.get::<Scheduler>().unwrap();
+        assert_eq!(Some(42), scheduler.now());
+
+        // The handle does not keep the container alive.
+        drop(container);
+        assert!(scheduler.container.upgrade().is_none());
+        assert_eq!(None, scheduler.now());
+    }
+
+    #[test]
+    fn resolve_detached() {
+        let mut container = ServiceContainer::default();
+
+        container.construct::<Clock>();
+        container.construct::<Scheduler>();
+        container.singleton(Calendar);
+        let container = container.bind_self_weak();
+
+        // The scheduler holds the handle, so it can not be detached, nor the clock using it.
+        assert!(container.resolve_detached::<Scheduler>().is_none());
+        assert!(container.resolve_detached::<Clock>().is_none());
+        assert!(container.resolve_detached::<Calendar>().is_some());
+
+        // The container itself is left intact.
+        assert!(container.get::<Scheduler>().is_some());
+    }
+
+    struct Job {
+        container: Arc<ServiceContainer>,
+    }
+
+    impl Construct for Job {
+        fn construct(container: &ServiceContainer) -> Option<Self> {
+            Some(Self {
+                container: container.get()?,
+            })
+        }
+    }
+
+    #[test]
+    fn bind_self() {
+        let mut container = ServiceContainer::default();
+
+        container.construct::<Job>();
+        let container = container.bind_self();
+
+        let job = container.get::<Job>().unwrap();
+        assert!(Arc::ptr_eq(&container, &job.container));
+        assert!(container.resolve_detached::<Job>().is_none());
+
+        // The container does not keep itself alive.
+        let weak = Arc::downgrade(&container);
+        drop((container, job));
+        assert!(weak.upgrade().is_none());
+
+        let container = ServiceContainer::new_self_bound(|c| c.construct::<Job>());
+        assert!(Arc::ptr_eq(
+            &container,
+            &container.get::<ServiceContainer>().unwrap(),
+        ));
+        assert!(Arc::ptr_eq(
+            &container,
+            &container.get::<Job>().unwrap().container
+        ));
+    }
+}
@@ -0,0 +1,443 @@
+//! Containers resolving services by custom keys in addition to their types, e.g. by names given
+//! by plugins loaded at runtime, or to tell apart the same type from multiple versions of a
+//! crate.
+//!
+//! [`ServiceContainer`] keeps keying the services by their [`TypeId`]s, so the typed path stays
+//! as it is. Note that the keyed services are still resolved as their types, so the keys do not
+//! make the services of a type resolvable across dynamic libraries; see [`ServiceContainer`].
+
+use std::any::{type_name, Any, TypeId};
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::{
+    AsyncResolve, AsyncResolver, AsyncServices, RawBinding, ResolutionError, ResolutionKind,
+    Resolve, ServiceContainer, ServiceVisitor, Services,
+};
+
+/// A key of the services in a [`KeyedContainer`], such as `String` or `TypeId`.
+pub trait ServiceKey: Eq + Hash + Clone + fmt::Debug + Send + Sync + 'static {}
+
+impl<K> ServiceKey for K where K: Eq + Hash + Clone + fmt::Debug + Send + Sync + 'static {}
+
+/// A container resolving services by keys of `K`, e.g. `KeyedContainer<String>` for the
+/// services named by strings.
+///
+/// A key is bound to a single service type, which the service must be resolved as. The services
+/// registered by the [`Services`] and [`AsyncServices`] methods are keyed by their types as
+/// usual, and can be the dependencies of the keyed ones, and vice versa.
+pub struct KeyedContainer<K> {
+    keyed: HashMap<K, RawBinding>,
+    keyed_async: HashMap<K, AsyncBinding>,
+    typed: ServiceContainer,
+}
+
+/// An asynchronous resolver put with a key, holding `Arc<dyn AsyncResolve<S, C>>` type-erased, as
+/// the keys are bound to different service types.
+#[derive(Clone)]
+struct AsyncBinding {
+    type_name: &'static str,
+    resolver: Arc<dyn Any + Send + Sync>,
+}
+
+impl fmt::Debug for AsyncBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AsyncBinding")
+            .field("type_name", &self.type_name)
+            .finish()
+    }
+}
+
+impl<K> KeyedContainer<K>
+where
+    K: ServiceKey,
+{
+    /// Creates an empty container.
+    pub fn new() -> Self {
+        Self {
+            keyed: HashMap::new(),
+            keyed_async: HashMap::new(),
+            typed: ServiceContainer::default(),
+        }
+    }
+
+    /// Returns whether a service is registered with the key or not, either synchronously or
+    /// asynchronously.
+    pub fn has_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Eq + Hash,
+    {
+        self.keyed.contains_key(key) || self.keyed_async.contains_key(key)
+    }
+
+    /// Returns the keys of the registered services in arbitrary order, each once even if
+    /// registered both synchronously and asynchronously.
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        let async_only = self
+            .keyed_async
+            .keys()
+            .filter(|k| !self.keyed.contains_key(*k));
+
+        self.keyed.keys().chain(async_only)
+    }
+
+    /// Gets the service with the key. Returns `None` if no service is registered with the key,
+    /// or if it is registered as another type.
+    pub fn get_keyed<S, Q>(&self, key: &Q) -> Option<Arc<S>>
+    where
+        S: ?Sized + Send + Sync + 'static,
+        K: Borrow<Q>,
+        Q: ?Sized + Eq + Hash,
+    {
+        let (key, binding) = self.keyed.get_key_value(key)?;
+        if binding.service_id() != TypeId::of::<S>() {
+            warn_mismatch::<S, K>(key, binding.type_name());
+            return None;
+        }
+
+        binding.resolve(self)
+    }
+
+    /// Gets the service with the key asynchronously, falling back to the synchronous one with
+    /// the key like [`AsyncServices::get_async`]. Returns `None` if no service is registered with
+    /// the key, or if it is registered as another type.
+    pub async fn get_keyed_async<S, Q>(&self, key: &Q) -> Option<Arc<S>>
+    where
+        S: ?Sized + Send + Sync + 'static,
+        K: Borrow<Q>,
+        Q: ?Sized + Eq + Hash,
+    {
+        let Some((key, binding)) = self.keyed_async.get_key_value(key) else {
+            return self.get_keyed(key);
+        };
+
+        let Some(resolver) = binding
+            .resolver
+            .downcast_ref::<Arc<dyn AsyncResolve<S, Self>>>()
+        else {
+            warn_mismatch::<S, K>(key, binding.type_name);
+            return None;
+        };
+
+        resolver.async_resolve(self).await
+    }
+
+    /// Puts a service with the key, replacing the existing one.
+    pub fn put_keyed<S, R>(&mut self, key: K, resolver: R)
+    where
+        S: ?Sized + Send + Sync + 'static,
+        R: Resolve<S, Self> + 'static,
+    {
+        self.keyed
+            .insert(key, RawBinding::new::<S, Self, R>(resolver));
+    }
+
+    /// Puts a service resolved asynchronously with the key, replacing the existing asynchronous
+    /// one. The synchronous one with the key, if any, is kept as the fallback.
+    pub fn put_keyed_async<S, R>(&mut self, key: K, resolver: R)
+    where
+        S: ?Sized + Send + Sync + 'static,
+        R: AsyncResolve<S, Self> + 'static,
+    {
+        let resolver: Arc<dyn AsyncResolve<S, Self>> = Arc::new(resolver);
+        let binding = AsyncBinding {
+            type_name: type_name::<S>(),
+            resolver: Arc::new(resolver),
+        };
+
+        self.keyed_async.insert(key, binding);
+    }
+}
+
+fn warn_mismatch<S, K>(key: &K, registered: &'static str)
+where
+    S: ?Sized,
+    K: fmt::Debug,
+{
+    warn!(
+        "The service keyed by {:?} is registered as {}, but resolved as {}.",
+        key,
+        registered,
+        type_name::<S>(),
+    );
+}
+
+impl<K> Default for KeyedContainer<K>
+where
+    K: ServiceKey,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K> Clone for KeyedContainer<K>
+where
+    K: ServiceKey,
+{
+    fn clone(&self) -> Self {
+        Self {
+            keyed: self.keyed.clone(),
+            keyed_async: self.keyed_async.clone(),
+            typed: self.typed.clone(),
+        }
+    }
+}
+
+impl<K> fmt::Debug for KeyedContainer<K>
+where
+    K: ServiceKey,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KeyedContainer")
+            .field("keyed", &self.keyed)
+            .field("keyed_async", &self.keyed_async)
+            .field("typed", &self.typed)
+            .finish()
+    }
+}
+
+impl<K> Services for KeyedContainer<K>
+where
+    K: ServiceKey,
+{
+    fn has<S>(&self) -> bool
+    where
+        S: ?Sized + 'static,
+    {
+        self.typed.has::<S>()
+    }
+
+    fn get<S>(&self) -> Option<Arc<S>>
+    where
+        S: ?Sized + Send + Sync + 'static,
+    {
+        self.typed.get_for::<S, Self>(self)
+    }
+
+    fn try_get<S>(&self) -> Result<Arc<S>, ResolutionError>
+    where
+        S: ?Sized + Send + Sync + 'static,
+    {
+        self.typed.try_get_for::<S, Self>(self)
+    }
+
+    fn put<S, R>(&mut self, resolver: R)
+    where
+        S: ?Sized + Send + Sync + 'static,
+        R: Resolve<S, Self> + 'static,
+    {
+        self.typed.put_for::<S, Self, R>(resolver)
+    }
+
+    /// Visits the services keyed by their types, then the ones put with keys, once for each key.
+    fn visit(&self, visitor: &mut impl ServiceVisitor) {
+        self.typed.visit(visitor);
+
+        for binding in self.keyed.values() {
+            visitor.visit_service(binding.type_name(), binding.kind(), false);
+        }
+
+        for binding in self.keyed_async.values() {
+            visitor.visit_service(binding.type_name, ResolutionKind::Async, true);
+        }
+    }
+}
+
+#[async_trait]
+impl<K> AsyncServices for KeyedContainer<K>
+where
+    K: ServiceKey,
+{
+    async fn get_async<S>(&self) -> Option<Arc<S>>
+    where
+        S: ?Sized + Send + Sync + 'static,
+    {
+        self.typed.get_async_for::<S, Self>(self).await
+    }
+
+    fn put_async<S>(&mut self, resolver: AsyncResolver<S, Self>)
+    where
+        S: ?Sized + Send + Sync + 'static,
+    {
+        self.typed.put_async_for(resolver);
+    }
+
+    fn is_resolved<S>(&self) -> bool
+    where
+        S: ?Sized + 'static,
+    {
+        AsyncServices::is_resolved::<S>(&self.typed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bind::{AsyncBindBy, AsyncBindServices, BindBy};
+    use crate::{BindServices, Singleton, SingletonServices};
+
+    trait Plugin: Send + Sync {
+        fn name(&self) -> String;
+    }
+
+    struct Config {
+        prefix: String,
+    }
+
+    struct Auth {
+        config: Arc<Config>,
+    }
+
+    impl Plugin for Auth {
+        fn name(&self) -> String {
+            format!("{}auth", self.config.prefix)
+        }
+    }
+
+    #[test]
+    fn string_keyed() {
+        let mut container = KeyedContainer::<String>::new();
+
+        container.singleton(Config {
+            prefix: "plugin:".to_string(),
+        });
+        container.put_keyed::<dyn Plugin, _>(
+            "auth".to_string(),
+            BindBy::from(|c: &KeyedContainer<String>| {
+                Some(Arc::new(Auth { config: c.get()? }) as Arc<dyn Plugin>)
+            }),
+        );
+        container.put_keyed("version".to_string(), Singleton::new(2u32));
+
+        // Keyed services are the dependencies of typed ones, and vice versa.
+        container
+            .bind_by::<String, _>(|c| Some(Arc::new(c.get_keyed::<dyn Plugin, _>("auth")?.name())));
+
+        assert_eq!(
+            "plugin:auth",
+            container.get_keyed::<dyn Plugin, _>("auth").unwrap().name()
+        );
+        assert_eq!("plugin:auth", *container.get::<String>().unwrap());
+        assert_eq!(2, *container.get_keyed::<u32, _>("version").unwrap());
+
+        // A key is bound to its service type.
+        assert!(container.has_key("version"));
+        assert!(container.get_keyed::<u64, _>("version").is_none());
+        assert!(container.get_keyed::<u32, _>("missing").is_none());
+
+        let mut keys = container.keys().cloned().collect::<Vec<_>>();
+        keys.sort();
+        assert_eq!(vec!["auth", "version"], keys);
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    enum Version {
+        V1,
+        V2,
+    }
+
+    #[test]
+    fn custom_keyed() {
+        let mut container = KeyedContainer::<(TypeId, Version)>::new();
+
+        // The same type registered twice, told apart by the version.
+        let key = |version| (TypeId::of::<Config>(), version);
+        for (version, prefix) in [(Version::V1, "v1:"), (Version::V2, "v2:")] {
+            container.put_keyed(
+                key(version),
+                Singleton::new(Config {
+                    prefix: prefix.to_string(),
+                }),
+            );
+        }
+
+        assert_eq!(
+            "v2:",
+            container
+                .get_keyed::<Config, _>(&key(Version::V2))
+                .unwrap()
+                .prefix
+        );
+        assert!(!container.has::<Config>());
+    }
+
+    struct Pool {
+        url: String,
+    }
+
+    #[tokio::test]
+    async fn async_keyed() {
+        let mut container = KeyedContainer::<String>::new();
+
+        container.bind_by_async(|_| async {
+            Some(Arc::new(Config {
+                prefix: "postgres://".to_string(),
+            }))
+        });
+        container.put_keyed_async(
+            "primary".to_string(),
+            AsyncBindBy::from(|c: &KeyedContainer<String>| {
+                let c = c.clone();
+                async move {
+                    let config = c.get_async::<Config>().await?;
+                    Some(Arc::new(Pool {
+                        url: format!("{}primary", config.prefix),
+                    }))
+                }
+            }),
+        );
+        container.put_keyed(
+            "replica".to_string(),
+            Singleton::new(Pool {
+                url: "postgres://replica".to_string(),
+            }),
+        );
+
+        let primary = container.get_keyed_async::<Pool, _>("primary").await;
+        assert_eq!("postgres://primary", primary.unwrap().url);
+
+        // Falls back to the synchronous one with the key.
+        let replica = container.get_keyed_async::<Pool, _>("replica").await;
+        assert_eq!("postgres://replica", replica.unwrap().url);
+
+        // A key is bound to its service type asynchronously too.
+        assert!(container
+            .get_keyed_async::<Config, _>("primary")
+            .await
+            .is_none());
+        assert!(container.get_keyed::<Pool, _>("primary").is_none());
+        assert!(container.has_key("primary"));
+
+        let mut keys = container.keys().cloned().collect::<Vec<_>>();
+        keys.sort();
+        assert_eq!(vec!["primary", "replica"], keys);
+
+        // Visits the typed services and every keyed one.
+        let mut registrations = vec![];
+        container.visit(&mut |name, kind, is_async| registrations.push((name, kind, is_async)));
+        registrations.sort_by_key(|&(_, _, is_async)| is_async);
+        assert_eq!(
+            vec![
+                (type_name::<Pool>(), ResolutionKind::Singleton, false),
+                (type_name::<Config>(), ResolutionKind::Async, true),
+                (type_name::<Pool>(), ResolutionKind::Async, true),
+            ],
+            registrations,
+        );
+
+        // The typed services report why they failed like the ServiceContainer.
+        assert_eq!(
+            Err(ResolutionError::WrongResolverKind {
+                type_name: type_name::<Config>(),
+            }),
+            container.try_get::<Config>().map(|_| ()),
+        );
+    }
+}
@@ -1,22 +1,158 @@
 //! Dependency injection functionality.
 
+// Defined ahead of the modules to be available in them.
+macro_rules! warn {
+    ($($arg:tt)*) => {{
+        #[cfg(feature = "tracing")]
+        ::tracing::warn!($($arg)*);
+        #[cfg(not(feature = "tracing"))]
+        let _ = format_args!($($arg)*);
+    }};
+}
+
 pub mod bind;
+#[cfg(feature = "tokio")]
+pub mod blocking;
+pub mod compose;
 pub mod construct;
+mod cycle;
+mod dispose;
+pub mod dynamic;
+pub mod error;
+pub mod factory;
+#[cfg(feature = "anyhow")]
+pub mod fallible;
+pub mod frozen;
+pub mod function;
+mod group;
+pub mod handle;
 pub mod inject;
+pub mod keyed;
+mod marked;
+pub mod memo;
+pub mod module;
+pub mod mutex;
+pub mod oneshot;
+pub mod overlay;
+pub mod raw;
+#[cfg(feature = "test-util")]
+pub mod recording;
+mod reentrancy;
+mod report;
+#[cfg(feature = "tokio")]
+mod runtime;
+#[cfg(feature = "tokio")]
+pub mod scope;
+pub mod scoped;
 pub mod singleton;
+#[cfg(feature = "tokio")]
+pub mod spawn;
+#[cfg(feature = "arc-swap")]
+pub mod swap;
 pub mod tagged;
+#[cfg(feature = "tokio")]
+pub mod timeout;
+pub mod visit;
+mod walk;
 
-use std::any::{Any, TypeId};
+use std::any::{type_name, Any, TypeId};
 use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::ops::{Deref, DerefMut};
 use std::sync::Arc;
 
 use async_trait::async_trait;
 
 pub use bind::{BindServices, Bound};
-pub use construct::{Construct, ConstructServices, Constructor};
+#[cfg(feature = "tokio")]
+pub use blocking::BlockingServices;
+pub use compose::{AsyncResolveExt, ResolveExt};
+pub use construct::{
+    AsyncConstruct, AsyncConstructServices, AsyncConstructor, Construct, ConstructServices,
+    Constructor,
+};
+pub use dynamic::DynServices;
+pub use error::{CustomError, ResolutionError};
+pub use factory::{AsyncFactory, Factory, FactoryServices};
+#[cfg(feature = "anyhow")]
+pub use fallible::{TryConstruct, TryConstructServices, TryConstructor};
+pub use frozen::FrozenContainer;
+pub use function::{FnServices, ServiceFn};
+pub use handle::{ContainerHandle, HandleServices};
 pub use inject::{Inject, InjectServices};
-pub use singleton::{Singleton, SingletonServices};
-pub use tagged::{Tagged, TaggedServices};
+pub use keyed::{KeyedContainer, ServiceKey};
+pub use marked::Marked;
+pub use memo::MemoScope;
+pub use module::{AsyncModule, AsyncModuleRegistry, ModuleError, ModuleId};
+pub use mutex::MutexServices;
+pub use oneshot::{OneShot, OneShotServices};
+pub use overlay::OverlayContainer;
+pub use raw::RawBinding;
+#[cfg(feature = "test-util")]
+pub use recording::RecordingContainer;
+#[cfg(feature = "derive")]
+pub use ruice_derive::{Construct, Inject, Services};
+#[cfg(feature = "tokio")]
+pub use scope::ScopedServices;
+pub use scoped::ScopeId;
+pub use singleton::{
+    AsyncLazySingleton, LazySingleton, OnceSingleton, Singleton, SingletonServices, WeakSingleton,
+};
+#[cfg(feature = "tokio")]
+pub use spawn::SpawnedSingleton;
+#[cfg(feature = "arc-swap")]
+pub use swap::{ArcSwapHandle, SwapServices};
+pub use tagged::{AsyncTagged, AsyncTaggedServices, TagMetadata, Tagged, TaggedServices};
+#[cfg(feature = "tokio")]
+pub use timeout::TimeoutServices;
+pub use visit::ServiceVisitor;
+
+/// Brings the container and all the extension traits into scope, so their methods are callable.
+///
+/// ```
+/// use std::sync::Arc;
+///
+/// use ruice::prelude::*;
+///
+/// struct Greeter;
+///
+/// let mut container = ServiceContainer::default();
+/// container.singleton(Greeter);
+/// container.put_tagged::<Greeter>(Arc::new(Greeter));
+///
+/// assert!(container.get::<Greeter>().is_some());
+/// assert_eq!(1, container.get_tagged::<Greeter>().len());
+/// ```
+pub mod prelude {
+    pub use crate::bind::AsyncBindServices;
+    #[cfg(feature = "tokio")]
+    pub use crate::BlockingServices;
+    #[cfg(feature = "tokio")]
+    pub use crate::ScopedServices;
+    #[cfg(feature = "arc-swap")]
+    pub use crate::SwapServices;
+    #[cfg(feature = "tokio")]
+    pub use crate::TimeoutServices;
+    pub use crate::{
+        AsyncConstruct, AsyncConstructServices, AsyncResolve, AsyncResolveExt, AsyncServices,
+        AsyncTaggedServices, BindServices, Construct, ConstructServices, DynServices,
+        FactoryServices, FnServices, HandleServices, Inject, InjectServices, MutexServices,
+        OneShotServices, Resolve, ResolveExt, ServiceContainer, Services, SingletonServices,
+        TaggedServices,
+    };
+    #[cfg(feature = "anyhow")]
+    pub use crate::{TryConstruct, TryConstructServices};
+}
+
+#[doc(hidden)]
+pub mod __private {
+    pub use async_trait::async_trait;
+
+    pub use crate::construct::{
+        resolve_field, ConstructIfConstructable, MaybeConstructor, SkipIfNotConstructable,
+    };
+}
 
 // Snippet: https://github.com/AzureMarker/shaku/blob/0be60063f093b164631853be72eb099813502172/shaku/src/trait_alias.rs
 // Adapted from https://stackoverflow.com/a/30293051/3267834
@@ -34,11 +170,86 @@ trait_alias! {
     pub Interface = Send + Sync
 }
 
+/// The kind of a resolver, used for introspecting the container.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ResolutionKind {
+    Singleton,
+    LazySingleton,
+    Bound,
+    BindBy,
+    Constructor,
+    /// A service left unset while registered, put by [`BindServices::bind_optional`].
+    Optional,
+    /// A service handed out to the first resolution only, put by
+    /// [`OneShotServices::put_once`].
+    OneShot,
+    Custom,
+    /// Any asynchronous resolver, reported on registration.
+    Async,
+}
+
+/// When the services constructed from the container are resolved first, set by
+/// [`ServiceContainer::with_policy`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ResolutionPolicy {
+    /// Resolves the services on their first resolution.
+    #[default]
+    Lazy,
+    /// Resolves the services put by [`ConstructServices::construct`],
+    /// [`SingletonServices::lazy_singleton`], and the like right on their registration, failing
+    /// fast on the missing dependencies.
+    Eager,
+}
+
+type RegistrationHookFn = dyn Fn(&'static str, ResolutionKind) + Send + Sync;
+
+#[derive(Clone)]
+struct RegistrationHook(Arc<RegistrationHookFn>);
+
+impl fmt::Debug for RegistrationHook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("RegistrationHook")
+    }
+}
+
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` can not resolve `{S}` from `{C}`",
+    label = "not a resolver of `{S}`",
+    note = "wrap closures in `BindBy::from`, or use `BindServices::bind_by`",
+    note = "resolvers are stored in the container, so they must own what they capture: \
+            clone or `Arc` the captured values and use a `move` closure"
+)]
 pub trait Resolve<S, C = ServiceContainer>: Send + Sync
 where
     S: ?Sized,
 {
+    /// Resolves the service.
+    /// The returned [`Arc`] is owned by the caller, so resolvers holding the service clone it.
     fn resolve(&self, container: &C) -> Option<Arc<S>>;
+
+    /// Borrows the service held by the resolver, if any, without cloning the [`Arc`].
+    /// Resolvers creating the service on resolution return `None`.
+    fn resolve_ref(&self) -> Option<&Arc<S>> {
+        None
+    }
+
+    /// Returns the kind of the resolver.
+    fn kind(&self) -> ResolutionKind {
+        ResolutionKind::Custom
+    }
+
+    /// Returns whether resolving the service is free of side effects, so it can be resolved just
+    /// to check the wiring, e.g. by [`ServiceContainer::validate`], and there is nothing to
+    /// create ahead of time, e.g. by [`ServiceContainer::warm_up_all`].
+    ///
+    /// A pure resolver neither creates nor consumes anything on resolution: it only hands out
+    /// the service it already holds, and resolving it any number of times is unobservable.
+    /// Resolvers running functions or constructors can not promise that, so they are impure by
+    /// default, as are the resolvers delegating to them.
+    fn is_pure(&self) -> bool {
+        false
+    }
 }
 
 struct Resolver<S, C = ServiceContainer>
@@ -46,17 +257,20 @@ where
     S: ?Sized,
 {
     resolve: Arc<dyn Resolve<S, C>>,
+    /// Merges the collections if the resolver holds one. See [`ServiceContainer::register_group`].
+    merge: Option<tagged::MergeFn<S>>,
 }
 
 impl<S, C> Resolver<S, C>
 where
-    S: ?Sized,
+    S: ?Sized + 'static,
 {
     fn new<R>(resolve: R) -> Self
     where
         R: Resolve<S, C> + 'static,
     {
         Self {
+            merge: tagged::merge_fn(&resolve),
             resolve: Arc::new(resolve),
         }
     }
@@ -66,12 +280,31 @@ where
     }
 }
 
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` can not resolve `{S}` asynchronously from `{C}`",
+    label = "not an async resolver of `{S}`",
+    note = "wrap async closures in `AsyncBindBy::from`, or use `AsyncBindServices::bind_by_async`",
+    note = "resolvers are stored in the container, so they must own what they capture: \
+            clone or `Arc` the captured values and use a `move` closure"
+)]
 #[async_trait]
 pub trait AsyncResolve<S, C = ServiceContainer>: Send + Sync
 where
     S: ?Sized,
 {
     async fn async_resolve(&self, container: &C) -> Option<Arc<S>>;
+
+    /// Returns whether the resolver caches the service and has resolved it already, without
+    /// triggering the resolution. Resolvers without a cache always resolve afresh, so they
+    /// return `false`.
+    fn is_resolved(&self) -> bool {
+        false
+    }
+
+    /// Returns whether resolving the service is free of side effects. See [`Resolve::is_pure`].
+    fn is_pure(&self) -> bool {
+        false
+    }
 }
 
 pub struct AsyncResolver<S, C = ServiceContainer>
@@ -79,6 +312,7 @@ where
     S: ?Sized,
 {
     resolve: Arc<dyn AsyncResolve<S, C>>,
+    async_only: bool,
 }
 
 impl<S, C> AsyncResolver<S, C>
@@ -91,9 +325,21 @@ where
     {
         Self {
             resolve: Arc::new(resolve),
+            async_only: false,
         }
     }
 
+    /// Marks the service as never resolved synchronously, for the expensive services which
+    /// must be awaited.
+    ///
+    /// Putting the resolver removes the synchronous one of the service, if any, and
+    /// [`AsyncServices::get_async`] no longer falls back to [`Services::get`] when it fails.
+    /// Resolving the service synchronously returns `None` with a warning.
+    pub fn async_only(mut self) -> Self {
+        self.async_only = true;
+        self
+    }
+
     pub fn as_inner(&self) -> &dyn AsyncResolve<S, C> {
         self.resolve.as_ref()
     }
@@ -112,13 +358,134 @@ pub trait Services: Sized + Send + Sync {
     where
         S: ?Sized + Send + Sync + 'static;
 
+    /// Returns whether the service is resolvable right now, by actually resolving it, while
+    /// [`Services::has`] only checks whether it is registered.
+    ///
+    /// Resolving the service runs the resolver with its side effects: services created on
+    /// resolution are constructed and discarded, lazy singletons are cached, and one-shot
+    /// services are consumed.
+    fn can_resolve<S>(&self) -> bool
+    where
+        S: ?Sized + Send + Sync + 'static,
+    {
+        self.get::<S>().is_some()
+    }
+
+    /// Runs the wiring function on the container, returning the container for chaining, e.g. for
+    /// composing the reusable modules defined as plain functions.
+    ///
+    /// ```
+    /// use ruice::{ServiceContainer, Services, SingletonServices};
+    ///
+    /// struct Database;
+    /// struct HttpClient;
+    ///
+    /// fn database_module() -> impl FnOnce(&mut ServiceContainer) {
+    ///     |c| c.singleton(Database)
+    /// }
+    ///
+    /// fn http_module(c: &mut ServiceContainer) {
+    ///     c.singleton(HttpClient);
+    /// }
+    ///
+    /// let mut container = ServiceContainer::default();
+    /// container.apply(database_module()).apply(http_module);
+    ///
+    /// assert!(container.has::<Database>() && container.has::<HttpClient>());
+    /// ```
+    fn apply<F>(&mut self, f: F) -> &mut Self
+    where
+        F: FnOnce(&mut Self),
+    {
+        f(self);
+        self
+    }
+
+    /// Resolves the service and returns whether it is the same instance as `other`, e.g. for
+    /// asserting in tests that a singleton is shared, or that a transient service is not.
+    /// Returns `false` if the service could not be resolved.
+    fn same_instance<S>(&self, other: &Arc<S>) -> bool
+    where
+        S: ?Sized + Send + Sync + 'static,
+    {
+        self.get::<S>().is_some_and(|s| Arc::ptr_eq(&s, other))
+    }
+
+    /// Calls the visitor for every registration of the container, without resolving them.
+    /// See [`ServiceVisitor`].
+    fn visit(&self, visitor: &mut impl ServiceVisitor);
+
+    /// Gets the service from the service container, reporting why it could not be resolved.
+    fn try_get<S>(&self) -> Result<Arc<S>, ResolutionError>
+    where
+        S: ?Sized + Send + Sync + 'static,
+    {
+        try_resolve(self)
+    }
+
+    /// Gets the service and maps it into a derived value, releasing the service right after.
+    fn map_get<S, T, F>(&self, f: F) -> Option<T>
+    where
+        S: ?Sized + Send + Sync + 'static,
+        F: FnOnce(&S) -> T,
+    {
+        self.get::<S>().map(|s| f(&s))
+    }
+
     /// Puts a service to the service container.
+    ///
+    /// The resolver is stored in the container, so it must not borrow anything:
+    ///
+    /// ```compile_fail
+    /// use std::sync::Arc;
+    ///
+    /// use ruice::bind::BindBy;
+    /// use ruice::{ServiceContainer, Services};
+    ///
+    /// let mut container = ServiceContainer::default();
+    /// let name = "Taro".to_string();
+    ///
+    /// // `name` does not live long enough.
+    /// container.put(BindBy::from(|_: &ServiceContainer| Some(Arc::new(name.clone()))));
+    /// ```
+    ///
+    /// Move an owned value into the closure instead, sharing it by an [`Arc`] if needed:
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use ruice::bind::BindBy;
+    /// use ruice::{ServiceContainer, Services};
+    ///
+    /// let mut container = ServiceContainer::default();
+    /// let name = Arc::new("Taro".to_string());
+    ///
+    /// container.put(BindBy::from(move |_: &ServiceContainer| Some(Arc::clone(&name))));
+    /// assert_eq!("Taro", container.get::<String>().unwrap().as_str());
+    /// ```
     fn put<S, R>(&mut self, resolver: R)
     where
         S: ?Sized + Send + Sync + 'static,
         R: Resolve<S, Self> + 'static;
 
+    /// Resolves the service once and discards the result, so caching resolvers are populated
+    /// before the first actual use. Resolution failures are logged, not propagated.
+    fn warm_up<S>(&self)
+    where
+        S: ?Sized + Send + Sync + 'static,
+    {
+        if self.get::<S>().is_none() {
+            warn!("could not warm up service {}", type_name::<S>());
+        }
+    }
+
     /// Replaces the service in the container by the mutation function.
+    ///
+    /// **The service is always put as a [`Singleton`], whatever resolver it was registered
+    /// with.** For example, replacing a service put by [`ConstructServices::construct`] resolves
+    /// it once and fixes the result, so it is no longer constructed on each resolution. Check the
+    /// current resolver by [`ServiceContainer::kind_of`] if that matters, or update services put
+    /// by [`MutexServices::singleton_mutex`] in place by [`ServiceContainer::modify`].
     fn replace<S, F>(&mut self, f: F)
     where
         S: Send + Sync + 'static,
@@ -126,6 +493,36 @@ pub trait Services: Sized + Send + Sync {
     {
         self.put(Singleton::new(f(self.get::<S>().as_deref())));
     }
+
+    /// Puts the service bound to the instance like [`BindServices::bind`], returning the one
+    /// resolved before, e.g. for restoring it later. Like [`Services::replace`], the instance is
+    /// put whatever resolver the service was registered with, and the previous one is resolved
+    /// by that resolver.
+    fn swap<S>(&mut self, service: Arc<S>) -> Option<Arc<S>>
+    where
+        S: ?Sized + Send + Sync + 'static,
+    {
+        let previous = self.get::<S>();
+        self.put(Bound::from(service));
+
+        previous
+    }
+
+    /// Gets the service, or puts the one built by `f` as a singleton if it could not be resolved.
+    fn get_or_insert_with<S, F>(&mut self, f: F) -> Arc<S>
+    where
+        S: Send + Sync + 'static,
+        F: FnOnce() -> S,
+    {
+        if let Some(service) = self.get::<S>() {
+            return service;
+        }
+
+        let service = Arc::new(f());
+        self.put(Singleton::from(Arc::clone(&service)));
+
+        service
+    }
 }
 
 #[async_trait]
@@ -135,107 +532,2080 @@ pub trait AsyncServices: Sized + Send + Sync {
     where
         S: ?Sized + Send + Sync + 'static;
 
-    /// Puts a asynchronous service to the service container.
-    fn put_async<S>(&mut self, resolver: AsyncResolver<S, Self>)
+    /// Gets the service asynchronously, reporting why it could not be resolved, like
+    /// [`Services::try_get`], e.g. [`ResolutionError::TimedOut`] under the default timeout of
+    /// the container.
+    async fn try_get_async<S>(&self) -> Result<Arc<S>, ResolutionError>
     where
-        S: ?Sized + Send + Sync + 'static;
-}
-
-type ServiceId = TypeId;
+        S: ?Sized + Send + Sync + 'static,
+        Self: Services,
+    {
+        if !self.has::<S>() {
+            return Err(ResolutionError::NotRegistered {
+                type_name: type_name::<S>(),
+            });
+        }
 
-#[derive(Debug, Clone, Default)]
-pub struct ServiceContainer {
-    services: HashMap<ServiceId, Arc<dyn Any + Send + Sync>>,
-}
+        report::scope_async(type_name::<S>(), self.get_async::<S>()).await
+    }
 
-impl Services for ServiceContainer {
-    fn has<S>(&self) -> bool
+    /// Gets the service asynchronously and maps it into a derived value, releasing the service
+    /// right after.
+    async fn map_get_async<S, T, F>(&self, f: F) -> Option<T>
     where
-        S: ?Sized + 'static,
+        S: ?Sized + Send + Sync + 'static,
+        F: FnOnce(&S) -> T + Send,
     {
-        self.services.contains_key(&TypeId::of::<S>())
+        self.get_async::<S>().await.map(|s| f(&s))
     }
 
-    fn get<S>(&self) -> Option<Arc<S>>
+    /// Returns whether the service is resolvable asynchronously right now, by actually
+    /// resolving it. See [`Services::can_resolve`] for the side effects.
+    async fn can_resolve_async<S>(&self) -> bool
     where
         S: ?Sized + Send + Sync + 'static,
     {
-        self.services
-            .get(&TypeId::of::<S>())
-            .and_then(|r| r.downcast_ref::<Resolver<S>>())
-            .and_then(|r| r.as_inner().resolve(self))
+        self.get_async::<S>().await.is_some()
     }
 
-    fn put<S, R>(&mut self, resolver: R)
+    /// Resolves the service asynchronously once and discards the result.
+    /// Resolution failures are logged, not propagated.
+    async fn warm_up_async<S>(&self)
     where
         S: ?Sized + Send + Sync + 'static,
-        R: Resolve<S, Self> + 'static,
     {
-        self.services
-            .insert(TypeId::of::<S>(), Arc::new(Resolver::new(resolver)));
+        if self.get_async::<S>().await.is_none() {
+            warn!("could not warm up service {}", type_name::<S>());
+        }
     }
-}
 
-#[async_trait]
-impl AsyncServices for ServiceContainer {
-    async fn get_async<S>(&self) -> Option<Arc<S>>
+    /// Puts a asynchronous service to the service container.
+    fn put_async<S>(&mut self, resolver: AsyncResolver<S, Self>)
+    where
+        S: ?Sized + Send + Sync + 'static;
+
+    /// Returns whether the asynchronous resolver of the service has cached the service already,
+    /// without triggering the resolution, e.g. for readiness probes of lazily connected
+    /// services. See [`AsyncResolve::is_resolved`].
+    fn is_resolved<S>(&self) -> bool
+    where
+        S: ?Sized + 'static,
+    {
+        false
+    }
+
+    /// Puts the asynchronous service bound to the instance, returning the one resolved before
+    /// by [`AsyncServices::get_async`]. See [`Services::swap`].
+    async fn swap_async<S>(&mut self, service: Arc<S>) -> Option<Arc<S>>
     where
         S: ?Sized + Send + Sync + 'static,
     {
-        let resolved = match self
-            .services
-            .get(&TypeId::of::<S>())
-            .and_then(|r| r.downcast_ref::<AsyncResolver<S>>())
-        {
-            Some(r) => r.as_inner().async_resolve(self).await,
-            _ => None,
-        };
+        let previous = self.get_async::<S>().await;
+        self.put_async(AsyncResolver::new(Bound::from(service)));
 
-        match resolved {
-            Some(s) => Some(s),
-            _ => self.get(),
-        }
+        previous
     }
 
-    fn put_async<S>(&mut self, resolver: AsyncResolver<S>)
+    /// Replaces the asynchronous service by the resolver built from its current value, e.g. for
+    /// rotating credentials.
+    ///
+    /// The current value is resolved by [`AsyncServices::get_async`] first, then the new resolver
+    /// is put. `f` receives `None` if the service is not registered, or if resolving it fails,
+    /// so it can build the replacement from scratch.
+    ///
+    /// The replacements are serialized by the exclusive borrow of the container held across both
+    /// steps: no other resolution nor registration can run on it in between, so nothing can
+    /// observe a half-replaced service. This says nothing about the outside world, though: the
+    /// current value may already be stale when the new resolver is built. Both steps are awaited
+    /// rather than blocked on, so replacing a service on a single-threaded runtime, or from within
+    /// a task of the runtime resolving it, does not deadlock.
+    async fn replace_async<S, F, Fut, R>(&mut self, f: F)
     where
         S: ?Sized + Send + Sync + 'static,
+        F: FnOnce(Option<Arc<S>>) -> Fut + Send,
+        Fut: Future<Output = R> + Send,
+        R: AsyncResolve<S, Self> + 'static,
     {
-        self.services.insert(TypeId::of::<S>(), Arc::new(resolver));
+        let current = self.get_async::<S>().await;
+        let resolver = f(current).await;
+
+        self.put_async(AsyncResolver::new(resolver));
     }
 }
 
-#[macro_export]
-macro_rules! alias {
-    ($int: ty, $act: ty $(,)?) => {
-        |c| c.get::<$act>().map(|s| s as ::std::sync::Arc<$int>)
-    };
+/// Resolves the service, reporting why it could not be resolved. See [`Services::try_get`].
+fn try_resolve<S, C>(container: &C) -> Result<Arc<S>, ResolutionError>
+where
+    S: ?Sized + Send + Sync + 'static,
+    C: Services,
+{
+    if !container.has::<S>() {
+        return Err(ResolutionError::NotRegistered {
+            type_name: type_name::<S>(),
+        });
+    }
+
+    report::scope(type_name::<S>(), || container.get::<S>())
 }
 
-#[cfg(test)]
-mod tests {
-    use crate::{ServiceContainer, Services, Singleton};
+type ServiceId = TypeId;
 
-    struct Greeter {
-        message: String,
+/// The entries of the container, shared by its clones until either of them mutates them, which
+/// copies the map but not the resolvers in it.
+#[derive(Debug)]
+struct Entries<E>(Arc<HashMap<ServiceId, Arc<E>>>)
+where
+    E: ?Sized;
+
+impl<E> Entries<E>
+where
+    E: ?Sized,
+{
+    fn with_capacity(capacity: usize) -> Self {
+        Self(Arc::new(HashMap::with_capacity(capacity)))
     }
 
-    impl Greeter {
-        fn greet(&self) -> String {
-            self.message.clone()
+    fn into_inner(self) -> HashMap<ServiceId, Arc<E>> {
+        Arc::try_unwrap(self.0).unwrap_or_else(|shared| (*shared).clone())
+    }
+}
+
+impl<'a, E> IntoIterator for &'a Entries<E>
+where
+    E: ?Sized,
+{
+    type Item = (&'a ServiceId, &'a Arc<E>);
+    type IntoIter = std::collections::hash_map::Iter<'a, ServiceId, Arc<E>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl<E> Clone for Entries<E>
+where
+    E: ?Sized,
+{
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+impl<E> Default for Entries<E>
+where
+    E: ?Sized,
+{
+    fn default() -> Self {
+        Self(Arc::default())
+    }
+}
+
+impl<E> Deref for Entries<E>
+where
+    E: ?Sized,
+{
+    type Target = HashMap<ServiceId, Arc<E>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<E> DerefMut for Entries<E>
+where
+    E: ?Sized,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        Arc::make_mut(&mut self.0)
+    }
+}
+
+/// A type-erased resolver stored in the [`ServiceContainer`].
+trait Entry: Any + Send + Sync {
+    fn as_any(&self) -> &dyn Any;
+
+    fn type_name(&self) -> &'static str;
+
+    fn service_id(&self) -> ServiceId;
+
+    /// Returns the type name of the container the service is resolved against.
+    fn container_type_name(&self) -> &'static str;
+
+    fn kind(&self) -> ResolutionKind;
+
+    fn is_pure(&self) -> bool;
+
+    /// Resolves the service against the container, boxing the resolved `Arc<S>`.
+    fn resolve_any(
+        &self,
+        container: &(dyn Any + Send + Sync),
+    ) -> Option<Box<dyn Any + Send + Sync>>;
+
+    /// Boxes the `Arc<S>` held by the resolver, if any, without resolving the service.
+    fn singleton_any(&self) -> Option<Box<dyn Any + Send + Sync>>;
+
+    /// Returns the strong count of the `Arc<S>` held by the resolver, if any.
+    fn strong_count(&self) -> Option<usize>;
+
+    fn memoized(&self) -> Arc<dyn Entry>;
+
+    fn frozen(
+        &self,
+        container: &(dyn Any + Send + Sync),
+    ) -> Result<Arc<dyn Entry>, ResolutionError>;
+
+    /// Merges the collection of the other entry into this one, or removes its members from this
+    /// one. Returns `None` unless the entries hold collections.
+    fn merged(&self, other: &dyn Entry, remove: bool) -> Option<Arc<dyn Entry>>;
+}
+
+impl fmt::Debug for dyn Entry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Entry").field(&self.type_name()).finish()
+    }
+}
+
+impl<S, C> Entry for Resolver<S, C>
+where
+    S: ?Sized + Send + Sync + 'static,
+    C: Send + Sync + 'static,
+{
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn type_name(&self) -> &'static str {
+        type_name::<S>()
+    }
+
+    fn service_id(&self) -> ServiceId {
+        TypeId::of::<S>()
+    }
+
+    fn container_type_name(&self) -> &'static str {
+        type_name::<C>()
+    }
+
+    fn kind(&self) -> ResolutionKind {
+        self.as_inner().kind()
+    }
+
+    fn is_pure(&self) -> bool {
+        self.as_inner().is_pure()
+    }
+
+    fn resolve_any(
+        &self,
+        container: &(dyn Any + Send + Sync),
+    ) -> Option<Box<dyn Any + Send + Sync>> {
+        let resolved = self.as_inner().resolve(container.downcast_ref::<C>()?)?;
+        Some(Box::new(resolved))
+    }
+
+    fn singleton_any(&self) -> Option<Box<dyn Any + Send + Sync>> {
+        let service = Arc::clone(self.as_inner().resolve_ref()?);
+        Some(Box::new(service))
+    }
+
+    fn strong_count(&self) -> Option<usize> {
+        self.as_inner().resolve_ref().map(Arc::strong_count)
+    }
+
+    fn memoized(&self) -> Arc<dyn Entry> {
+        Arc::new(Self::new(LazySingleton::from(Arc::clone(&self.resolve))))
+    }
+
+    fn frozen(
+        &self,
+        container: &(dyn Any + Send + Sync),
+    ) -> Result<Arc<dyn Entry>, ResolutionError> {
+        let service = report::scope(type_name::<S>(), || {
+            self.as_inner().resolve(container.downcast_ref::<C>()?)
+        })?;
+
+        Ok(Arc::new(Self::new(Singleton::from(service))))
+    }
+
+    fn merged(&self, other: &dyn Entry, remove: bool) -> Option<Arc<dyn Entry>> {
+        let other = other.as_any().downcast_ref::<Self>()?;
+        let merge = self.merge?;
+        let merged = merge(
+            self.as_inner().resolve_ref()?,
+            other.as_inner().resolve_ref()?,
+            remove,
+        );
+
+        Some(Arc::new(Self::new(tagged::Collection::new(merged, merge))))
+    }
+}
+
+/// A type-erased asynchronous resolver stored in the [`ServiceContainer`].
+#[async_trait]
+trait AsyncEntry: Any + Send + Sync {
+    fn as_any(&self) -> &dyn Any;
+
+    fn type_name(&self) -> &'static str;
+
+    /// Returns the type name of the container the service is resolved against.
+    fn container_type_name(&self) -> &'static str;
+
+    async fn warm_up_async(&self, container: &(dyn Any + Send + Sync)) -> bool;
+
+    fn is_resolved(&self) -> bool;
+
+    fn is_async_only(&self) -> bool;
+}
+
+impl fmt::Debug for dyn AsyncEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("AsyncEntry")
+            .field(&self.type_name())
+            .finish()
+    }
+}
+
+#[async_trait]
+impl<S, C> AsyncEntry for AsyncResolver<S, C>
+where
+    S: ?Sized + Send + Sync + 'static,
+    C: Send + Sync + 'static,
+{
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn type_name(&self) -> &'static str {
+        type_name::<S>()
+    }
+
+    fn container_type_name(&self) -> &'static str {
+        type_name::<C>()
+    }
+
+    async fn warm_up_async(&self, container: &(dyn Any + Send + Sync)) -> bool {
+        match container.downcast_ref::<C>() {
+            Some(c) => self.as_inner().async_resolve(c).await.is_some(),
+            _ => false,
         }
     }
 
-    #[test]
-    fn service_get_put() {
-        let mut container = ServiceContainer::default();
+    fn is_resolved(&self) -> bool {
+        self.as_inner().is_resolved()
+    }
 
-        container.put(Singleton::new(Greeter {
-            message: "Hello, world!".to_string(),
-        }));
+    fn is_async_only(&self) -> bool {
+        self.async_only
+    }
+}
 
-        assert!(container.has::<Greeter>());
+/// Downcasts the entry of the service into its resolver against the container type `C`.
+///
+/// The entries are keyed by the service type, so a failure means the service is registered, but
+/// against another container type, e.g. by [`ServiceContainer::put_for`] with a custom container
+/// while resolving it with [`Services::get`] on the inner one. That is a bug in the wiring rather
+/// than a missing service: it panics under the `strict` feature, and is warned about otherwise.
+fn downcast_entry<'a, S, C, R>(entry: &'a dyn Any, registered_for: &'static str) -> Option<&'a R>
+where
+    S: ?Sized,
+    R: 'static,
+{
+    let resolver = entry.downcast_ref::<R>();
+    if resolver.is_none() {
+        let message = format!(
+            "The service {} is registered against {}, but resolved against {}.",
+            type_name::<S>(),
+            registered_for,
+            type_name::<C>(),
+        );
 
-        assert_eq!("Hello, world!", container.get::<Greeter>().unwrap().greet());
+        #[cfg(feature = "strict")]
+        panic!("{}", message);
+        #[cfg(not(feature = "strict"))]
+        warn!("{}", message);
+    }
+
+    resolver
+}
+
+/// Synchronous and asynchronous resolvers are stored separately, so a service can be registered
+/// for both [`Services::get`] and [`AsyncServices::get_async`] at the same time.
+///
+/// # Thread safety
+///
+/// The container is shared across threads, so the services must be `Send + Sync`. A service
+/// holding an `Rc` or a `RefCell` is rejected where it is put, with the compiler naming the
+/// offending field:
+///
+/// ```compile_fail
+/// use std::rc::Rc;
+///
+/// use ruice::prelude::*;
+///
+/// struct Session {
+///     user: Rc<String>,
+/// }
+///
+/// let mut container = ServiceContainer::default();
+///
+/// // `Rc<String>` cannot be sent between threads safely.
+/// container.singleton(Session {
+///     user: Rc::new("Taro".to_string()),
+/// });
+/// ```
+///
+/// Use `Arc` and `Mutex` instead. Trait objects are `Send + Sync` only when declared so, either
+/// by the trait itself (`trait Greeter: Send + Sync`) or by the bound type
+/// (`dyn Greeter + Send + Sync`).
+///
+/// These bounds are auto traits of the standard library, which
+/// `#[diagnostic::on_unimplemented]` can not customize, unlike the traits of this crate such as
+/// [`Resolve`] and [`Construct`].
+///
+/// # Dynamic libraries
+///
+/// The services are identified by their [`TypeId`]s, which are not guaranteed to be the same
+/// for a type compiled into different compilation units, such as the host and a plugin loaded
+/// from a `cdylib`. A service registered by a plugin may then be unresolvable from the host,
+/// and vice versa, even as the same `dyn Trait`. [`Services::try_get`] warns when a missing
+/// service has the same type name as a registered one. Renaming the keys, e.g. by
+/// [`KeyedContainer`], does not help either, as resolving the services downcasts them by their
+/// type IDs too. Exchange the services across the boundary through an
+/// FFI-safe interface instead, registering them on each side of it.
+///
+/// Cloning the container is cheap: the clones share the resolvers until either of them puts or
+/// removes a service, which copies the map of them, but not the resolvers themselves. See
+/// [`ServiceContainer::fork`].
+#[derive(Debug, Clone, Default)]
+pub struct ServiceContainer {
+    services: Entries<dyn Entry>,
+    async_services: Entries<dyn AsyncEntry>,
+    groups: Vec<group::Group>,
+    registration_hook: Option<RegistrationHook>,
+    disposers: Vec<dispose::Disposer>,
+    casts: HashMap<(ServiceId, ServiceId), walk::Cast>,
+    #[cfg(feature = "tokio")]
+    default_async_timeout: Option<std::time::Duration>,
+    #[cfg(feature = "tokio")]
+    runtime_handle: Option<tokio::runtime::Handle>,
+    #[cfg(feature = "tokio")]
+    spawned: HashMap<ServiceId, spawn::Spawned>,
+    policy: ResolutionPolicy,
+    eager_failures: Vec<ServiceId>,
+    scoped: scoped::ScopedSingletons,
+}
+
+impl ServiceContainer {
+    /// Creates an empty container with space for at least `capacity` services.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            services: Entries::with_capacity(capacity),
+            ..Default::default()
+        }
+    }
+
+    /// Sets when the services constructed from the container are resolved first, for the apps
+    /// preferring failures on startup over surprises at runtime.
+    ///
+    /// Under [`ResolutionPolicy::Eager`], each service registered by a
+    /// [`ResolutionKind::Constructor`] or a [`ResolutionKind::LazySingleton`] resolver is resolved
+    /// right after putting it: lazy singletons are created and cached, and the others are
+    /// constructed once and discarded to check their dependencies. Only the resolvers put against
+    /// the container itself are resolved eagerly, not the ones put through a wrapping container.
+    ///
+    /// The services failed to resolve on registration, e.g. the ones registered before their
+    /// dependencies, are resolved again by [`ServiceContainer::resolve_eager`], which reports
+    /// those still failing. Call it once the wiring is done.
+    pub fn with_policy(mut self, policy: ResolutionPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Resolves again the services failed to resolve on their registration under
+    /// [`ResolutionPolicy::Eager`], returning the errors of those still failing, sorted by their
+    /// type names. See [`ServiceContainer::with_policy`].
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use ruice::{Construct, ConstructServices, ResolutionPolicy, ServiceContainer};
+    /// use ruice::{SingletonServices, Services};
+    ///
+    /// struct Config;
+    ///
+    /// struct Repository {
+    ///     _config: Arc<Config>,
+    /// }
+    ///
+    /// impl Construct for Repository {
+    ///     fn construct(container: &ServiceContainer) -> Option<Self> {
+    ///         Some(Self {
+    ///             _config: container.get()?,
+    ///         })
+    ///     }
+    /// }
+    ///
+    /// let mut container = ServiceContainer::default().with_policy(ResolutionPolicy::Eager);
+    ///
+    /// // Registered before its dependency, so it fails to resolve for now.
+    /// container.lazy_singleton::<Repository>();
+    /// assert!(container.resolve_eager().is_err());
+    ///
+    /// container.singleton(Config);
+    /// assert!(container.resolve_eager().is_ok());
+    /// ```
+    pub fn resolve_eager(&mut self) -> Result<(), Vec<ResolutionError>> {
+        let failures = std::mem::take(&mut self.eager_failures);
+        let entries = failures
+            .iter()
+            .filter_map(|id| self.services.get(id))
+            .collect::<Vec<_>>();
+
+        let errors = self.resolve_entries(entries);
+        self.eager_failures = errors.iter().map(|(id, _)| *id).collect();
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors.into_iter().map(|(_, e)| e).collect())
+        }
+    }
+
+    /// Creates a container starting from the services of this one, diverging freely afterwards:
+    /// the services put into or removed from either of them do not affect the other.
+    ///
+    /// Unlike a child container, the fork is flat and resolves the services without falling back
+    /// to the original. The resolvers are shared, and so are the singletons held by them, while
+    /// the map of the resolvers is copied only on the first mutation of either container. Unlike
+    /// [`Clone::clone`], the fork starts with no scoped singletons of its own, so they are
+    /// constructed from its services. See [`ServiceContainer::get_scoped_singleton`].
+    pub fn fork(&self) -> Self {
+        Self {
+            scoped: Default::default(),
+            ..self.clone()
+        }
+    }
+
+    /// Returns the number of the registered services. A service registered both synchronously
+    /// and asynchronously is counted once.
+    pub fn len(&self) -> usize {
+        self.services.len()
+            + self
+                .async_services
+                .keys()
+                .filter(|id| !self.services.contains_key(id))
+                .count()
+    }
+
+    /// Returns whether no service is registered.
+    pub fn is_empty(&self) -> bool {
+        self.services.is_empty() && self.async_services.is_empty()
+    }
+
+    /// Returns whether a service of the type name is registered under another type ID, which
+    /// happens when the same type is compiled into multiple compilation units.
+    fn registered_under_another_id(&self, id: ServiceId, name: &str) -> bool {
+        self.services
+            .iter()
+            .any(|(i, e)| *i != id && e.type_name() == name)
+    }
+
+    /// Returns the type names of the registered services, sorted for reproducible output, e.g.
+    /// for logging the wiring or diffing it between deployments.
+    pub fn registered_type_names(&self) -> Vec<&'static str> {
+        let mut names = vec![];
+        self.visit(&mut |name, _, _| names.push(name));
+        names.sort_unstable();
+        names.dedup();
+
+        names
+    }
+
+    /// Returns the type names of the registered services with the kinds of their resolvers,
+    /// sorted by the type names. Asynchronous resolvers are reported as
+    /// [`ResolutionKind::Async`], after the synchronous one of the same service if any.
+    pub fn registrations(&self) -> Vec<(&'static str, ResolutionKind)> {
+        let mut registrations = vec![];
+        self.visit(&mut |name, kind, _| registrations.push((name, kind)));
+        registrations.sort_by_key(|(name, kind)| (*name, *kind == ResolutionKind::Async));
+
+        registrations
+    }
+
+    /// Gets the service resolving it against the given container, reporting why it could not be
+    /// resolved. See [`ServiceContainer::get_for`] and [`Services::try_get`].
+    pub fn try_get_for<S, C>(&self, container: &C) -> Result<Arc<S>, ResolutionError>
+    where
+        S: ?Sized + Send + Sync + 'static,
+        C: Services,
+    {
+        let id = TypeId::of::<S>();
+        if !self.services.contains_key(&id) && self.async_services.contains_key(&id) {
+            return Err(ResolutionError::WrongResolverKind {
+                type_name: type_name::<S>(),
+            });
+        }
+
+        // Scanned only when reporting the failure, not to slow down every missing resolution.
+        if !container.has::<S>() && self.registered_under_another_id(id, type_name::<S>()) {
+            warn!(
+                "The service {} is not registered, but another type of the same name is; the \
+                 type may come from another compilation unit, e.g. a dynamically loaded library",
+                type_name::<S>(),
+            );
+        }
+
+        try_resolve(container)
+    }
+
+    /// Gets the service, resolving it against the given container.
+    /// This is the building block for custom containers wrapping a [`ServiceContainer`];
+    /// `#[derive(Services)]` generates the delegation under the `derive` feature.
+    pub fn get_for<S, C>(&self, container: &C) -> Option<Arc<S>>
+    where
+        S: ?Sized + Send + Sync + 'static,
+        C: Send + Sync + 'static,
+    {
+        let id = TypeId::of::<S>();
+
+        #[cfg(debug_assertions)]
+        if !self.services.contains_key(&id)
+            && self.async_services.contains_key(&id)
+            && reentrancy::in_async_resolution()
+        {
+            warn!(
+                "The service {} is registered only asynchronously, but resolved synchronously \
+                 within an asynchronous resolution; resolve it by get_async instead",
+                type_name::<S>(),
+            );
+        }
+
+        let Some(entry) = self.services.get(&id) else {
+            if self
+                .async_services
+                .get(&id)
+                .is_some_and(|e| e.is_async_only())
+            {
+                warn!(
+                    "The service {} is registered as async-only; resolve it by get_async",
+                    type_name::<S>(),
+                );
+            }
+
+            return None;
+        };
+
+        downcast_entry::<S, C, Resolver<S, C>>(entry.as_any(), entry.container_type_name())?
+            .as_inner()
+            .resolve(container)
+    }
+
+    /// Borrows the service held by its resolver without cloning the [`Arc`], for the services
+    /// registered by [`SingletonServices::singleton`] or [`BindServices::bind`].
+    /// Returns `None` for the services created on resolution; use [`Services::get`] for them.
+    pub fn get_ref<S>(&self) -> Option<&Arc<S>>
+    where
+        S: ?Sized + Send + Sync + 'static,
+    {
+        self.services
+            .get(&TypeId::of::<S>())
+            .and_then(|r| {
+                downcast_entry::<S, Self, Resolver<S>>(r.as_any(), r.container_type_name())
+            })
+            .and_then(|r| r.as_inner().resolve_ref())
+    }
+
+    /// Returns the kind of the synchronous resolver of the service, if registered.
+    pub fn kind_of<S>(&self) -> Option<ResolutionKind>
+    where
+        S: ?Sized + 'static,
+    {
+        self.services.get(&TypeId::of::<S>()).map(|e| e.kind())
+    }
+
+    /// Puts a service resolved against the given container type.
+    pub fn put_for<S, C, R>(&mut self, resolver: R)
+    where
+        S: ?Sized + Send + Sync + 'static,
+        C: Send + Sync + 'static,
+        R: Resolve<S, C> + 'static,
+    {
+        let kind = resolver.kind();
+        self.drop_disposer(TypeId::of::<S>());
+        self.services
+            .insert(TypeId::of::<S>(), Arc::new(Resolver::<S, C>::new(resolver)));
+
+        self.registered(type_name::<S>(), kind);
+
+        let eager = matches!(
+            kind,
+            ResolutionKind::Constructor | ResolutionKind::LazySingleton
+        );
+        if self.policy == ResolutionPolicy::Eager
+            && eager
+            && TypeId::of::<C>() == TypeId::of::<Self>()
+        {
+            let id = TypeId::of::<S>();
+            self.eager_failures.retain(|i| *i != id);
+
+            if let Err(error) = self.try_get::<S>() {
+                warn!("could not resolve {} eagerly: {}", type_name::<S>(), error);
+                self.eager_failures.push(id);
+            }
+        }
+    }
+
+    /// Gets the service asynchronously, resolving it against the given container.
+    pub async fn get_async_for<S, C>(&self, container: &C) -> Option<Arc<S>>
+    where
+        S: ?Sized + Send + Sync + 'static,
+        C: Send + Sync + 'static,
+    {
+        let resolver = self.async_services.get(&TypeId::of::<S>()).and_then(|r| {
+            downcast_entry::<S, C, AsyncResolver<S, C>>(r.as_any(), r.container_type_name())
+        });
+        let async_only = resolver.is_some_and(|r| r.async_only);
+
+        let resolved = match resolver {
+            #[cfg(feature = "tokio")]
+            Some(r) => {
+                let resolving = reentrancy::Resolving(r.as_inner().async_resolve(container));
+                self.within_timeout(resolving).await
+            }
+            #[cfg(not(feature = "tokio"))]
+            Some(r) => reentrancy::Resolving(r.as_inner().async_resolve(container)).await,
+            _ => None,
+        };
+
+        match resolved {
+            Some(s) => Some(s),
+            None if async_only => None,
+            _ => self.get_for(container),
+        }
+    }
+
+    /// Puts a asynchronous service resolved against the given container type.
+    pub fn put_async_for<S, C>(&mut self, resolver: AsyncResolver<S, C>)
+    where
+        S: ?Sized + Send + Sync + 'static,
+        C: Send + Sync + 'static,
+    {
+        let id = TypeId::of::<S>();
+        if resolver.async_only {
+            self.drop_disposer(id);
+            self.services.remove(&id);
+        }
+
+        self.async_services.insert(id, Arc::new(resolver));
+
+        self.registered(type_name::<S>(), ResolutionKind::Async);
+    }
+
+    /// Sets the hook called on every registration of a service with its type name and the kind
+    /// of its resolver, e.g. for logging the wiring at startup or enforcing a policy on it.
+    /// Asynchronous resolvers are reported as [`ResolutionKind::Async`].
+    ///
+    /// The hook is shared with the clones of the container made afterwards.
+    pub fn set_registration_hook<F>(&mut self, f: F)
+    where
+        F: Fn(&'static str, ResolutionKind) + Send + Sync + 'static,
+    {
+        self.registration_hook = Some(RegistrationHook(Arc::new(f)));
+    }
+
+    fn registered(&self, type_name: &'static str, kind: ResolutionKind) {
+        if let Some(RegistrationHook(hook)) = &self.registration_hook {
+            hook(type_name, kind);
+        }
+    }
+
+    /// Copies the binding of the service from the other container, sharing its resolvers.
+    /// Returns `false` without changing anything if the other container does not have it.
+    pub fn copy_binding<S>(&mut self, from: &ServiceContainer) -> bool
+    where
+        S: ?Sized + 'static,
+    {
+        let id = TypeId::of::<S>();
+        let entry = from.services.get(&id).map(Arc::clone);
+        let async_entry = from.async_services.get(&id).map(Arc::clone);
+        if entry.is_none() && async_entry.is_none() {
+            return false;
+        }
+
+        match entry {
+            Some(e) => self.services.insert(id, e),
+            None => self.services.remove(&id),
+        };
+        match async_entry {
+            Some(e) => self.async_services.insert(id, e),
+            None => self.async_services.remove(&id),
+        };
+
+        true
+    }
+
+    /// Resolves every synchronous service in the container once, discarding the results.
+    /// Asynchronous services are skipped; use [`ServiceContainer::warm_up_all_async`] for them.
+    /// Resolution failures are logged, not propagated.
+    ///
+    /// The pure resolvers have nothing to warm up, and the one-shot services put by
+    /// [`OneShotServices::put_once`] would be consumed, so they are skipped.
+    pub fn warm_up_all(&self) {
+        for entry in self.services.values() {
+            self.warm_up_entry(entry);
+        }
+    }
+
+    /// Resolves every service in the container once, including asynchronous ones.
+    /// Resolution failures are logged, not propagated. See [`ServiceContainer::warm_up_all`].
+    pub async fn warm_up_all_async(&self) {
+        for entry in self.async_services.values() {
+            if !entry.warm_up_async(self).await {
+                warn!("could not warm up service {}", entry.type_name());
+            }
+        }
+
+        for (id, entry) in &self.services {
+            if !self.async_services.contains_key(id) {
+                self.warm_up_entry(entry);
+            }
+        }
+    }
+
+    fn warm_up_entry(&self, entry: &Arc<dyn Entry>) {
+        if entry.is_pure() || entry.kind() == ResolutionKind::OneShot {
+            return;
+        }
+
+        if entry.resolve_any(self).is_none() {
+            warn!("could not warm up service {}", entry.type_name());
+        }
+    }
+
+    /// Checks the wiring by resolving every synchronous service whose resolver is pure, such as
+    /// singletons and bound services, returning the errors of those registered but failed to
+    /// resolve, without side effects.
+    ///
+    /// Impure resolvers, such as constructors and functions which may open connections, are
+    /// skipped; use [`ServiceContainer::validate_all`] to resolve them too. See
+    /// [`Resolve::is_pure`] for the contract. The optional services put by
+    /// [`BindServices::bind_optional`] may be left unset. Asynchronous services are skipped, as
+    /// are the services registered against another container type, e.g. by
+    /// [`ServiceContainer::put_for`].
+    pub fn validate(&self) -> Result<(), Vec<ResolutionError>> {
+        self.validate_entries(|e| e.is_pure())
+    }
+
+    /// Checks the wiring by resolving every synchronous service, including the impure ones,
+    /// returning the errors of those failed to resolve. See [`ServiceContainer::validate`].
+    ///
+    /// Resolving the services runs their resolvers with the side effects described by
+    /// [`Services::can_resolve`], such as constructors opening connections, so call this at
+    /// startup rather than on a serving container. The one-shot services put by
+    /// [`OneShotServices::put_once`] are only checked to be registered, not to consume them, and
+    /// the optional ones put by [`BindServices::bind_optional`] may be left unset. Asynchronous
+    /// services are skipped, as are the services registered against another container type,
+    /// e.g. by [`ServiceContainer::put_for`].
+    pub fn validate_all(&self) -> Result<(), Vec<ResolutionError>> {
+        self.validate_entries(|e| e.kind() != ResolutionKind::OneShot)
+    }
+
+    fn validate_entries<F>(&self, f: F) -> Result<(), Vec<ResolutionError>>
+    where
+        F: Fn(&dyn Entry) -> bool,
+    {
+        let entries = self
+            .services
+            .values()
+            .filter(|e| e.container_type_name() == type_name::<Self>() && f(e.as_ref()))
+            .collect::<Vec<_>>();
+
+        let errors = self.resolve_entries(entries);
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors.into_iter().map(|(_, e)| e).collect())
+        }
+    }
+
+    /// Resolves the entries in the order of their type names, returning the errors of those
+    /// failed to resolve.
+    fn resolve_entries(
+        &self,
+        mut entries: Vec<&Arc<dyn Entry>>,
+    ) -> Vec<(ServiceId, ResolutionError)> {
+        entries.sort_by_key(|e| e.type_name());
+
+        let mut errors = vec![];
+        for entry in entries {
+            match report::scope(entry.type_name(), || entry.resolve_any(self)) {
+                // Left unset deliberately.
+                Err(_) if entry.kind() == ResolutionKind::Optional => {}
+                Err(error) => errors.push((entry.service_id(), error)),
+                Ok(_) => {}
+            }
+        }
+
+        errors
+    }
+
+    /// Constructs every service registered by [`ConstructServices::construct`] and replaces
+    /// them with singletons, so no construction happens after the startup.
+    ///
+    /// Services are constructed in the dependency order, sharing the instances between their
+    /// dependents. If any construction fails, the container is left untouched and the error is
+    /// returned, including the circular dependencies detected. The services registered against
+    /// another container type, e.g. by [`ServiceContainer::put_for`], are left as they are.
+    pub fn freeze_as_singletons(&mut self) -> Result<(), ResolutionError> {
+        let ids = self
+            .services
+            .iter()
+            .filter(|(_, e)| e.kind() == ResolutionKind::Constructor)
+            .filter(|(_, e)| e.container_type_name() == type_name::<Self>())
+            .map(|(id, _)| *id)
+            .collect::<Vec<_>>();
+
+        let mut memoized = self.clone();
+        for id in &ids {
+            let entry = memoized.services[id].memoized();
+            memoized.services.insert(*id, entry);
+        }
+
+        let frozen = ids
+            .iter()
+            .map(|id| Ok((*id, memoized.services[id].frozen(&memoized)?)))
+            .collect::<Result<Vec<_>, ResolutionError>>()?;
+
+        self.services.extend(frozen);
+        Ok(())
+    }
+}
+
+impl Services for ServiceContainer {
+    fn has<S>(&self) -> bool
+    where
+        S: ?Sized + 'static,
+    {
+        let id = TypeId::of::<S>();
+        self.services.contains_key(&id) || self.async_services.contains_key(&id)
+    }
+
+    fn get<S>(&self) -> Option<Arc<S>>
+    where
+        S: ?Sized + Send + Sync + 'static,
+    {
+        self.get_for(self)
+    }
+
+    fn try_get<S>(&self) -> Result<Arc<S>, ResolutionError>
+    where
+        S: ?Sized + Send + Sync + 'static,
+    {
+        self.try_get_for(self)
+    }
+
+    fn put<S, R>(&mut self, resolver: R)
+    where
+        S: ?Sized + Send + Sync + 'static,
+        R: Resolve<S, Self> + 'static,
+    {
+        self.put_for::<S, Self, R>(resolver);
+    }
+
+    fn visit(&self, visitor: &mut impl ServiceVisitor) {
+        for entry in self.services.values() {
+            visitor.visit_service(entry.type_name(), entry.kind(), false);
+        }
+
+        for entry in self.async_services.values() {
+            visitor.visit_service(entry.type_name(), ResolutionKind::Async, true);
+        }
+    }
+}
+
+#[async_trait]
+impl AsyncServices for ServiceContainer {
+    async fn get_async<S>(&self) -> Option<Arc<S>>
+    where
+        S: ?Sized + Send + Sync + 'static,
+    {
+        self.get_async_for(self).await
+    }
+
+    fn put_async<S>(&mut self, resolver: AsyncResolver<S>)
+    where
+        S: ?Sized + Send + Sync + 'static,
+    {
+        self.put_async_for(resolver);
+    }
+
+    fn is_resolved<S>(&self) -> bool
+    where
+        S: ?Sized + 'static,
+    {
+        self.async_services
+            .get(&TypeId::of::<S>())
+            .is_some_and(|e| e.is_resolved())
+    }
+}
+
+#[macro_export]
+macro_rules! alias {
+    ($int: ty, $act: ty $(,)?) => {
+        |c| c.get::<$act>().map(|s| s as ::std::sync::Arc<$int>)
+    };
+}
+
+/// Includes the registrations only when all the features of the calling crate are enabled,
+/// optionally falling back to the `else` block otherwise.
+///
+/// The block is compiled out entirely when the features are disabled, so import the
+/// feature-gated service types within it to avoid unused-import warnings.
+///
+/// ```
+/// use std::sync::Arc;
+///
+/// use ruice::{register_if_feature, BindServices, ServiceContainer, Services};
+///
+/// trait Cache: Send + Sync {
+///     fn name(&self) -> &'static str;
+/// }
+///
+/// struct NoCache;
+///
+/// impl Cache for NoCache {
+///     fn name(&self) -> &'static str {
+///         "none"
+///     }
+/// }
+///
+/// # #[cfg(feature = "redis")]
+/// # mod redis {
+/// #     pub struct RedisCache;
+/// #     impl super::Cache for RedisCache {
+/// #         fn name(&self) -> &'static str { "redis" }
+/// #     }
+/// # }
+/// let mut container = ServiceContainer::default();
+///
+/// register_if_feature!("redis" => {
+///     use redis::RedisCache;
+///
+///     container.bind::<dyn Cache>(Arc::new(RedisCache));
+/// } else {
+///     container.bind::<dyn Cache>(Arc::new(NoCache));
+/// });
+///
+/// // Multiple features are required all together.
+/// register_if_feature!("redis", "metrics" => {
+///     container.bind::<dyn Cache>(Arc::new(redis::RedisCache));
+/// });
+///
+/// assert_eq!("none", container.get::<dyn Cache>().unwrap().name());
+/// ```
+#[macro_export]
+macro_rules! register_if_feature {
+    ($($feature: literal),+ $(,)? => $body: block else $fallback: block) => {
+        #[cfg(all($(feature = $feature),+))]
+        $body
+        #[cfg(not(all($(feature = $feature),+)))]
+        $fallback
+    };
+    ($($feature: literal),+ $(,)? => $body: block) => {
+        #[cfg(all($(feature = $feature),+))]
+        $body
+    };
+}
+
+/// Registers the service by [`ConstructServices::construct`] if it implements [`Construct`] for
+/// the container, or skips it otherwise. Returns whether the service is registered.
+///
+/// The dispatch happens where the macro is expanded, so the service type must be concrete there.
+/// Within a generic function, e.g. `fn wire<T>(c: &mut ServiceContainer)`, the bound on `T` is
+/// all that is known, and the service is skipped unless the function requires `T: Construct`.
+/// Expand the macro in the code listing the concrete types instead, such as a module's
+/// registration function or a macro generating one.
+///
+/// ```
+/// use std::sync::Arc;
+///
+/// use ruice::{register_if_constructable, Construct, ServiceContainer, Services};
+///
+/// struct Config;
+///
+/// struct Repository {
+///     _config: Arc<Config>,
+/// }
+///
+/// impl Construct for Repository {
+///     fn construct(container: &ServiceContainer) -> Option<Self> {
+///         Some(Self {
+///             _config: container.get()?,
+///         })
+///     }
+/// }
+///
+/// let mut container = ServiceContainer::default();
+///
+/// assert!(register_if_constructable!(container, Repository));
+/// assert!(!register_if_constructable!(container, Config));
+///
+/// assert!(container.has::<Repository>());
+/// assert!(!container.has::<Config>());
+/// ```
+#[macro_export]
+macro_rules! register_if_constructable {
+    ($container: expr, $service: ty $(,)?) => {{
+        #[allow(unused_imports)]
+        use $crate::__private::{ConstructIfConstructable as _, SkipIfNotConstructable as _};
+
+        (&$crate::__private::MaybeConstructor::<$service>::new()).register(&mut $container)
+    }};
+}
+
+/// Binds the service as the trait object and each of the listed ones, sharing one [`Arc`].
+///
+/// Trait objects are distinct services, so a service bound as `dyn Sub` is not resolved as its
+/// supertrait `dyn Super` unless bound as that too. The macro binds them all at once by coercing
+/// the service from its concrete type, which works with any traits the type implements, and
+/// unlike [`BindServices::bind_upcast`] needs no casting method on the subtrait before Rust 1.86.
+///
+/// The service is either a path, such as a unit struct or a constant, or a parenthesized
+/// expression:
+///
+/// ```
+/// use std::sync::Arc;
+///
+/// use ruice::{bind_with_supertraits, ServiceContainer, Services};
+///
+/// trait Named: Send + Sync {
+///     fn name(&self) -> String;
+/// }
+///
+/// trait Greet: Named {
+///     fn greet(&self) -> String {
+///         format!("Hello, {}!", self.name())
+///     }
+/// }
+///
+/// struct Person(String);
+///
+/// impl Named for Person {
+///     fn name(&self) -> String {
+///         self.0.clone()
+///     }
+/// }
+///
+/// impl Greet for Person {}
+///
+/// let mut container = ServiceContainer::default();
+/// bind_with_supertraits!(container, (Person("Taro".to_string())) as dyn Greet, dyn Named);
+///
+/// assert_eq!("Hello, Taro!", container.get::<dyn Greet>().unwrap().greet());
+/// assert_eq!("Taro", container.get::<dyn Named>().unwrap().name());
+/// ```
+#[macro_export]
+macro_rules! bind_with_supertraits {
+    ($container: expr, ($service: expr) as $sub: ty $(, $super: ty)* $(,)?) => {{
+        let service = ::std::sync::Arc::new($service);
+
+        $crate::BindServices::bind::<$sub>(&mut $container, service.clone());
+        $(
+            $crate::BindServices::bind::<$super>(&mut $container, service.clone());
+        )*
+    }};
+    ($container: expr, $service: path as $sub: ty $(, $super: ty)* $(,)?) => {
+        $crate::bind_with_supertraits!($container, ($service) as $sub $(, $super)*)
+    };
+}
+
+/// Builds a [`ServiceContainer`] from the registrations listed in order, each keyed by the
+/// registration method it expands to:
+///
+/// - `singleton: expr` puts the value by [`SingletonServices::singleton`].
+/// - `bind: Service as dyn Trait` binds the service by [`BindServices::bind`]. The service is
+///   either a path or a parenthesized expression, as in [`bind_with_supertraits!`].
+/// - `construct: Type` and `lazy_singleton: Type` put the type by
+///   [`ConstructServices::construct`] and [`SingletonServices::lazy_singleton`].
+/// - `tagged: [expr, ...] as dyn Trait` puts each service by [`TaggedServices::put_tagged`].
+///
+/// ```
+/// use std::sync::Arc;
+///
+/// use ruice::prelude::*;
+///
+/// struct Config {
+///     prefix: String,
+/// }
+///
+/// trait Handler: Send + Sync {
+///     fn handle(&self) -> String;
+/// }
+///
+/// struct Ping;
+///
+/// impl Handler for Ping {
+///     fn handle(&self) -> String {
+///         "pong".to_string()
+///     }
+/// }
+///
+/// let container = ruice::container! {
+///     singleton: Config { prefix: "/api".to_string() },
+///     bind: Ping as dyn Handler,
+///     tagged: [Ping, Ping] as dyn Handler,
+/// };
+///
+/// assert_eq!("/api", container.get::<Config>().unwrap().prefix);
+/// assert_eq!("pong", container.get::<dyn Handler>().unwrap().handle());
+/// assert_eq!(2, container.get_tagged::<dyn Handler>().len());
+/// ```
+#[macro_export]
+macro_rules! container {
+    ($($entries: tt)*) => {{
+        #[allow(unused_mut)]
+        let mut container = $crate::ServiceContainer::default();
+        $crate::__register!(container; $($entries)*);
+
+        container
+    }};
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __register {
+    ($container: ident; $(,)?) => {};
+    ($container: ident; singleton: $service: expr $(, $($rest: tt)*)?) => {
+        $crate::SingletonServices::singleton(&mut $container, $service);
+        $crate::__register!($container; $($($rest)*)?);
+    };
+    ($container: ident; bind: ($service: expr) as $interface: ty $(, $($rest: tt)*)?) => {
+        $crate::bind_with_supertraits!($container, ($service) as $interface);
+        $crate::__register!($container; $($($rest)*)?);
+    };
+    ($container: ident; bind: $service: path as $interface: ty $(, $($rest: tt)*)?) => {
+        $crate::bind_with_supertraits!($container, $service as $interface);
+        $crate::__register!($container; $($($rest)*)?);
+    };
+    ($container: ident; construct: $service: ty $(, $($rest: tt)*)?) => {
+        $crate::ConstructServices::construct::<$service>(&mut $container);
+        $crate::__register!($container; $($($rest)*)?);
+    };
+    ($container: ident; lazy_singleton: $service: ty $(, $($rest: tt)*)?) => {
+        $crate::SingletonServices::lazy_singleton::<$service>(&mut $container);
+        $crate::__register!($container; $($($rest)*)?);
+    };
+    (
+        $container: ident;
+        tagged: [$($service: expr),* $(,)?] as $tag: ty $(, $($rest: tt)*)?
+    ) => {
+        $(
+            $crate::TaggedServices::put_tagged::<$tag>(
+                &mut $container,
+                ::std::sync::Arc::new($service),
+            );
+        )*
+        $crate::__register!($container; $($($rest)*)?);
+    };
+}
+
+/// Gets all the services from the container, returning them as a tuple, or `None` if any of
+/// them is missing. Meant for the closures binding a service composed of others, listing the
+/// dependencies at a glance:
+///
+/// ```
+/// use std::sync::Arc;
+///
+/// use ruice::{deps, BindServices, ServiceContainer, Services, SingletonServices};
+///
+/// struct Config;
+/// struct Pool;
+///
+/// struct Repository {
+///     _config: Arc<Config>,
+///     _pool: Arc<Pool>,
+/// }
+///
+/// let mut container = ServiceContainer::default();
+/// container.singleton(Config);
+/// container.singleton(Pool);
+/// container.bind_by(|c| {
+///     let (config, pool) = deps!(c => Config, Pool)?;
+///     Some(Arc::new(Repository {
+///         _config: config,
+///         _pool: pool,
+///     }))
+/// });
+///
+/// assert!(container.get::<Repository>().is_some());
+/// ```
+#[macro_export]
+macro_rules! deps {
+    ($container: expr => $($service: ty),+ $(,)?) => {
+        (|| {
+            #[allow(unused_imports)]
+            use $crate::Services as _;
+
+            ::std::option::Option::Some(($($container.get::<$service>()?,)+))
+        })()
+    };
+}
+
+/// Gets all the services from the container asynchronously, one after another, like
+/// [`deps!`]. The macro awaits them, so it is used within an asynchronous context, such as
+/// [`AsyncConstruct::construct_async`] or the future owning the container passed by
+/// [`AsyncBindServices::bind_by_async_owned`](crate::bind::AsyncBindServices::bind_by_async_owned).
+#[macro_export]
+macro_rules! deps_async {
+    ($container: expr => $($service: ty),+ $(,)?) => {
+        async {
+            #[allow(unused_imports)]
+            use $crate::AsyncServices as _;
+
+            ::std::option::Option::Some(($($container.get_async::<$service>().await?,)+))
+        }
+        .await
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use std::any::TypeId;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use crate::bind::{AsyncBindBy, AsyncBindServices};
+    use crate::{
+        AsyncServices, BindServices, Bound, Construct, ConstructServices, OneShotServices,
+        ResolutionError, ResolutionKind, ResolutionPolicy, Resolve, ServiceContainer, Services,
+        Singleton, SingletonServices, TaggedServices,
+    };
+
+    struct Greeter {
+        message: String,
+    }
+
+    impl Greeter {
+        fn greet(&self) -> String {
+            self.message.clone()
+        }
+    }
+
+    #[test]
+    fn service_get_put() {
+        let mut container = ServiceContainer::default();
+
+        container.put(Singleton::new(Greeter {
+            message: "Hello, world!".to_string(),
+        }));
+
+        assert!(container.has::<Greeter>());
+
+        assert_eq!("Hello, world!", container.get::<Greeter>().unwrap().greet());
+    }
+
+    #[tokio::test]
+    async fn warm_up() {
+        let mut container = ServiceContainer::default();
+        let resolutions = Arc::new(AtomicUsize::new(0));
+
+        let counter = Arc::clone(&resolutions);
+        container.bind_by(move |_| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            Some(Arc::new(Greeter {
+                message: "Hello, world!".to_string(),
+            }))
+        });
+
+        let counter = Arc::clone(&resolutions);
+        container.bind_by_async(move |_| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            async { Some(Arc::new(0_u32)) }
+        });
+
+        // Failing resolutions do not abort warming up.
+        container.bind_by(|_| -> Option<Arc<String>> { None });
+
+        container.warm_up::<Greeter>();
+        assert_eq!(1, resolutions.load(Ordering::SeqCst));
+
+        container.warm_up_all();
+        assert_eq!(2, resolutions.load(Ordering::SeqCst));
+
+        container.warm_up_async::<u32>().await;
+        assert_eq!(3, resolutions.load(Ordering::SeqCst));
+
+        container.warm_up_all_async().await;
+        assert_eq!(5, resolutions.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn map_get() {
+        let mut container = ServiceContainer::default();
+
+        container.put(Singleton::new(Greeter {
+            message: "Hello, world!".to_string(),
+        }));
+
+        assert_eq!(Some(13), container.map_get(|g: &Greeter| g.greet().len()),);
+        assert_eq!(
+            Some("Hello, world!".to_string()),
+            container.map_get_async(|g: &Greeter| g.greet()).await,
+        );
+        assert_eq!(None, container.map_get(|s: &String| s.len()));
+    }
+
+    struct Credentials {
+        token: String,
+    }
+
+    #[tokio::test]
+    async fn replace_async() {
+        let mut container = ServiceContainer::default();
+
+        container.bind_by_async(|_| async {
+            Some(Arc::new(Credentials {
+                token: "v1".to_string(),
+            }))
+        });
+
+        // Rotate the credentials, based on the current ones.
+        container
+            .replace_async(|current: Option<Arc<Credentials>>| async move {
+                let token = format!("{}-rotated", current.unwrap().token);
+                AsyncBindBy::from(move |_: &ServiceContainer| {
+                    let token = token.clone();
+                    async move { Some(Arc::new(Credentials { token })) }
+                })
+            })
+            .await;
+
+        let credentials = container.get_async::<Credentials>().await.unwrap();
+        assert_eq!("v1-rotated", credentials.token);
+
+        // Failing to resolve the current ones issues new ones.
+        container.bind_by_async(|_| async { None::<Arc<Credentials>> });
+        container
+            .replace_async(|current: Option<Arc<Credentials>>| async move {
+                assert!(current.is_none());
+                Bound::from(Arc::new(Credentials {
+                    token: "v2".to_string(),
+                }))
+            })
+            .await;
+
+        let credentials = container.get_async::<Credentials>().await.unwrap();
+        assert_eq!("v2", credentials.token);
+    }
+
+    #[test]
+    fn copy_binding() {
+        let mut production = ServiceContainer::default();
+        production.put(Singleton::new(Greeter {
+            message: "Hello, world!".to_string(),
+        }));
+        production.put(Singleton::new(42_u32));
+
+        let mut test = ServiceContainer::default();
+        assert!(test.copy_binding::<Greeter>(&production));
+        assert!(!test.copy_binding::<String>(&production));
+
+        assert_eq!("Hello, world!", test.get::<Greeter>().unwrap().greet());
+        assert!(!test.has::<u32>());
+        assert!(!test.has::<String>());
+    }
+
+    #[tokio::test]
+    async fn can_resolve() {
+        let mut container = ServiceContainer::default();
+
+        // Registered, but not resolvable unless the condition is met.
+        container.bind_by(|c| c.get::<String>().map(|_| Arc::new(42u32)));
+        container.bind_by_async(|_| async { None::<Arc<u64>> });
+
+        assert!(container.has::<u32>());
+        assert!(!container.can_resolve::<u32>());
+        assert!(container.has::<u64>());
+        assert!(!container.can_resolve_async::<u64>().await);
+
+        container.put(Singleton::new("Taro".to_string()));
+        assert!(container.can_resolve::<u32>());
+        assert!(container.can_resolve_async::<u32>().await);
+    }
+
+    #[test]
+    fn registration_hook() {
+        use std::sync::Mutex;
+
+        let mut container = ServiceContainer::default();
+        let registrations = Arc::new(Mutex::new(vec![]));
+
+        let log = Arc::clone(&registrations);
+        container.set_registration_hook(move |name, kind| log.lock().unwrap().push((name, kind)));
+
+        container.put(Singleton::new(Greeter {
+            message: "Hello, world!".to_string(),
+        }));
+        container.bind_by(|_| Some(Arc::new(42u32)));
+        container.bind_by_async(|_| async { Some(Arc::new(42u64)) });
+
+        assert_eq!(
+            vec![
+                (std::any::type_name::<Greeter>(), ResolutionKind::Singleton),
+                (std::any::type_name::<u32>(), ResolutionKind::BindBy),
+                (std::any::type_name::<u64>(), ResolutionKind::Async),
+            ],
+            *registrations.lock().unwrap(),
+        );
+    }
+
+    #[test]
+    fn replace_flattens_kind() {
+        let mut container = ServiceContainer::default();
+
+        container.bind_by(|_| {
+            Some(Arc::new(Greeter {
+                message: "Hello".to_string(),
+            }))
+        });
+        assert_eq!(Some(ResolutionKind::BindBy), container.kind_of::<Greeter>());
+
+        container.replace::<Greeter, _>(|greeter| Greeter {
+            message: format!("{}, world!", greeter.unwrap().message),
+        });
+        assert_eq!(
+            Some(ResolutionKind::Singleton),
+            container.kind_of::<Greeter>()
+        );
+        assert_eq!("Hello, world!", container.get::<Greeter>().unwrap().greet());
+    }
+
+    #[test]
+    fn get_or_insert_with() {
+        let mut container = ServiceContainer::default();
+
+        let greeter = container.get_or_insert_with(|| Greeter {
+            message: "Hello, world!".to_string(),
+        });
+        assert!(Arc::ptr_eq(&greeter, &container.get::<Greeter>().unwrap()));
+
+        // The registered service is returned without building another one.
+        let same = container.get_or_insert_with::<Greeter, _>(|| unreachable!());
+        assert!(Arc::ptr_eq(&greeter, &same));
+    }
+
+    #[test]
+    fn register_if_feature() {
+        let mut container = ServiceContainer::default();
+
+        register_if_feature!("tokio" => {
+            container.put(Singleton::new("tokio"));
+        } else {
+            container.put(Singleton::new("std"));
+        });
+
+        let runtime = if cfg!(feature = "tokio") {
+            "tokio"
+        } else {
+            "std"
+        };
+        assert_eq!(runtime, *container.get::<&str>().unwrap());
+    }
+
+    struct Farewell {
+        greeter: Arc<Greeter>,
+    }
+
+    impl Construct for Farewell {
+        fn construct(container: &ServiceContainer) -> Option<Self> {
+            Some(Self {
+                greeter: container.get()?,
+            })
+        }
+    }
+
+    #[test]
+    fn same_instance() {
+        let mut container = ServiceContainer::default();
+
+        container.singleton(Greeter {
+            message: "Hello!".to_string(),
+        });
+        container.construct::<Farewell>();
+
+        let greeter = container.get::<Greeter>().unwrap();
+        assert!(container.same_instance(&greeter));
+        assert!(container.fork().same_instance(&greeter));
+
+        // Constructed afresh on every resolution.
+        let farewell = container.get::<Farewell>().unwrap();
+        assert!(!container.same_instance(&farewell));
+        assert!(!ServiceContainer::default().same_instance(&greeter));
+    }
+
+    #[test]
+    fn eager_policy() {
+        let mut container = ServiceContainer::default().with_policy(ResolutionPolicy::Eager);
+        let created = Arc::new(AtomicUsize::new(0));
+
+        container.singleton(Greeter {
+            message: "Goodbye!".to_string(),
+        });
+        let counter = Arc::clone(&created);
+        container.put_lazy(move |c: &ServiceContainer| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            Farewell {
+                greeter: c.get().unwrap(),
+            }
+        });
+
+        // Created on registration, and shared afterwards.
+        assert_eq!(1, created.load(Ordering::SeqCst));
+        assert_eq!(
+            "Goodbye!",
+            container.get::<Farewell>().unwrap().greeter.message
+        );
+        assert_eq!(1, created.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn eager_policy_missing_dependency() {
+        let mut container = ServiceContainer::default().with_policy(ResolutionPolicy::Eager);
+
+        // Registered before its dependency, failing on registration without panicking.
+        container.construct::<Farewell>();
+        assert_eq!(
+            Err(vec![ResolutionError::ResolverReturnedNone {
+                type_name: std::any::type_name::<Farewell>(),
+            }]),
+            container.resolve_eager(),
+        );
+
+        // Resolved again once the dependency is registered.
+        container.singleton(Greeter {
+            message: "Goodbye!".to_string(),
+        });
+        assert_eq!(Ok(()), container.resolve_eager());
+        assert_eq!(Ok(()), container.resolve_eager());
+    }
+
+    #[test]
+    fn register_if_constructable() {
+        let mut container = ServiceContainer::default();
+
+        assert!(!register_if_constructable!(container, Greeter));
+        assert!(register_if_constructable!(container, Farewell));
+        assert!(!container.has::<Greeter>());
+
+        container.put(Singleton::new(Greeter {
+            message: "Goodbye!".to_string(),
+        }));
+        assert_eq!(
+            "Goodbye!",
+            container.get::<Farewell>().unwrap().greeter.greet()
+        );
+
+        // Constructable only from another kind of container.
+        struct Scoped;
+        impl Construct<Scoped, Arc<ServiceContainer>> for Scoped {
+            fn construct(_container: &Arc<ServiceContainer>) -> Option<Scoped> {
+                Some(Scoped)
+            }
+        }
+
+        assert!(!register_if_constructable!(container, Scoped));
+    }
+
+    struct Wrapper;
+
+    #[test]
+    #[cfg_attr(
+        feature = "strict",
+        should_panic(expected = "registered against ruice::tests::Wrapper")
+    )]
+    fn get_against_another_container() {
+        let mut container = ServiceContainer::default();
+
+        container.put_for::<Greeter, Wrapper, _>(Singleton::new(Greeter {
+            message: "Hello, world!".to_string(),
+        }));
+
+        // Registered, but not resolvable against the container itself.
+        assert!(container.has::<Greeter>());
+        assert!(container.get::<Greeter>().is_none());
+        assert!(container.get_for::<Greeter, _>(&Wrapper).is_some());
+    }
+
+    #[test]
+    fn len() {
+        let mut container = ServiceContainer::default();
+        assert!(container.is_empty());
+
+        container.put(Singleton::new(Greeter {
+            message: "Hello, world!".to_string(),
+        }));
+        container.put(Singleton::new(42usize));
+        container.bind_by_async(|_| async { Some(Arc::new(42usize)) });
+        container.bind_by_async(|_| async { Some(Arc::new("async")) });
+
+        assert_eq!(3, container.len());
+        assert!(!container.is_empty());
+    }
+
+    #[test]
+    fn registrations() {
+        let mut container = ServiceContainer::default();
+
+        container.put(Singleton::new(42usize));
+        container.bind_by_async(|_| async { Some(Arc::new(42usize)) });
+        container.put(Singleton::new(Greeter {
+            message: "Hello, world!".to_string(),
+        }));
+        container.bind_by::<&str, _>(|_| Some(Arc::new("Taro")));
+
+        let greeter = std::any::type_name::<Greeter>();
+        assert_eq!(
+            vec!["&str", greeter, "usize"],
+            container.registered_type_names()
+        );
+        assert_eq!(
+            vec![
+                ("&str", ResolutionKind::BindBy),
+                (greeter, ResolutionKind::Singleton),
+                ("usize", ResolutionKind::Singleton),
+                ("usize", ResolutionKind::Async),
+            ],
+            container.registrations()
+        );
+
+        let mut asynchronous = vec![];
+        container.visit(&mut |name, _, is_async| {
+            if is_async {
+                asynchronous.push(name);
+            }
+        });
+        assert_eq!(vec!["usize"], asynchronous);
+    }
+
+    #[test]
+    fn swap() {
+        let mut container = ServiceContainer::default();
+
+        let greeter = Arc::new(Greeter {
+            message: "Hello".to_string(),
+        });
+        assert!(container.swap(Arc::clone(&greeter)).is_none());
+
+        // Override the service temporarily, and restore it.
+        let previous = container
+            .swap(Arc::new(Greeter {
+                message: "Mocked".to_string(),
+            }))
+            .unwrap();
+        assert!(Arc::ptr_eq(&greeter, &previous));
+        assert_eq!("Mocked", container.get::<Greeter>().unwrap().greet());
+
+        container.swap(previous);
+        assert!(Arc::ptr_eq(&greeter, &container.get::<Greeter>().unwrap()));
+    }
+
+    #[tokio::test]
+    async fn swap_async() {
+        let mut container = ServiceContainer::default();
+
+        container.bind_by_async(|_| async { Some(Arc::new(1u32)) });
+
+        let previous = container.swap_async(Arc::new(2u32)).await;
+        assert_eq!(Some(1), previous.as_deref().copied());
+        assert_eq!(2, *container.get_async::<u32>().await.unwrap());
+        assert!(container.is_resolved::<u32>());
+    }
+
+    #[test]
+    fn registered_under_another_id() {
+        let mut container = ServiceContainer::default();
+        container.put(Singleton::new(42usize));
+
+        let name = std::any::type_name::<usize>();
+        assert!(container.registered_under_another_id(TypeId::of::<u64>(), name));
+        assert!(!container.registered_under_another_id(TypeId::of::<usize>(), name));
+        assert!(!container.registered_under_another_id(TypeId::of::<u64>(), "u64"));
+    }
+
+    #[test]
+    fn fork() {
+        let mut container = ServiceContainer::default();
+        container.put(Singleton::new(Greeter {
+            message: "Hello".to_string(),
+        }));
+
+        let mut fork = container.fork();
+        assert!(Arc::ptr_eq(&container.services.0, &fork.services.0));
+
+        // Diverges on the first mutation, still sharing the resolvers.
+        fork.put(Singleton::new(42usize));
+        assert!(!Arc::ptr_eq(&container.services.0, &fork.services.0));
+        assert!(container.get::<usize>().is_none());
+        assert!(Arc::ptr_eq(
+            &container.get::<Greeter>().unwrap(),
+            &fork.get::<Greeter>().unwrap(),
+        ));
+
+        container.put(Singleton::new("original"));
+        assert!(fork.get::<&str>().is_none());
+    }
+
+    #[test]
+    fn with_capacity() {
+        let mut container = ServiceContainer::with_capacity(16);
+        assert!(container.services.capacity() >= 16);
+
+        container.put(Singleton::new(Greeter {
+            message: "Hello, world!".to_string(),
+        }));
+
+        assert_eq!("Hello, world!", container.get::<Greeter>().unwrap().greet());
+    }
+
+    trait Named: Send + Sync {
+        fn name(&self) -> &str;
+    }
+
+    trait Greet: Named {}
+
+    trait Welcome: Greet {}
+
+    struct Taro;
+
+    impl Named for Taro {
+        fn name(&self) -> &str {
+            "Taro"
+        }
+    }
+
+    impl Greet for Taro {}
+
+    impl Welcome for Taro {}
+
+    #[test]
+    fn bind_with_supertraits() {
+        let mut container = ServiceContainer::default();
+
+        bind_with_supertraits!(container, Taro as dyn Welcome, dyn Greet, dyn Named);
+
+        let welcome = container.get::<dyn Welcome>().unwrap();
+        let greet = container.get::<dyn Greet>().unwrap();
+        let named = container.get::<dyn Named>().unwrap();
+        assert_eq!("Taro", named.name());
+
+        // All the trait objects share the same service.
+        assert_eq!(
+            Arc::as_ptr(&welcome) as *const (),
+            Arc::as_ptr(&greet) as *const ()
+        );
+        assert_eq!(
+            Arc::as_ptr(&welcome) as *const (),
+            Arc::as_ptr(&named) as *const ()
+        );
+
+        // Only the listed traits are bound.
+        assert!(!container.has::<Taro>());
+    }
+
+    trait Handler: Send + Sync {
+        fn path(&self) -> &str;
+    }
+
+    struct Health;
+
+    impl Handler for Health {
+        fn path(&self) -> &str {
+            "/health"
+        }
+    }
+
+    struct Users {
+        greeter: Arc<Greeter>,
+    }
+
+    impl Handler for Users {
+        fn path(&self) -> &str {
+            "/users"
+        }
+    }
+
+    impl Construct for Users {
+        fn construct(container: &ServiceContainer) -> Option<Self> {
+            Some(Self {
+                greeter: container.get()?,
+            })
+        }
+    }
+
+    struct Greeting {
+        _greeter: Arc<Greeter>,
+    }
+
+    impl Construct for Greeting {
+        fn construct(container: &ServiceContainer) -> Option<Self> {
+            Some(Self {
+                _greeter: container.get()?,
+            })
+        }
+    }
+
+    #[test]
+    fn container() {
+        let greeter = Greeter {
+            message: "Hello!".to_string(),
+        };
+
+        let container = container! {
+            singleton: greeter,
+            bind: Taro as dyn Named,
+            bind: (Users { greeter: Arc::new(Greeter { message: "Hi!".to_string() }) })
+                as dyn Handler,
+            construct: Users,
+            lazy_singleton: Greeting,
+            tagged: [Health, Health] as dyn Handler,
+        };
+
+        assert_eq!("Hello!", container.get::<Greeter>().unwrap().greet());
+        assert_eq!("Taro", container.get::<dyn Named>().unwrap().name());
+        assert_eq!("/users", container.get::<dyn Handler>().unwrap().path());
+        assert_eq!("Hello!", container.get::<Users>().unwrap().greeter.greet());
+        assert!(container.get::<Greeting>().is_some());
+        assert_eq!(2, container.get_tagged::<dyn Handler>().len());
+
+        // Registered in order, so the later registration wins.
+        let container = container! {
+            singleton: 1u32,
+            singleton: 2u32,
+        };
+        assert_eq!(2, *container.get::<u32>().unwrap());
+
+        assert!(container! {}.is_empty());
+    }
+
+    struct Connection;
+
+    impl Construct for Connection {
+        fn construct(_container: &ServiceContainer) -> Option<Self> {
+            CONNECTIONS.fetch_add(1, Ordering::SeqCst);
+            Some(Self)
+        }
+    }
+
+    static CONNECTIONS: AtomicUsize = AtomicUsize::new(0);
+
+    struct StartupToken;
+
+    /// A pure resolver which lost its service, e.g. a handle released elsewhere.
+    struct Released;
+
+    impl Resolve<u64, ServiceContainer> for Released {
+        fn resolve(&self, _container: &ServiceContainer) -> Option<Arc<u64>> {
+            None
+        }
+
+        fn is_pure(&self) -> bool {
+            true
+        }
+    }
+
+    #[test]
+    fn validate() {
+        let mut container = ServiceContainer::default();
+
+        container.singleton(Greeter {
+            message: "Hello!".to_string(),
+        });
+        container.bind_optional::<dyn Named>(None);
+        container.put_once(StartupToken);
+        let connections = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&connections);
+        container.bind_by(move |_| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            Some(Arc::new(Connection))
+        });
+        container.bind_by(|_| None::<Arc<u32>>);
+        assert_eq!(Ok(()), container.validate());
+
+        container.put(Released);
+        assert_eq!(
+            Err(vec![ResolutionError::ResolverReturnedNone {
+                type_name: std::any::type_name::<u64>(),
+            }]),
+            container.validate(),
+        );
+
+        // Only the pure resolvers are resolved, so nothing is constructed or consumed.
+        assert_eq!(0, connections.load(Ordering::SeqCst));
+        assert!(container.get::<StartupToken>().is_some());
+    }
+
+    #[test]
+    fn validate_all() {
+        let mut container = ServiceContainer::default();
+
+        container.singleton(Greeter {
+            message: "Hello!".to_string(),
+        });
+        container.bind_optional::<dyn Named>(None);
+        container.put_once(StartupToken);
+        container.construct::<Connection>();
+        container.construct::<Greeting>();
+        container.bind_by(|_| None::<Arc<u32>>);
+
+        assert_eq!(
+            Err(vec![ResolutionError::ResolverReturnedNone {
+                type_name: std::any::type_name::<u32>(),
+            }]),
+            container.validate_all(),
+        );
+        assert_eq!(1, CONNECTIONS.load(Ordering::SeqCst));
+
+        // Neither validating nor warming up consumes the one-shot service.
+        container.singleton(42u32);
+        container.warm_up_all();
+        assert_eq!(Ok(()), container.validate_all());
+        assert!(container.get::<StartupToken>().is_some());
+    }
+
+    #[tokio::test]
+    async fn deps() {
+        struct Pool;
+
+        struct Repository {
+            greeter: Arc<Greeter>,
+            _pool: Arc<Pool>,
+        }
+
+        let mut container = ServiceContainer::default();
+        container.singleton(Greeter {
+            message: "Hello!".to_string(),
+        });
+        container.bind_by_async_owned(|c| async move {
+            let (greeter, pool) = deps_async!(c => Greeter, Pool)?;
+            Some(Arc::new(Repository {
+                greeter,
+                _pool: pool,
+            }))
+        });
+
+        // Resolved from the container resolving the repository, so registered later.
+        container.bind_by_async(|_| async { Some(Arc::new(Pool)) });
+
+        // Missing the pool synchronously.
+        container.bind_by(|c| {
+            let (greeter, pool) = deps!(c => Greeter, Pool)?;
+            Some(Arc::new(Repository {
+                greeter,
+                _pool: pool,
+            }))
+        });
+        assert!(container.get::<Repository>().is_none());
+
+        let repository = container.get_async::<Repository>().await.unwrap();
+        assert_eq!("Hello!", repository.greeter.greet());
     }
 }
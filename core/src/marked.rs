@@ -0,0 +1,174 @@
+//! Distinct singletons of the same type told apart by zero-sized marker types, e.g. a `Config`
+//! for reads and another for writes, without wrapping each of them in a newtype.
+
+use std::fmt;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use crate::{FrozenContainer, ServiceContainer, Services, Singleton};
+
+/// The service put under the marker `M`, registered as a service of its own, so it is apart
+/// from the one keyed by its type alone and `get::<Config>()` does not resolve it.
+///
+/// Being registered as usual, it is listed, removed, disposed, and grouped along with the other
+/// services, e.g. by `container.remove::<Marked<Config, Read>>()`. Unlike the names of
+/// [`KeyedContainer`](crate::KeyedContainer), a misspelled marker does not compile, and renaming
+/// it renames every use of it.
+pub struct Marked<S, M>
+where
+    S: ?Sized,
+    M: ?Sized,
+{
+    service: Arc<S>,
+    _marker: PhantomData<fn(&M)>,
+}
+
+impl<S, M> Marked<S, M>
+where
+    S: ?Sized,
+    M: ?Sized,
+{
+    /// Returns the service put under the marker.
+    pub fn service(&self) -> &Arc<S> {
+        &self.service
+    }
+}
+
+impl<S, M> fmt::Debug for Marked<S, M>
+where
+    S: ?Sized,
+    M: ?Sized,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Marked").finish_non_exhaustive()
+    }
+}
+
+impl ServiceContainer {
+    /// Puts a singleton service under the marker `M`, resolvable by
+    /// [`ServiceContainer::get_tagged_type`] with the same marker. Replaces the one put under
+    /// the marker before, if any.
+    ///
+    /// ```
+    /// use ruice::ServiceContainer;
+    ///
+    /// struct Config {
+    ///     url: &'static str,
+    /// }
+    ///
+    /// struct Read;
+    /// struct Write;
+    ///
+    /// let mut container = ServiceContainer::default();
+    /// container.singleton_tagged::<_, Read>(Config { url: "replica" });
+    /// container.singleton_tagged::<_, Write>(Config { url: "primary" });
+    ///
+    /// assert_eq!("replica", container.get_tagged_type::<Config, Read>().unwrap().url);
+    /// assert_eq!("primary", container.get_tagged_type::<Config, Write>().unwrap().url);
+    /// ```
+    pub fn singleton_tagged<S, M>(&mut self, service: S)
+    where
+        S: Send + Sync + 'static,
+        M: ?Sized + 'static,
+    {
+        self.bind_tagged_type::<S, M>(Arc::new(service));
+    }
+
+    /// Puts the instance, e.g. a trait object, under the marker `M`.
+    /// See [`ServiceContainer::singleton_tagged`].
+    pub fn bind_tagged_type<S, M>(&mut self, service: Arc<S>)
+    where
+        S: ?Sized + Send + Sync + 'static,
+        M: ?Sized + 'static,
+    {
+        self.put(Singleton::new(Marked::<S, M> {
+            service,
+            _marker: PhantomData,
+        }));
+    }
+
+    /// Returns whether a service is put under the marker `M` or not.
+    pub fn has_tagged_type<S, M>(&self) -> bool
+    where
+        S: ?Sized + 'static,
+        M: ?Sized + 'static,
+    {
+        self.has::<Marked<S, M>>()
+    }
+
+    /// Gets the service put under the marker `M`.
+    pub fn get_tagged_type<S, M>(&self) -> Option<Arc<S>>
+    where
+        S: ?Sized + Send + Sync + 'static,
+        M: ?Sized + 'static,
+    {
+        self.get::<Marked<S, M>>()
+            .map(|marked| Arc::clone(&marked.service))
+    }
+}
+
+impl FrozenContainer {
+    /// Gets the service put under the marker `M`. See [`ServiceContainer::get_tagged_type`].
+    pub fn get_tagged_type<S, M>(&self) -> Option<Arc<S>>
+    where
+        S: ?Sized + Send + Sync + 'static,
+        M: ?Sized + 'static,
+    {
+        self.get::<Marked<S, M>>()
+            .map(|marked| Arc::clone(&marked.service))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Services, SingletonServices};
+
+    #[derive(Debug, PartialEq)]
+    struct Config(&'static str);
+
+    struct Read;
+
+    struct Write;
+
+    trait Storage: Send + Sync {}
+
+    struct Disk;
+
+    impl Storage for Disk {}
+
+    #[test]
+    fn singleton_tagged() {
+        let mut container = ServiceContainer::default();
+
+        container.singleton(Config("default"));
+        container.singleton_tagged::<_, Read>(Config("replica"));
+        container.singleton_tagged::<_, Write>(Config("primary"));
+        container.bind_tagged_type::<dyn Storage, Read>(Arc::new(Disk));
+
+        assert_eq!(
+            Config("replica"),
+            *container.get_tagged_type::<Config, Read>().unwrap()
+        );
+        assert_eq!(
+            Config("primary"),
+            *container.get_tagged_type::<Config, Write>().unwrap()
+        );
+        assert_eq!(Config("default"), *container.get::<Config>().unwrap());
+        assert!(container.get_tagged_type::<dyn Storage, Read>().is_some());
+
+        // Keyed by both the service and the marker.
+        assert!(container.has_tagged_type::<Config, Read>());
+        assert!(!container.has_tagged_type::<dyn Storage, Write>());
+        assert!(container.get_tagged_type::<String, Read>().is_none());
+
+        // Registered as services of their own.
+        assert_eq!(4, container.len());
+        assert!(container.has::<Marked<Config, Write>>());
+        assert!(container.remove::<Marked<Config, Write>>());
+        assert!(!container.has_tagged_type::<Config, Write>());
+
+        let container = container.freeze();
+        assert!(container.get_tagged_type::<Config, Read>().is_some());
+    }
+}
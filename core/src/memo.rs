@@ -0,0 +1,299 @@
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::future::poll_fn;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::task::{Poll, Waker};
+
+use async_trait::async_trait;
+
+use crate::{
+    AsyncResolver, AsyncServices, Resolve, Resolver, ServiceContainer, ServiceVisitor, Services,
+};
+
+/// Memoizes the asynchronous resolutions for a scope such as a request, so the repeated
+/// [`AsyncServices::get_async`] calls for a service within the scope share the same instance,
+/// while the next scope resolves it afresh. The memoized services are released when the scope
+/// is dropped.
+///
+/// The scope resolves the services from a clone of the container, which shares the resolvers
+/// and the singletons with it. The services registered on the container resolve their
+/// dependencies from the container directly, bypassing the scope; the resolvers put on the
+/// scope resolve theirs through it, sharing the memoized ones. Synchronous resolutions are not
+/// memoized.
+///
+/// The first resolution of a service within the scope is single-flight: the concurrent ones
+/// wait for it and share its instance. If it fails or is cancelled, the next one resolves the
+/// service again.
+///
+/// ```
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// use std::sync::Arc;
+///
+/// use ruice::bind::AsyncBindServices;
+/// use ruice::{AsyncServices, MemoScope, ServiceContainer};
+///
+/// struct Connection;
+///
+/// let mut container = ServiceContainer::default();
+/// container.bind_by_async(|_| async { Some(Arc::new(Connection)) });
+///
+/// let scope = MemoScope::new(&container);
+/// let connection = scope.get_async::<Connection>().await.unwrap();
+/// assert!(Arc::ptr_eq(&connection, &scope.get_async().await.unwrap()));
+///
+/// // Another scope connects again.
+/// let scope = MemoScope::new(&container);
+/// assert!(!Arc::ptr_eq(&connection, &scope.get_async().await.unwrap()));
+/// # }
+/// ```
+#[derive(Debug, Default)]
+pub struct MemoScope {
+    inner: ServiceContainer,
+    memoized: Mutex<HashMap<TypeId, Memo>>,
+}
+
+/// The memoized service, or the wakers of the resolutions waiting for the first one.
+#[derive(Debug)]
+enum Memo {
+    Resolving(Vec<Waker>),
+    Resolved(Arc<dyn Any + Send + Sync>),
+}
+
+/// Hands the resolution over to the waiting ones when dropped unresolved, i.e. failed or
+/// cancelled.
+struct Resolving<'a> {
+    memoized: &'a Mutex<HashMap<TypeId, Memo>>,
+    id: TypeId,
+}
+
+impl Drop for Resolving<'_> {
+    fn drop(&mut self) {
+        let mut memoized = self.memoized.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(Memo::Resolving(_)) = memoized.get(&self.id) {
+            if let Some(Memo::Resolving(wakers)) = memoized.remove(&self.id) {
+                wakers.into_iter().for_each(Waker::wake);
+            }
+        }
+    }
+}
+
+impl MemoScope {
+    pub fn new(container: &ServiceContainer) -> Self {
+        Self {
+            inner: container.clone(),
+            memoized: Mutex::default(),
+        }
+    }
+
+    /// Returns the memoized service, or starts resolving it if no one else is.
+    async fn memoized<S>(&self) -> Result<Option<Arc<S>>, Resolving<'_>>
+    where
+        S: ?Sized + Send + Sync + 'static,
+    {
+        let id = TypeId::of::<S>();
+
+        loop {
+            {
+                let mut memoized = self.memoized.lock().unwrap_or_else(PoisonError::into_inner);
+                match memoized.get(&id) {
+                    Some(Memo::Resolved(s)) => return Ok(s.downcast_ref::<Arc<S>>().cloned()),
+                    Some(Memo::Resolving(_)) => {}
+                    None => {
+                        memoized.insert(id, Memo::Resolving(vec![]));
+                        return Err(Resolving {
+                            memoized: &self.memoized,
+                            id,
+                        });
+                    }
+                }
+            }
+
+            // Waits for the one resolving it, then checks again as it may have failed.
+            poll_fn(|cx| {
+                let mut memoized = self.memoized.lock().unwrap_or_else(PoisonError::into_inner);
+                match memoized.get_mut(&id) {
+                    Some(Memo::Resolving(wakers)) => {
+                        if !wakers.iter().any(|w| w.will_wake(cx.waker())) {
+                            wakers.push(cx.waker().clone());
+                        }
+                        Poll::Pending
+                    }
+                    _ => Poll::Ready(()),
+                }
+            })
+            .await;
+        }
+    }
+}
+
+impl Services for MemoScope {
+    fn has<S>(&self) -> bool
+    where
+        S: ?Sized + 'static,
+    {
+        self.inner.has::<S>()
+    }
+
+    fn get<S>(&self) -> Option<Arc<S>>
+    where
+        S: ?Sized + Send + Sync + 'static,
+    {
+        match self.inner.services.get(&TypeId::of::<S>()) {
+            Some(e) if e.as_any().is::<Resolver<S, Self>>() => self.inner.get_for(self),
+            _ => self.inner.get(),
+        }
+    }
+
+    fn put<S, R>(&mut self, resolver: R)
+    where
+        S: ?Sized + Send + Sync + 'static,
+        R: Resolve<S, Self> + 'static,
+    {
+        self.inner.put_for::<S, Self, R>(resolver);
+    }
+
+    fn visit(&self, visitor: &mut impl ServiceVisitor) {
+        self.inner.visit(visitor);
+    }
+}
+
+#[async_trait]
+impl AsyncServices for MemoScope {
+    async fn get_async<S>(&self) -> Option<Arc<S>>
+    where
+        S: ?Sized + Send + Sync + 'static,
+    {
+        let resolving = match self.memoized::<S>().await {
+            Ok(s) => return s,
+            Err(resolving) => resolving,
+        };
+
+        // Not locked while resolving, as the resolver may resolve other services through the
+        // scope.
+        let resolved: Arc<S> = match self.inner.async_services.get(&TypeId::of::<S>()) {
+            Some(e) if e.as_any().is::<AsyncResolver<S, Self>>() => {
+                self.inner.get_async_for(self).await
+            }
+            _ => self.inner.get_async().await,
+        }?;
+
+        let mut memoized = self.memoized.lock().unwrap_or_else(PoisonError::into_inner);
+        let service = Memo::Resolved(Arc::new(Arc::clone(&resolved)));
+        if let Some(Memo::Resolving(wakers)) = memoized.insert(resolving.id, service) {
+            wakers.into_iter().for_each(Waker::wake);
+        }
+
+        Some(resolved)
+    }
+
+    fn put_async<S>(&mut self, resolver: AsyncResolver<S, Self>)
+    where
+        S: ?Sized + Send + Sync + 'static,
+    {
+        self.inner.put_async_for(resolver);
+    }
+
+    fn is_resolved<S>(&self) -> bool
+    where
+        S: ?Sized + 'static,
+    {
+        self.memoized
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&TypeId::of::<S>())
+            .is_some_and(|m| matches!(m, Memo::Resolved(_)))
+            || AsyncServices::is_resolved::<S>(&self.inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+    use crate::bind::AsyncBindServices;
+    use crate::{AsyncConstruct, AsyncConstructServices};
+
+    trait Database: Send + Sync {
+        fn id(&self) -> usize;
+    }
+
+    struct Connection(usize);
+
+    impl Database for Connection {
+        fn id(&self) -> usize {
+            self.0
+        }
+    }
+
+    struct Repository {
+        database: Arc<dyn Database>,
+    }
+
+    #[async_trait]
+    impl AsyncConstruct<Repository, MemoScope> for Repository {
+        async fn construct_async(container: &MemoScope) -> Option<Self> {
+            Some(Self {
+                database: container.get_async().await?,
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn get_async() {
+        let mut container = ServiceContainer::default();
+        let connections = Arc::new(AtomicUsize::new(0));
+
+        let counter = Arc::clone(&connections);
+        container.bind_by_async(move |_| {
+            let id = counter.fetch_add(1, Ordering::SeqCst);
+            async move { Some(Arc::new(Connection(id)) as Arc<dyn Database>) }
+        });
+
+        let mut scope = MemoScope::new(&container);
+
+        // The resolvers put on the scope share the memoized services.
+        scope.construct_async::<Repository>();
+
+        let database = scope.get_async::<dyn Database>().await.unwrap();
+        let repository = scope.get_async::<Repository>().await.unwrap();
+        assert_eq!(database.id(), repository.database.id());
+        assert!(scope.is_resolved::<dyn Database>());
+        assert_eq!(1, connections.load(Ordering::SeqCst));
+
+        // Another scope resolves afresh.
+        let scope = MemoScope::new(&container);
+        assert_ne!(
+            database.id(),
+            scope.get_async::<dyn Database>().await.unwrap().id()
+        );
+        assert_eq!(2, connections.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn get_async_concurrent() {
+        let mut container = ServiceContainer::default();
+        let connections = Arc::new(AtomicUsize::new(0));
+
+        let counter = Arc::clone(&connections);
+        container.bind_by_async(move |_| {
+            let id = counter.fetch_add(1, Ordering::SeqCst);
+            async move {
+                tokio::task::yield_now().await;
+                Some(Arc::new(Connection(id)) as Arc<dyn Database>)
+            }
+        });
+
+        // The concurrent first resolutions wait for the one resolving the service.
+        let scope = MemoScope::new(&container);
+        let (a, b, c) = tokio::join!(
+            scope.get_async::<dyn Database>(),
+            scope.get_async::<dyn Database>(),
+            scope.get_async::<dyn Database>(),
+        );
+        assert!(Arc::ptr_eq(a.as_ref().unwrap(), &b.unwrap()));
+        assert!(Arc::ptr_eq(a.as_ref().unwrap(), &c.unwrap()));
+        assert_eq!(1, connections.load(Ordering::SeqCst));
+    }
+}
@@ -0,0 +1,357 @@
+//! Asynchronous modules initializing the container in the order of their dependencies, e.g.
+//! connecting to the database, then running the migrations, then registering the repositories.
+
+use std::any::{type_name, TypeId};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+use async_trait::async_trait;
+
+use crate::ServiceContainer;
+
+/// Identifies a module by its type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ModuleId {
+    id: TypeId,
+    name: &'static str,
+}
+
+impl ModuleId {
+    pub fn of<M>() -> Self
+    where
+        M: ?Sized + 'static,
+    {
+        Self {
+            id: TypeId::of::<M>(),
+            name: type_name::<M>(),
+        }
+    }
+
+    /// Returns the type name of the module.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+/// An error occurred while running the modules.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum ModuleError {
+    #[error("Circular dependency detected between modules: {}", .0.join(" -> "))]
+    CircularDependency(Vec<&'static str>),
+
+    #[error("The module {module} depends on {dependency}, which is not registered.")]
+    MissingDependency {
+        module: &'static str,
+        dependency: &'static str,
+    },
+
+    #[error("The module {module} failed to initialize: {source}")]
+    InitFailed {
+        module: &'static str,
+        source: Box<dyn Error + Send + Sync>,
+    },
+}
+
+/// A module registering services into the container asynchronously, after the modules it
+/// depends on.
+#[async_trait]
+pub trait AsyncModule<C = ServiceContainer>: Send + Sync {
+    /// Returns the modules to be initialized before this one.
+    fn dependencies(&self) -> Vec<ModuleId> {
+        vec![]
+    }
+
+    async fn inject_async(&self, container: &mut C) -> Result<(), Box<dyn Error + Send + Sync>>;
+}
+
+/// Runs the registered modules in the order of their dependencies.
+pub struct AsyncModuleRegistry<C = ServiceContainer> {
+    modules: Vec<(ModuleId, Box<dyn AsyncModule<C>>)>,
+}
+
+impl<C> AsyncModuleRegistry<C>
+where
+    C: Send + Sync,
+{
+    pub fn new() -> Self {
+        Self { modules: vec![] }
+    }
+
+    /// Registers the module, replacing the one of the same type.
+    pub fn add<M>(&mut self, module: M) -> &mut Self
+    where
+        M: AsyncModule<C> + 'static,
+    {
+        let id = ModuleId::of::<M>();
+        self.modules.retain(|(m, _)| *m != id);
+        self.modules.push((id, Box::new(module)));
+
+        self
+    }
+
+    /// Initializes the modules one by one, each after its dependencies. Independent modules run
+    /// in the order of registration.
+    ///
+    /// The dependency graph is checked before running any module. The first module failed to
+    /// initialize aborts the run, leaving the modules after it not run.
+    pub async fn run(&self, container: &mut C) -> Result<(), ModuleError> {
+        for i in self.sorted()? {
+            let (id, module) = &self.modules[i];
+            module
+                .inject_async(container)
+                .await
+                .map_err(|source| ModuleError::InitFailed {
+                    module: id.name,
+                    source,
+                })?;
+        }
+
+        Ok(())
+    }
+
+    /// Sorts the modules topologically, returning their indices.
+    fn sorted(&self) -> Result<Vec<usize>, ModuleError> {
+        let indices = self
+            .modules
+            .iter()
+            .enumerate()
+            .map(|(i, (id, _))| (*id, i))
+            .collect::<HashMap<_, _>>();
+
+        let mut sorted = Vec::with_capacity(self.modules.len());
+        let mut visited = vec![false; self.modules.len()];
+        let mut visiting = vec![];
+
+        for i in 0..self.modules.len() {
+            self.visit(i, &indices, &mut visited, &mut visiting, &mut sorted)?;
+        }
+
+        Ok(sorted)
+    }
+
+    fn visit(
+        &self,
+        i: usize,
+        indices: &HashMap<ModuleId, usize>,
+        visited: &mut [bool],
+        visiting: &mut Vec<usize>,
+        sorted: &mut Vec<usize>,
+    ) -> Result<(), ModuleError> {
+        if visited[i] {
+            return Ok(());
+        }
+
+        if let Some(start) = visiting.iter().position(|v| *v == i) {
+            let mut cycle = visiting[start..]
+                .iter()
+                .map(|v| self.modules[*v].0.name)
+                .collect::<Vec<_>>();
+            cycle.push(self.modules[i].0.name);
+
+            return Err(ModuleError::CircularDependency(cycle));
+        }
+
+        visiting.push(i);
+        let (id, module) = &self.modules[i];
+        for dependency in module.dependencies() {
+            let Some(&d) = indices.get(&dependency) else {
+                return Err(ModuleError::MissingDependency {
+                    module: id.name,
+                    dependency: dependency.name,
+                });
+            };
+
+            self.visit(d, indices, visited, visiting, sorted)?;
+        }
+        visiting.pop();
+
+        visited[i] = true;
+        sorted.push(i);
+
+        Ok(())
+    }
+}
+
+impl<C> Default for AsyncModuleRegistry<C>
+where
+    C: Send + Sync,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C> fmt::Debug for AsyncModuleRegistry<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.modules.iter().map(|(id, _)| id.name))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+    use crate::{Services, SingletonServices};
+
+    type Log = Arc<Mutex<Vec<&'static str>>>;
+
+    struct Pool;
+
+    struct Database(Log);
+
+    #[async_trait]
+    impl AsyncModule for Database {
+        async fn inject_async(
+            &self,
+            container: &mut ServiceContainer,
+        ) -> Result<(), Box<dyn Error + Send + Sync>> {
+            tokio::task::yield_now().await;
+            container.singleton(Pool);
+            self.0.lock().unwrap().push("database");
+
+            Ok(())
+        }
+    }
+
+    struct Migrations(Log);
+
+    #[async_trait]
+    impl AsyncModule for Migrations {
+        fn dependencies(&self) -> Vec<ModuleId> {
+            vec![ModuleId::of::<Database>()]
+        }
+
+        async fn inject_async(
+            &self,
+            container: &mut ServiceContainer,
+        ) -> Result<(), Box<dyn Error + Send + Sync>> {
+            container.get::<Pool>().ok_or("the pool is not connected")?;
+            self.0.lock().unwrap().push("migrations");
+
+            Ok(())
+        }
+    }
+
+    struct Repositories(Log, bool);
+
+    #[async_trait]
+    impl AsyncModule for Repositories {
+        fn dependencies(&self) -> Vec<ModuleId> {
+            vec![ModuleId::of::<Migrations>(), ModuleId::of::<Database>()]
+        }
+
+        async fn inject_async(
+            &self,
+            _container: &mut ServiceContainer,
+        ) -> Result<(), Box<dyn Error + Send + Sync>> {
+            if self.1 {
+                return Err("invalid schema".into());
+            }
+
+            self.0.lock().unwrap().push("repositories");
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn run() {
+        let log = Log::default();
+        let mut registry = AsyncModuleRegistry::new();
+        registry
+            .add(Repositories(Arc::clone(&log), false))
+            .add(Migrations(Arc::clone(&log)))
+            .add(Database(Arc::clone(&log)));
+
+        let mut container = ServiceContainer::default();
+        registry.run(&mut container).await.unwrap();
+
+        assert_eq!(
+            vec!["database", "migrations", "repositories"],
+            *log.lock().unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn run_failed() {
+        let log = Log::default();
+        let mut registry = AsyncModuleRegistry::new();
+        registry
+            .add(Repositories(Arc::clone(&log), true))
+            .add(Migrations(Arc::clone(&log)));
+
+        let mut container = ServiceContainer::default();
+        let Err(ModuleError::MissingDependency { module, dependency }) =
+            registry.run(&mut container).await
+        else {
+            panic!("the missing dependency must be detected");
+        };
+        assert_eq!(type_name::<Migrations>(), module);
+        assert_eq!(type_name::<Database>(), dependency);
+        assert!(log.lock().unwrap().is_empty());
+
+        registry.add(Database(Arc::clone(&log)));
+        let Err(ModuleError::InitFailed { module, source }) = registry.run(&mut container).await
+        else {
+            panic!("the failure must be propagated");
+        };
+        assert_eq!(type_name::<Repositories>(), module);
+        assert_eq!("invalid schema", source.to_string());
+    }
+
+    struct Chicken;
+
+    #[async_trait]
+    impl AsyncModule for Chicken {
+        fn dependencies(&self) -> Vec<ModuleId> {
+            vec![ModuleId::of::<Egg>()]
+        }
+
+        async fn inject_async(
+            &self,
+            _container: &mut ServiceContainer,
+        ) -> Result<(), Box<dyn Error + Send + Sync>> {
+            unreachable!()
+        }
+    }
+
+    struct Egg;
+
+    #[async_trait]
+    impl AsyncModule for Egg {
+        fn dependencies(&self) -> Vec<ModuleId> {
+            vec![ModuleId::of::<Chicken>()]
+        }
+
+        async fn inject_async(
+            &self,
+            _container: &mut ServiceContainer,
+        ) -> Result<(), Box<dyn Error + Send + Sync>> {
+            unreachable!()
+        }
+    }
+
+    #[tokio::test]
+    async fn run_circular() {
+        let mut registry = AsyncModuleRegistry::new();
+        registry.add(Chicken).add(Egg);
+
+        let Err(ModuleError::CircularDependency(cycle)) =
+            registry.run(&mut ServiceContainer::default()).await
+        else {
+            panic!("the circular dependency must be detected");
+        };
+        assert_eq!(
+            vec![
+                type_name::<Chicken>(),
+                type_name::<Egg>(),
+                type_name::<Chicken>()
+            ],
+            cycle
+        );
+    }
+}
@@ -0,0 +1,153 @@
+use std::sync::{Mutex, MutexGuard, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use crate::{ServiceContainer, Services, SingletonServices};
+
+pub trait MutexServices: Services {
+    /// Puts a singleton service guarded by a [`Mutex`], resolvable as `Mutex<S>`.
+    fn singleton_mutex<S>(&mut self, service: S)
+    where
+        S: Send + 'static,
+    {
+        self.singleton(Mutex::new(service));
+    }
+
+    /// Puts a singleton service guarded by a [`RwLock`], resolvable as `RwLock<S>`.
+    fn singleton_rw_lock<S>(&mut self, service: S)
+    where
+        S: Send + Sync + 'static,
+    {
+        self.singleton(RwLock::new(service));
+    }
+}
+
+impl<C> MutexServices for C where C: Services {}
+
+/// The guards borrow the lock held by the container, which keeps it alive, so they are valid
+/// as long as the container is borrowed. To hold a lock beyond that, e.g. across a spawned task,
+/// get the lock itself by `get::<Mutex<S>>()`, owning the [`Arc`](std::sync::Arc) of it.
+///
+/// Only the services put as singletons can be locked this way. A poisoned lock is recovered, as
+/// the singletons have no other owner to restore them.
+impl ServiceContainer {
+    /// Locks the singleton service put by [`MutexServices::singleton_mutex`].
+    pub fn get_locked<S>(&self) -> Option<MutexGuard<'_, S>>
+    where
+        S: Send + 'static,
+    {
+        Some(
+            self.get_ref::<Mutex<S>>()?
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner),
+        )
+    }
+
+    /// Modifies the singleton service put by [`MutexServices::singleton_mutex`] or
+    /// [`MutexServices::singleton_rw_lock`] in place, keeping its resolver, unlike
+    /// [`Services::replace`]. The clones of the container sharing the service observe the
+    /// modification too. Returns `false` if the service is not put that way.
+    pub fn modify<S, F>(&self, f: F) -> bool
+    where
+        S: Send + Sync + 'static,
+        F: FnOnce(&mut S),
+    {
+        if let Some(mut service) = self.get_locked::<S>() {
+            f(&mut service);
+        } else if let Some(mut service) = self.get_write::<S>() {
+            f(&mut service);
+        } else {
+            return false;
+        }
+
+        true
+    }
+
+    /// Locks the singleton service put by [`MutexServices::singleton_rw_lock`] for reading.
+    pub fn get_read<S>(&self) -> Option<RwLockReadGuard<'_, S>>
+    where
+        S: Send + Sync + 'static,
+    {
+        Some(
+            self.get_ref::<RwLock<S>>()?
+                .read()
+                .unwrap_or_else(PoisonError::into_inner),
+        )
+    }
+
+    /// Locks the singleton service put by [`MutexServices::singleton_rw_lock`] for writing.
+    pub fn get_write<S>(&self) -> Option<RwLockWriteGuard<'_, S>>
+    where
+        S: Send + Sync + 'static,
+    {
+        Some(
+            self.get_ref::<RwLock<S>>()?
+                .write()
+                .unwrap_or_else(PoisonError::into_inner),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    #[derive(Default)]
+    struct Counter {
+        count: usize,
+    }
+
+    #[test]
+    fn singleton_mutex() {
+        let mut container = ServiceContainer::default();
+
+        container.singleton_mutex(Counter::default());
+
+        container.get_locked::<Counter>().unwrap().count += 1;
+        container.get_locked::<Counter>().unwrap().count += 1;
+
+        assert_eq!(2, container.get_locked::<Counter>().unwrap().count);
+        assert_eq!(
+            2,
+            container
+                .get::<Mutex<Counter>>()
+                .unwrap()
+                .lock()
+                .unwrap()
+                .count
+        );
+    }
+
+    #[test]
+    fn singleton_rw_lock() {
+        let mut container = ServiceContainer::default();
+
+        container.singleton_rw_lock(HashMap::<String, String>::new());
+
+        container
+            .get_write::<HashMap<String, String>>()
+            .unwrap()
+            .insert("name".to_string(), "Taro".to_string());
+
+        let cache = container.get_read::<HashMap<String, String>>().unwrap();
+        assert_eq!(Some("Taro"), cache.get("name").map(String::as_str));
+
+        // Not registered as a mutex.
+        assert!(container.get_locked::<HashMap<String, String>>().is_none());
+    }
+
+    #[test]
+    fn modify() {
+        let mut container = ServiceContainer::default();
+
+        container.singleton_mutex(Counter::default());
+        let cloned = container.clone();
+
+        assert!(container.modify::<Counter, _>(|c| c.count += 1));
+        assert_eq!(1, cloned.get_locked::<Counter>().unwrap().count);
+
+        // Not put as a mutex nor a rw-lock.
+        container.singleton("Taro".to_string());
+        assert!(!container.modify::<String, _>(|s| s.push('!')));
+    }
+}
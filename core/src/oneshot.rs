@@ -0,0 +1,64 @@
+use std::sync::{Arc, Mutex, PoisonError};
+
+use crate::{ResolutionKind, Resolve, Services};
+
+/// Hands the service out to the first resolution only.
+pub struct OneShot<S> {
+    service: Mutex<Option<Arc<S>>>,
+}
+
+impl<S> OneShot<S> {
+    pub fn new(service: S) -> Self {
+        Self {
+            service: Mutex::new(Some(Arc::new(service))),
+        }
+    }
+}
+
+impl<S, C> Resolve<S, C> for OneShot<S>
+where
+    S: Send + Sync,
+{
+    fn resolve(&self, _container: &C) -> Option<Arc<S>> {
+        self.service
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take()
+    }
+
+    fn kind(&self) -> ResolutionKind {
+        ResolutionKind::OneShot
+    }
+}
+
+pub trait OneShotServices: Services {
+    /// Puts a service which is moved out by the first [`Services::get`].
+    /// Subsequent resolutions return `None`, while the service stays registered.
+    fn put_once<S>(&mut self, service: S)
+    where
+        S: Send + Sync + 'static,
+    {
+        self.put(OneShot::new(service));
+    }
+}
+
+impl<C> OneShotServices for C where C: Services {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ServiceContainer;
+
+    struct StartupToken;
+
+    #[test]
+    fn put_once() {
+        let mut container = ServiceContainer::default();
+
+        container.put_once(StartupToken);
+
+        assert!(container.get::<StartupToken>().is_some());
+        assert!(container.get::<StartupToken>().is_none());
+        assert!(container.has::<StartupToken>());
+    }
+}
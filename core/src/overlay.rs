@@ -0,0 +1,234 @@
+use std::any::TypeId;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::{
+    AsyncResolver, AsyncServices, Bound, Resolve, ServiceContainer, ServiceVisitor, Services,
+};
+
+/// Layers overrides, such as test doubles, on top of a container without mutating it.
+///
+/// Resolving a service checks the overrides first, then delegates to the base container. The
+/// services resolved from the base resolve their dependencies from the base, so they do not see
+/// the overrides. Register the component under test on the overlay instead, e.g. by
+/// [`ConstructServices::construct`](crate::ConstructServices::construct) with a
+/// [`Construct`](crate::Construct) implemented generically over the container, so it resolves
+/// its dependencies through the overlay:
+///
+/// ```
+/// use std::sync::Arc;
+///
+/// use ruice::prelude::*;
+/// use ruice::OverlayContainer;
+///
+/// trait Database: Send + Sync {
+///     fn name(&self) -> &str;
+/// }
+///
+/// struct Postgres;
+///
+/// impl Database for Postgres {
+///     fn name(&self) -> &str {
+///         "postgres"
+///     }
+/// }
+///
+/// struct MockDatabase;
+///
+/// impl Database for MockDatabase {
+///     fn name(&self) -> &str {
+///         "mock"
+///     }
+/// }
+///
+/// struct Repository {
+///     database: Arc<dyn Database>,
+/// }
+///
+/// impl<C: Services> Construct<Self, C> for Repository {
+///     fn construct(container: &C) -> Option<Self> {
+///         Some(Self {
+///             database: container.get()?,
+///         })
+///     }
+/// }
+///
+/// let mut base = ServiceContainer::default();
+/// base.bind::<dyn Database>(Arc::new(Postgres));
+/// base.construct::<Repository>();
+/// let base = Arc::new(base);
+///
+/// let mut overlay = OverlayContainer::new(Arc::clone(&base));
+/// overlay.override_with::<dyn Database>(Arc::new(MockDatabase));
+/// overlay.construct::<Repository>();
+///
+/// assert_eq!("mock", overlay.get::<Repository>().unwrap().database.name());
+/// assert_eq!("postgres", base.get::<Repository>().unwrap().database.name());
+/// ```
+#[derive(Debug)]
+pub struct OverlayContainer {
+    base: Arc<ServiceContainer>,
+    overrides: ServiceContainer,
+}
+
+impl OverlayContainer {
+    pub fn new(base: Arc<ServiceContainer>) -> Self {
+        Self {
+            base,
+            overrides: ServiceContainer::default(),
+        }
+    }
+
+    pub fn base(&self) -> &Arc<ServiceContainer> {
+        &self.base
+    }
+
+    /// Overrides the service by the instance, e.g. a mock, resolved instead of the base one.
+    pub fn override_with<S>(&mut self, service: Arc<S>)
+    where
+        S: ?Sized + Send + Sync + 'static,
+    {
+        self.put(Bound::from(service));
+    }
+
+    /// Returns whether the service is overridden by the overlay.
+    pub fn is_overridden<S>(&self) -> bool
+    where
+        S: ?Sized + 'static,
+    {
+        self.overrides.has::<S>()
+    }
+}
+
+impl Services for OverlayContainer {
+    fn has<S>(&self) -> bool
+    where
+        S: ?Sized + 'static,
+    {
+        self.overrides.has::<S>() || self.base.has::<S>()
+    }
+
+    fn get<S>(&self) -> Option<Arc<S>>
+    where
+        S: ?Sized + Send + Sync + 'static,
+    {
+        if self.overrides.services.contains_key(&TypeId::of::<S>()) {
+            return self.overrides.get_for(self);
+        }
+
+        self.base.get()
+    }
+
+    fn put<S, R>(&mut self, resolver: R)
+    where
+        S: ?Sized + Send + Sync + 'static,
+        R: Resolve<S, Self> + 'static,
+    {
+        self.overrides.put_for::<S, Self, R>(resolver);
+    }
+
+    /// Visits the overrides, then the registrations of the base, including the overridden ones.
+    fn visit(&self, visitor: &mut impl ServiceVisitor) {
+        self.overrides.visit(visitor);
+        self.base.visit(visitor);
+    }
+}
+
+#[async_trait]
+impl AsyncServices for OverlayContainer {
+    async fn get_async<S>(&self) -> Option<Arc<S>>
+    where
+        S: ?Sized + Send + Sync + 'static,
+    {
+        let id = TypeId::of::<S>();
+        if self.overrides.async_services.contains_key(&id) {
+            return self.overrides.get_async_for(self).await;
+        }
+
+        // Overridden synchronously, which takes precedence over the base as well.
+        if self.overrides.services.contains_key(&id) {
+            return self.overrides.get_for(self);
+        }
+
+        self.base.get_async().await
+    }
+
+    fn put_async<S>(&mut self, resolver: AsyncResolver<S, Self>)
+    where
+        S: ?Sized + Send + Sync + 'static,
+    {
+        self.overrides.put_async_for(resolver);
+    }
+
+    fn is_resolved<S>(&self) -> bool
+    where
+        S: ?Sized + 'static,
+    {
+        if self
+            .overrides
+            .async_services
+            .contains_key(&TypeId::of::<S>())
+        {
+            return AsyncServices::is_resolved::<S>(&self.overrides);
+        }
+
+        AsyncServices::is_resolved::<S>(self.base.as_ref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bind::AsyncBindServices;
+    use crate::SingletonServices;
+
+    trait Mailer: Send + Sync {
+        fn send(&self) -> &str;
+    }
+
+    struct Smtp;
+
+    impl Mailer for Smtp {
+        fn send(&self) -> &str {
+            "smtp"
+        }
+    }
+
+    struct FakeMailer;
+
+    impl Mailer for FakeMailer {
+        fn send(&self) -> &str {
+            "fake"
+        }
+    }
+
+    struct Config;
+
+    #[tokio::test]
+    async fn override_with() {
+        let mut base = ServiceContainer::default();
+        base.singleton(Config);
+        base.bind_by_async(|_| async { Some(Arc::new(Smtp) as Arc<dyn Mailer>) });
+        let base = Arc::new(base);
+
+        let mut overlay = OverlayContainer::new(Arc::clone(&base));
+        overlay.override_with::<dyn Mailer>(Arc::new(FakeMailer));
+
+        // Overridden services are resolved from the overlay, the rest from the base.
+        assert!(overlay.is_overridden::<dyn Mailer>());
+        assert_eq!("fake", overlay.get::<dyn Mailer>().unwrap().send());
+        assert_eq!(
+            "fake",
+            overlay.get_async::<dyn Mailer>().await.unwrap().send()
+        );
+        assert!(Arc::ptr_eq(
+            &base.get::<Config>().unwrap(),
+            &overlay.get::<Config>().unwrap()
+        ));
+
+        // The base is left untouched.
+        assert_eq!("smtp", base.get_async::<dyn Mailer>().await.unwrap().send());
+        assert!(!overlay.is_overridden::<Config>());
+    }
+}
@@ -0,0 +1,141 @@
+//! Low-level access to the type-erased bindings of the container, for code registering services
+//! whose types are not known where the registration happens, e.g. registries generated by
+//! macros or plugins loaded at runtime.
+
+use std::any::TypeId;
+use std::fmt;
+use std::sync::Arc;
+
+use crate::{Entry, ResolutionKind, Resolve, Resolver, ServiceContainer};
+
+/// A type-erased binding of a service, holding its resolver.
+///
+/// A binding always knows the type of its service, so it can only be put under that type: the
+/// container stays consistent whatever bindings are put into it. The binding resolves the
+/// service against the container type given on creation.
+#[derive(Clone)]
+pub struct RawBinding {
+    entry: Arc<dyn Entry>,
+}
+
+impl RawBinding {
+    /// Creates a binding of the service resolved by the resolver against the container type.
+    pub fn new<S, C, R>(resolver: R) -> Self
+    where
+        S: ?Sized + Send + Sync + 'static,
+        C: Send + Sync + 'static,
+        R: Resolve<S, C> + 'static,
+    {
+        Self {
+            entry: Arc::new(Resolver::<S, C>::new(resolver)),
+        }
+    }
+
+    /// Returns the type ID of the service.
+    pub fn service_id(&self) -> TypeId {
+        self.entry.service_id()
+    }
+
+    /// Returns the type name of the service.
+    pub fn type_name(&self) -> &'static str {
+        self.entry.type_name()
+    }
+
+    /// Returns the kind of the resolver.
+    pub fn kind(&self) -> ResolutionKind {
+        self.entry.kind()
+    }
+
+    /// Resolves the service against the container, or returns `None` if either the service or
+    /// the container is not of the types of the binding.
+    pub(crate) fn resolve<S, C>(&self, container: &C) -> Option<Arc<S>>
+    where
+        S: ?Sized + Send + Sync + 'static,
+        C: Send + Sync + 'static,
+    {
+        self.entry
+            .resolve_any(container)?
+            .downcast::<Arc<S>>()
+            .ok()
+            .map(|s| *s)
+    }
+}
+
+impl fmt::Debug for RawBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RawBinding")
+            .field("type_name", &self.type_name())
+            .field("kind", &self.kind())
+            .finish()
+    }
+}
+
+impl ServiceContainer {
+    /// Gets the synchronous binding of the service, sharing its resolver.
+    pub fn get_raw<S>(&self) -> Option<RawBinding>
+    where
+        S: ?Sized + 'static,
+    {
+        self.get_raw_by_id(TypeId::of::<S>())
+    }
+
+    /// Gets the synchronous binding of the service with the type ID, sharing its resolver.
+    pub fn get_raw_by_id(&self, id: TypeId) -> Option<RawBinding> {
+        self.services.get(&id).map(|e| RawBinding {
+            entry: Arc::clone(e),
+        })
+    }
+
+    /// Puts the binding under the type of its service, replacing the existing one.
+    pub fn put_raw(&mut self, binding: RawBinding) {
+        let kind = binding.kind();
+        let name = binding.type_name();
+        self.services.insert(binding.service_id(), binding.entry);
+
+        self.registered(name, kind);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Services, Singleton, SingletonServices};
+
+    struct Greeter;
+
+    #[test]
+    fn raw_binding() {
+        let mut source = ServiceContainer::default();
+        source.singleton(Greeter);
+
+        // Registrations are carried by type IDs only.
+        let registry: Vec<(TypeId, RawBinding)> = vec![
+            (
+                TypeId::of::<Greeter>(),
+                source.get_raw::<Greeter>().unwrap(),
+            ),
+            (
+                TypeId::of::<u32>(),
+                RawBinding::new::<u32, ServiceContainer, _>(Singleton::new(42u32)),
+            ),
+        ];
+
+        let mut container = ServiceContainer::default();
+        for (id, binding) in registry {
+            assert_eq!(id, binding.service_id());
+            container.put_raw(binding);
+        }
+
+        assert!(Arc::ptr_eq(
+            &source.get::<Greeter>().unwrap(),
+            &container.get::<Greeter>().unwrap(),
+        ));
+        assert_eq!(42, *container.get::<u32>().unwrap());
+        assert_eq!(
+            Some(ResolutionKind::Singleton),
+            container
+                .get_raw_by_id(TypeId::of::<u32>())
+                .map(|b| b.kind()),
+        );
+    }
+}
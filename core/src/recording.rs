@@ -0,0 +1,250 @@
+//! A container recording the services resolved from it, for verifying the wiring in tests.
+
+use std::any::{type_name, TypeId};
+use std::sync::{Arc, Mutex, PoisonError};
+
+use async_trait::async_trait;
+
+use crate::{
+    AsyncResolver, AsyncServices, Resolve, Resolver, ServiceContainer, ServiceVisitor, Services,
+};
+
+/// Wraps a container, recording every [`Services::get`] and [`AsyncServices::get_async`] call
+/// on it before forwarding the call, whether the service is resolved or not.
+///
+/// Only the calls on the recorder itself are recorded. The services registered on the inner
+/// container beforehand resolve their dependencies from the inner container directly, so a
+/// handler is verified against the services it asks for, not the whole dependency graph. The
+/// resolvers put on the recorder resolve their dependencies through it, recording them too.
+///
+/// ```
+/// use ruice::{RecordingContainer, ServiceContainer, Services, SingletonServices};
+///
+/// struct Database;
+/// struct Cache;
+///
+/// fn handle<C: Services>(container: &C) {
+///     let _database = container.get::<Database>();
+///     let _cache = container.get::<Cache>();
+/// }
+///
+/// let mut container = ServiceContainer::default();
+/// container.singleton(Database);
+///
+/// let container = RecordingContainer::new(container);
+/// handle(&container);
+///
+/// assert_eq!(
+///     vec![std::any::type_name::<Database>(), std::any::type_name::<Cache>()],
+///     container.resolved_types(),
+/// );
+/// ```
+#[derive(Default)]
+pub struct RecordingContainer {
+    inner: ServiceContainer,
+    resolved: Mutex<Vec<(TypeId, &'static str)>>,
+}
+
+impl RecordingContainer {
+    pub fn new(inner: ServiceContainer) -> Self {
+        Self {
+            inner,
+            resolved: Mutex::default(),
+        }
+    }
+
+    pub fn inner(&self) -> &ServiceContainer {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> ServiceContainer {
+        self.inner
+    }
+
+    /// Returns the type names of the services resolved so far, each once in the order of their
+    /// first resolution.
+    pub fn resolved_types(&self) -> Vec<&'static str> {
+        let resolved = self.resolved.lock().unwrap_or_else(PoisonError::into_inner);
+
+        let mut types = Vec::<(TypeId, &'static str)>::with_capacity(resolved.len());
+        for &(id, name) in resolved.iter() {
+            if !types.iter().any(|&(seen, _)| seen == id) {
+                types.push((id, name));
+            }
+        }
+
+        types.into_iter().map(|(_, name)| name).collect()
+    }
+
+    /// Returns whether the service has been resolved so far.
+    pub fn has_resolved<S>(&self) -> bool
+    where
+        S: ?Sized + 'static,
+    {
+        self.resolved
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .any(|&(id, _)| id == TypeId::of::<S>())
+    }
+
+    /// Forgets the services resolved so far, e.g. between the handlers under test.
+    pub fn clear(&self) {
+        self.resolved
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clear();
+    }
+
+    fn record<S>(&self)
+    where
+        S: ?Sized + 'static,
+    {
+        self.resolved
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push((TypeId::of::<S>(), type_name::<S>()));
+    }
+}
+
+impl Services for RecordingContainer {
+    fn has<S>(&self) -> bool
+    where
+        S: ?Sized + 'static,
+    {
+        self.inner.has::<S>()
+    }
+
+    fn get<S>(&self) -> Option<Arc<S>>
+    where
+        S: ?Sized + Send + Sync + 'static,
+    {
+        self.record::<S>();
+
+        match self.inner.services.get(&TypeId::of::<S>()) {
+            Some(e) if e.as_any().is::<Resolver<S, Self>>() => self.inner.get_for(self),
+            _ => self.inner.get(),
+        }
+    }
+
+    fn put<S, R>(&mut self, resolver: R)
+    where
+        S: ?Sized + Send + Sync + 'static,
+        R: Resolve<S, Self> + 'static,
+    {
+        self.inner.put_for::<S, Self, R>(resolver);
+    }
+
+    fn visit(&self, visitor: &mut impl ServiceVisitor) {
+        self.inner.visit(visitor);
+    }
+}
+
+#[async_trait]
+impl AsyncServices for RecordingContainer {
+    async fn get_async<S>(&self) -> Option<Arc<S>>
+    where
+        S: ?Sized + Send + Sync + 'static,
+    {
+        self.record::<S>();
+
+        match self.inner.async_services.get(&TypeId::of::<S>()) {
+            Some(e) if e.as_any().is::<AsyncResolver<S, Self>>() => {
+                self.inner.get_async_for(self).await
+            }
+            _ => self.inner.get_async().await,
+        }
+    }
+
+    fn put_async<S>(&mut self, resolver: AsyncResolver<S, Self>)
+    where
+        S: ?Sized + Send + Sync + 'static,
+    {
+        self.inner.put_async_for(resolver);
+    }
+
+    fn is_resolved<S>(&self) -> bool
+    where
+        S: ?Sized + 'static,
+    {
+        AsyncServices::is_resolved::<S>(&self.inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bind::AsyncBindServices;
+    use crate::{BindServices, Construct, ConstructServices, SingletonServices};
+
+    trait Database: Send + Sync {}
+
+    trait Cache: Send + Sync {}
+
+    struct Postgres;
+
+    impl Database for Postgres {}
+
+    struct Memory;
+
+    impl Cache for Memory {}
+
+    struct Repository {
+        _database: Arc<dyn Database>,
+    }
+
+    impl Construct<Repository, RecordingContainer> for Repository {
+        fn construct(container: &RecordingContainer) -> Option<Self> {
+            Some(Self {
+                _database: container.get()?,
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn resolved_types() {
+        let mut container = ServiceContainer::default();
+        container.bind::<dyn Database>(Arc::new(Postgres));
+        container.bind_by_async(|_| async { Some(Arc::new(Memory) as Arc<dyn Cache>) });
+
+        let container = RecordingContainer::new(container);
+        assert!(container.resolved_types().is_empty());
+
+        assert!(container.get::<dyn Database>().is_some());
+        assert!(container.get_async::<dyn Cache>().await.is_some());
+        assert!(container.get::<dyn Database>().is_some());
+        assert!(container.get::<String>().is_none());
+
+        assert_eq!(
+            vec![
+                type_name::<dyn Database>(),
+                type_name::<dyn Cache>(),
+                type_name::<String>(),
+            ],
+            container.resolved_types(),
+        );
+
+        container.clear();
+        assert!(!container.has_resolved::<dyn Database>());
+    }
+
+    #[test]
+    fn put_on_recorder() {
+        let mut container = RecordingContainer::default();
+        container.singleton(Postgres);
+        container.bind_by(|c| Some(c.get::<Postgres>()? as Arc<dyn Database>));
+        container.construct::<Repository>();
+
+        assert!(container.get::<Repository>().is_some());
+
+        // The dependencies resolved through the recorder are recorded too.
+        assert_eq!(
+            vec![
+                type_name::<Repository>(),
+                type_name::<dyn Database>(),
+                type_name::<Postgres>(),
+            ],
+            container.resolved_types(),
+        );
+    }
+}
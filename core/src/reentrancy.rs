@@ -0,0 +1,42 @@
+//! Tracks the asynchronous resolutions polled on the current thread, to diagnose the services
+//! resolved synchronously from within them.
+
+use std::cell::Cell;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+thread_local! {
+    static POLLING: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Marks the current thread as polling an asynchronous resolution while the inner future is
+/// polled. The mark is not kept across `.await` points, as the task may move to another thread.
+pub(crate) struct Resolving<F>(pub(crate) F);
+
+impl<F> Future for Resolving<F>
+where
+    F: Future + Unpin,
+{
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        struct Unmark;
+
+        impl Drop for Unmark {
+            fn drop(&mut self) {
+                POLLING.with(|p| p.set(p.get() - 1));
+            }
+        }
+
+        POLLING.with(|p| p.set(p.get() + 1));
+        let _unmark = Unmark;
+
+        Pin::new(&mut self.0).poll(cx)
+    }
+}
+
+/// Returns whether an asynchronous resolution is being polled on the current thread.
+pub(crate) fn in_async_resolution() -> bool {
+    POLLING.with(|p| p.get() > 0)
+}
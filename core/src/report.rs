@@ -0,0 +1,79 @@
+//! Records why a resolution failed on the current thread, as the resolvers return only `Option`,
+//! for reporting it by [`Services::try_get`](crate::Services::try_get) and
+//! [`AsyncServices::try_get_async`](crate::AsyncServices::try_get_async).
+//!
+//! Each reporting resolution records into a slot of its own, so the nested ones, such as the
+//! resolutions of the fields of a bundle, neither clear the failure of the enclosing one nor leak
+//! theirs into it.
+
+use std::cell::RefCell;
+use std::future::{poll_fn, Future};
+use std::pin::pin;
+
+use crate::ResolutionError;
+
+thread_local! {
+    static FAILURES: RefCell<Vec<Option<ResolutionError>>> = const { RefCell::new(vec![]) };
+}
+
+/// Pops the slot of the reporting resolution when dropped, even on panic, moving the failure
+/// recorded into the given one if any.
+struct Guard<'a>(Option<&'a mut Option<ResolutionError>>);
+
+impl Drop for Guard<'_> {
+    fn drop(&mut self) {
+        let failure = FAILURES.with(|f| f.borrow_mut().pop()).flatten();
+        if let Some(slot) = self.0.as_mut() {
+            **slot = failure;
+        }
+    }
+}
+
+/// Records the failure for the innermost reporting resolution, unless it already has one, as the
+/// first failure causes the others. Nothing is recorded outside of reporting resolutions.
+pub(crate) fn record(error: ResolutionError) {
+    FAILURES.with(|f| {
+        if let Some(failure @ None) = f.borrow_mut().last_mut() {
+            *failure = Some(error);
+        }
+    });
+}
+
+/// Runs the resolution, reporting the failure recorded meanwhile if it failed, or that the
+/// resolver of `type_name` returned nothing otherwise.
+pub(crate) fn scope<T, F>(type_name: &'static str, resolve: F) -> Result<T, ResolutionError>
+where
+    F: FnOnce() -> Option<T>,
+{
+    FAILURES.with(|f| f.borrow_mut().push(None));
+    let _guard = Guard(None);
+
+    resolve().ok_or_else(|| {
+        FAILURES
+            .with(|f| f.borrow_mut().last_mut().and_then(Option::take))
+            .unwrap_or(ResolutionError::ResolverReturnedNone { type_name })
+    })
+}
+
+/// Runs the asynchronous resolution like [`scope`]. The slot is kept by the future between its
+/// polls, as the task may be polled on another thread, or interleaved with other tasks.
+pub(crate) async fn scope_async<T, F>(
+    type_name: &'static str,
+    resolve: F,
+) -> Result<T, ResolutionError>
+where
+    F: Future<Output = Option<T>>,
+{
+    let mut resolve = pin!(resolve);
+    let mut failure = None;
+
+    let resolved = poll_fn(|cx| {
+        FAILURES.with(|f| f.borrow_mut().push(failure.take()));
+        let _guard = Guard(Some(&mut failure));
+
+        resolve.as_mut().poll(cx)
+    })
+    .await;
+
+    resolved.ok_or_else(|| failure.unwrap_or(ResolutionError::ResolverReturnedNone { type_name }))
+}
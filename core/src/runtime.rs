@@ -0,0 +1,93 @@
+use tokio::runtime::Handle;
+
+use crate::ServiceContainer;
+
+/// The container carries the handle of the Tokio runtime the services are bound to, so they
+/// can spawn their background tasks on it even when constructed eagerly outside the runtime,
+/// where [`Handle::current`] would panic.
+impl ServiceContainer {
+    /// Sets the handle of the runtime the services spawn their tasks on.
+    /// See [`ServiceContainer::runtime_handle`].
+    pub fn with_runtime_handle(mut self, handle: Handle) -> Self {
+        self.runtime_handle = Some(handle);
+        self
+    }
+
+    /// Returns the handle set by [`ServiceContainer::with_runtime_handle`], or the one of the
+    /// current runtime if any, for the resolvers spawning tasks.
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use ruice::{Construct, ConstructServices, ServiceContainer, Services};
+    /// use tokio::task::JoinHandle;
+    ///
+    /// struct Ticker {
+    ///     task: JoinHandle<()>,
+    /// }
+    ///
+    /// impl Construct for Ticker {
+    ///     fn construct(container: &ServiceContainer) -> Option<Self> {
+    ///         let task = container.runtime_handle()?.spawn(async {});
+    ///         Some(Self { task })
+    ///     }
+    /// }
+    ///
+    /// let runtime = tokio::runtime::Runtime::new().unwrap();
+    /// let handle = runtime.handle().clone();
+    /// let mut container = ServiceContainer::default().with_runtime_handle(handle);
+    /// container.construct::<Ticker>();
+    ///
+    /// // Constructed outside the runtime.
+    /// assert!(container.get::<Ticker>().is_some());
+    /// ```
+    pub fn runtime_handle(&self) -> Option<Handle> {
+        self.runtime_handle
+            .clone()
+            .or_else(|| Handle::try_current().ok())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+
+    use tokio::runtime::Builder;
+    use tokio::task::JoinHandle;
+
+    use super::*;
+    use crate::{Construct, ConstructServices, Services};
+
+    static TICKED: AtomicBool = AtomicBool::new(false);
+
+    struct Ticker {
+        task: JoinHandle<()>,
+    }
+
+    impl Construct for Ticker {
+        fn construct(container: &ServiceContainer) -> Option<Self> {
+            let task = container.runtime_handle()?.spawn(async {
+                TICKED.store(true, Ordering::SeqCst);
+            });
+
+            Some(Self { task })
+        }
+    }
+
+    #[test]
+    fn runtime_handle() {
+        let mut container = ServiceContainer::default();
+        container.construct::<Ticker>();
+        assert!(container.get::<Ticker>().is_none());
+
+        let runtime = Builder::new_current_thread().build().unwrap();
+        let mut container = container.with_runtime_handle(runtime.handle().clone());
+        container.construct::<Ticker>();
+
+        let ticker: Arc<Ticker> = container.get().unwrap();
+        let ticker = Arc::into_inner(ticker).unwrap();
+        runtime.block_on(ticker.task).unwrap();
+        assert!(TICKED.load(Ordering::SeqCst));
+    }
+}
@@ -0,0 +1,155 @@
+use std::future::Future;
+use std::mem;
+use std::sync::{Arc, Mutex, PoisonError};
+
+use async_trait::async_trait;
+use tokio::task::{AbortHandle, JoinSet};
+
+use crate::AsyncServices;
+
+type Tasks = Arc<Mutex<JoinSet<()>>>;
+
+tokio::task_local! {
+    static TASKS: Tasks;
+}
+
+/// Spawns a sub-task tied to the lifetime of the surrounding
+/// [`ScopedServices::scoped_get_async`] call.
+///
+/// The task is aborted if the resolution is cancelled, and awaited before the resolution
+/// completes otherwise. Returns `None` without spawning anything when called outside a scoped
+/// resolution.
+pub fn spawn<F>(future: F) -> Option<AbortHandle>
+where
+    F: Future<Output = ()> + Send + 'static,
+{
+    TASKS
+        .try_with(|tasks| {
+            tasks
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .spawn(future)
+        })
+        .ok()
+}
+
+#[async_trait]
+pub trait ScopedServices: AsyncServices {
+    /// Gets the service asynchronously, tying the sub-tasks spawned by [`spawn`] during the
+    /// resolution to the returned future.
+    /// Dropping the future aborts all of them, so no task outlives a cancelled resolution.
+    ///
+    /// A panic of a sub-task is resumed once the resolution completes, like
+    /// [`std::thread::scope`] does, aborting the other sub-tasks still running.
+    async fn scoped_get_async<S>(&self) -> Option<Arc<S>>
+    where
+        S: ?Sized + Send + Sync + 'static,
+    {
+        let tasks = Tasks::default();
+        let resolved = TASKS.scope(Arc::clone(&tasks), self.get_async::<S>()).await;
+
+        let mut tasks = mem::take(&mut *tasks.lock().unwrap_or_else(PoisonError::into_inner));
+        while let Some(joined) = tasks.join_next().await {
+            // Cancelled sub-tasks are aborted through their handles on purpose.
+            if let Err(e) = joined {
+                if e.is_panic() {
+                    std::panic::resume_unwind(e.into_panic());
+                }
+            }
+        }
+
+        resolved
+    }
+}
+
+impl<C> ScopedServices for C where C: AsyncServices {}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    use super::*;
+    use crate::bind::AsyncBindServices;
+    use crate::ServiceContainer;
+
+    struct SetOnDrop(Arc<AtomicBool>);
+
+    impl Drop for SetOnDrop {
+        fn drop(&mut self) {
+            self.0.store(true, Ordering::SeqCst);
+        }
+    }
+
+    struct Connection;
+
+    #[tokio::test]
+    async fn sub_tasks_are_awaited() {
+        let mut container = ServiceContainer::default();
+        let finished = Arc::new(AtomicBool::new(false));
+
+        let flag = Arc::clone(&finished);
+        container.bind_by_async(move |_| {
+            let flag = Arc::clone(&flag);
+            async move {
+                spawn(async move {
+                    tokio::task::yield_now().await;
+                    flag.store(true, Ordering::SeqCst);
+                })?;
+
+                Some(Arc::new(Connection))
+            }
+        });
+
+        assert!(container.scoped_get_async::<Connection>().await.is_some());
+        assert!(finished.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn sub_tasks_are_aborted_on_cancellation() {
+        let mut container = ServiceContainer::default();
+        let dropped = Arc::new(AtomicBool::new(false));
+
+        let flag = Arc::clone(&dropped);
+        container.bind_by_async(move |_| {
+            let guard = SetOnDrop(Arc::clone(&flag));
+            async move {
+                spawn(async move {
+                    let _guard = guard;
+                    std::future::pending::<()>().await;
+                })?;
+
+                std::future::pending::<Option<Arc<Connection>>>().await
+            }
+        });
+
+        // Cancel the resolution after it started.
+        tokio::select! {
+            biased;
+            _ = container.scoped_get_async::<Connection>() => unreachable!(),
+            _ = tokio::task::yield_now() => {},
+        }
+
+        while !dropped.load(Ordering::SeqCst) {
+            tokio::task::yield_now().await;
+        }
+    }
+
+    #[test]
+    fn spawn_outside_scope() {
+        assert!(spawn(async {}).is_none());
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "flushing failed")]
+    async fn sub_task_panic_is_resumed() {
+        let mut container = ServiceContainer::default();
+
+        container.bind_by_async(|_| async {
+            spawn(async { panic!("flushing failed") })?;
+
+            Some(Arc::new(Connection))
+        });
+
+        container.scoped_get_async::<Connection>().await;
+    }
+}
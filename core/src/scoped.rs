@@ -0,0 +1,225 @@
+//! Singletons shared within a scope given at runtime, such as a session or a tenant, rather than
+//! globally.
+
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, Mutex, PoisonError};
+
+use crate::{cycle, Construct, ServiceContainer};
+
+/// Identifies a scope of the services resolved by [`ServiceContainer::get_scoped_singleton`],
+/// e.g. a session or a tenant.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ScopeId(String);
+
+impl ScopeId {
+    pub fn new(id: impl Into<String>) -> Self {
+        Self(id.into())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<&str> for ScopeId {
+    fn from(value: &str) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<String> for ScopeId {
+    fn from(value: String) -> Self {
+        Self(value)
+    }
+}
+
+impl From<u64> for ScopeId {
+    fn from(value: u64) -> Self {
+        Self(value.to_string())
+    }
+}
+
+impl fmt::Display for ScopeId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+pub(crate) type ScopedSingletons =
+    Arc<Mutex<HashMap<(TypeId, ScopeId), Arc<dyn Any + Send + Sync>>>>;
+
+/// The scoped singletons are held by the container and shared with its clones, but not with its
+/// forks, until their scope is dropped by [`ServiceContainer::drop_scope`].
+impl ServiceContainer {
+    /// Gets the singleton of the scope, constructing it from the container on the first
+    /// resolution within the scope, so each scope gets its own instance shared within it.
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use ruice::{Construct, ScopeId, ServiceContainer};
+    ///
+    /// struct TenantCache;
+    ///
+    /// impl Construct for TenantCache {
+    ///     fn construct(_container: &ServiceContainer) -> Option<Self> {
+    ///         Some(Self)
+    ///     }
+    /// }
+    ///
+    /// let container = ServiceContainer::default();
+    /// let (acme, globex) = (ScopeId::from("acme"), ScopeId::from("globex"));
+    ///
+    /// let cache = container.get_scoped_singleton::<TenantCache>(&acme).unwrap();
+    /// assert!(Arc::ptr_eq(&cache, &container.get_scoped_singleton(&acme).unwrap()));
+    /// assert!(!Arc::ptr_eq(&cache, &container.get_scoped_singleton(&globex).unwrap()));
+    ///
+    /// // The session of the tenant ended.
+    /// container.drop_scope(&acme);
+    /// assert!(!Arc::ptr_eq(&cache, &container.get_scoped_singleton(&acme).unwrap()));
+    /// ```
+    ///
+    /// The service is constructed without holding the lock, so the concurrent first resolutions
+    /// within a scope may all construct it, but share the one cached first.
+    pub fn get_scoped_singleton<S>(&self, scope_id: &ScopeId) -> Option<Arc<S>>
+    where
+        S: Construct<S, Self> + Send + Sync + 'static,
+    {
+        let key = (TypeId::of::<S>(), scope_id.clone());
+        if let Some(service) = self.scoped_service::<S>(&key) {
+            return Some(service);
+        }
+
+        let constructed: Arc<dyn Any + Send + Sync> = {
+            let _guard = cycle::enter::<S>()?;
+            Arc::new(Arc::new(S::construct(self)?))
+        };
+
+        let mut scoped = self.scoped.lock().unwrap_or_else(PoisonError::into_inner);
+        let service = scoped.entry(key).or_insert(constructed);
+
+        service.downcast_ref::<Arc<S>>().map(Arc::clone)
+    }
+
+    /// Drops the singletons of the scope, e.g. when the session ends, so they are released once
+    /// no one else holds them. Returns the number of the singletons dropped.
+    pub fn drop_scope(&self, scope_id: &ScopeId) -> usize {
+        let mut scoped = self.scoped.lock().unwrap_or_else(PoisonError::into_inner);
+
+        let before = scoped.len();
+        scoped.retain(|(_, id), _| id != scope_id);
+
+        before - scoped.len()
+    }
+
+    fn scoped_service<S>(&self, key: &(TypeId, ScopeId)) -> Option<Arc<S>>
+    where
+        S: Send + Sync + 'static,
+    {
+        let scoped = self.scoped.lock().unwrap_or_else(PoisonError::into_inner);
+
+        scoped.get(key)?.downcast_ref::<Arc<S>>().map(Arc::clone)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use super::*;
+    use crate::{Services, SingletonServices};
+
+    struct Database;
+
+    struct TenantCache {
+        database: Arc<Database>,
+    }
+
+    impl Construct for TenantCache {
+        fn construct(container: &ServiceContainer) -> Option<Self> {
+            Some(Self {
+                database: container.get()?,
+            })
+        }
+    }
+
+    struct Session;
+
+    impl Construct for Session {
+        fn construct(_container: &ServiceContainer) -> Option<Self> {
+            Some(Self)
+        }
+    }
+
+    #[test]
+    fn get_scoped_singleton() {
+        let mut container = ServiceContainer::default();
+        let (acme, globex) = (ScopeId::from("acme"), ScopeId::from(42));
+        assert!(container
+            .get_scoped_singleton::<TenantCache>(&acme)
+            .is_none());
+
+        container.singleton(Database);
+        let cache = container
+            .get_scoped_singleton::<TenantCache>(&acme)
+            .unwrap();
+        let session = container.get_scoped_singleton::<Session>(&acme).unwrap();
+
+        // Shared within the scope, including from the clones of the container.
+        let cloned = container.clone();
+        let shared = thread::spawn(move || cloned.get_scoped_singleton::<TenantCache>(&acme))
+            .join()
+            .unwrap()
+            .unwrap();
+        assert!(Arc::ptr_eq(&cache, &shared));
+
+        // Not shared with the other scopes, but the global singletons are.
+        let other = container
+            .get_scoped_singleton::<TenantCache>(&globex)
+            .unwrap();
+        assert!(!Arc::ptr_eq(&cache, &other));
+        assert!(Arc::ptr_eq(&cache.database, &other.database));
+
+        let acme = ScopeId::new("acme");
+        assert_eq!(2, container.drop_scope(&acme));
+        assert_eq!(0, container.drop_scope(&acme));
+        assert!(!Arc::ptr_eq(
+            &session,
+            &container.get_scoped_singleton(&acme).unwrap()
+        ));
+        assert!(Arc::ptr_eq(
+            &other,
+            &container.get_scoped_singleton(&globex).unwrap()
+        ));
+    }
+
+    #[test]
+    fn fork() {
+        let mut container = ServiceContainer::default();
+        let acme = ScopeId::from("acme");
+
+        container.singleton(Database);
+        let cache = container
+            .get_scoped_singleton::<TenantCache>(&acme)
+            .unwrap();
+
+        // The fork constructs its own from the overridden dependency.
+        let mut fork = container.fork();
+        fork.singleton(Database);
+        let forked = fork.get_scoped_singleton::<TenantCache>(&acme).unwrap();
+        assert!(!Arc::ptr_eq(&cache, &forked));
+        assert!(Arc::ptr_eq(
+            &fork.get::<Database>().unwrap(),
+            &forked.database
+        ));
+
+        // Dropping the scope of either one leaves the other intact.
+        assert_eq!(1, fork.drop_scope(&acme));
+        assert!(Arc::ptr_eq(
+            &cache,
+            &container.get_scoped_singleton(&acme).unwrap()
+        ));
+    }
+}
@@ -1,8 +1,17 @@
-use std::sync::Arc;
+use std::sync::{Arc, Condvar, Mutex, OnceLock, PoisonError, Weak};
+use std::thread::{self, ThreadId};
 
-use crate::{Resolve, Services};
+use async_trait::async_trait;
 
-pub struct Singleton<S> {
+use crate::{
+    cycle, AsyncResolve, Construct, Constructor, ResolutionKind, Resolve, ServiceContainer,
+    Services,
+};
+
+pub struct Singleton<S>
+where
+    S: ?Sized,
+{
     service: Arc<S>,
 }
 
@@ -14,13 +23,335 @@ impl<S> Singleton<S> {
     }
 }
 
+impl<S> From<Arc<S>> for Singleton<S>
+where
+    S: ?Sized,
+{
+    fn from(value: Arc<S>) -> Self {
+        Self { service: value }
+    }
+}
+
 impl<S, C> Resolve<S, C> for Singleton<S>
 where
-    S: Send + Sync,
+    S: ?Sized + Send + Sync,
 {
     fn resolve(&self, _container: &C) -> Option<Arc<S>> {
         Some(Arc::clone(&self.service))
     }
+
+    fn resolve_ref(&self) -> Option<&Arc<S>> {
+        Some(&self.service)
+    }
+
+    fn kind(&self) -> ResolutionKind {
+        ResolutionKind::Singleton
+    }
+
+    fn is_pure(&self) -> bool {
+        true
+    }
+}
+
+/// Resolves the service by the inner resolver on the first resolution, and shares it afterwards.
+///
+/// The first resolution is single-flight: when multiple threads resolve the service for the
+/// first time at once, the others wait for the one running the inner resolver and share its
+/// instance. If it fails, the next one runs the inner resolver again.
+///
+/// No lock is held while the inner resolver runs, so resolving the dependencies reentrantly
+/// never deadlocks. Instead, a circular dependency fails the resolution, whatever the inner
+/// resolver is, reported as [`ResolutionError::CircularDependency`](crate::ResolutionError) by
+/// [`Services::try_get`]. Like [`OnceSingleton`], a circular dependency resolved from multiple
+/// threads at once can still deadlock.
+pub struct LazySingleton<S, C = ServiceContainer>
+where
+    S: ?Sized,
+{
+    inner: Arc<dyn Resolve<S, C>>,
+    state: Mutex<LazyState<S>>,
+    initialized: Condvar,
+}
+
+struct LazyState<S>
+where
+    S: ?Sized,
+{
+    service: Option<Arc<S>>,
+    initializing: Option<ThreadId>,
+}
+
+/// Hands over the initialization to the next resolution when dropped, even on panic.
+struct Initializing<'a, S>
+where
+    S: ?Sized,
+{
+    state: &'a Mutex<LazyState<S>>,
+    initialized: &'a Condvar,
+}
+
+impl<S> Drop for Initializing<'_, S>
+where
+    S: ?Sized,
+{
+    fn drop(&mut self) {
+        self.state
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .initializing = None;
+        self.initialized.notify_all();
+    }
+}
+
+impl<S, C> From<Arc<dyn Resolve<S, C>>> for LazySingleton<S, C>
+where
+    S: ?Sized,
+{
+    fn from(value: Arc<dyn Resolve<S, C>>) -> Self {
+        Self {
+            inner: value,
+            state: Mutex::new(LazyState {
+                service: None,
+                initializing: None,
+            }),
+            initialized: Condvar::new(),
+        }
+    }
+}
+
+impl<S, C> LazySingleton<S, C>
+where
+    S: ?Sized,
+{
+    pub fn new<R>(resolver: R) -> Self
+    where
+        R: Resolve<S, C> + 'static,
+    {
+        Self::from(Arc::new(resolver) as Arc<dyn Resolve<S, C>>)
+    }
+}
+
+impl<S, C> Resolve<S, C> for LazySingleton<S, C>
+where
+    S: ?Sized + Send + Sync + 'static,
+{
+    fn resolve(&self, container: &C) -> Option<Arc<S>> {
+        let current = thread::current().id();
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+
+        loop {
+            if let Some(s) = &state.service {
+                return Some(Arc::clone(s));
+            }
+
+            match state.initializing {
+                None => break,
+                // Resolved reentrantly while initializing, which is a circular dependency
+                // whatever the inner resolver is.
+                Some(thread) if thread == current => {
+                    drop(state);
+                    let _guard = cycle::enter_wrapping::<S>()?;
+                    return self.inner.resolve(container);
+                }
+                Some(_) => {
+                    state = self
+                        .initialized
+                        .wait(state)
+                        .unwrap_or_else(PoisonError::into_inner);
+                }
+            }
+        }
+
+        let _resolving = cycle::enter_wrapping::<S>()?;
+        state.initializing = Some(current);
+        drop(state);
+
+        let _initializing = Initializing {
+            state: &self.state,
+            initialized: &self.initialized,
+        };
+        let resolved = self.inner.resolve(container)?;
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+
+        Some(Arc::clone(state.service.get_or_insert(resolved)))
+    }
+
+    fn kind(&self) -> ResolutionKind {
+        ResolutionKind::LazySingleton
+    }
+}
+
+/// Resolves the service asynchronously by the inner resolver on the first resolution, and shares
+/// it afterwards.
+///
+/// No lock is held while the inner resolver runs. Unlike [`LazySingleton`], concurrent first
+/// resolutions may each run the inner resolver, but all of them get the instance stored first.
+/// Failed resolutions are not cached, so the next resolution tries again.
+pub struct AsyncLazySingleton<S, C = ServiceContainer>
+where
+    S: ?Sized,
+{
+    inner: Arc<dyn AsyncResolve<S, C>>,
+    service: Mutex<Option<Arc<S>>>,
+}
+
+impl<S, C> From<Arc<dyn AsyncResolve<S, C>>> for AsyncLazySingleton<S, C>
+where
+    S: ?Sized,
+{
+    fn from(value: Arc<dyn AsyncResolve<S, C>>) -> Self {
+        Self {
+            inner: value,
+            service: Mutex::new(None),
+        }
+    }
+}
+
+impl<S, C> AsyncLazySingleton<S, C>
+where
+    S: ?Sized,
+{
+    pub fn new<R>(resolver: R) -> Self
+    where
+        R: AsyncResolve<S, C> + 'static,
+    {
+        Self::from(Arc::new(resolver) as Arc<dyn AsyncResolve<S, C>>)
+    }
+}
+
+#[async_trait]
+impl<S, C> AsyncResolve<S, C> for AsyncLazySingleton<S, C>
+where
+    S: ?Sized + Send + Sync,
+    C: Send + Sync,
+{
+    async fn async_resolve(&self, container: &C) -> Option<Arc<S>> {
+        if let Some(s) = self
+            .service
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .as_ref()
+        {
+            return Some(Arc::clone(s));
+        }
+
+        let resolved = self.inner.async_resolve(container).await?;
+        let mut service = self.service.lock().unwrap_or_else(PoisonError::into_inner);
+
+        Some(Arc::clone(service.get_or_insert(resolved)))
+    }
+
+    fn is_resolved(&self) -> bool {
+        self.service
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .is_some()
+    }
+}
+
+/// Creates the service by the function on the first resolution, exactly once, and shares it
+/// afterwards.
+///
+/// Like [`LazySingleton`], concurrent first resolutions wait for the one running the function.
+/// Resolving the service reentrantly from the function fails instead of deadlocking, but a
+/// circular dependency resolved from multiple threads at once can still deadlock.
+pub struct OnceSingleton<S, C = ServiceContainer> {
+    #[allow(clippy::type_complexity)]
+    f: Box<dyn Fn(&C) -> S + Send + Sync>,
+    service: OnceLock<Arc<S>>,
+}
+
+impl<S, C, F> From<F> for OnceSingleton<S, C>
+where
+    F: Fn(&C) -> S + Send + Sync + 'static,
+{
+    fn from(value: F) -> Self {
+        Self {
+            f: Box::new(value),
+            service: OnceLock::new(),
+        }
+    }
+}
+
+impl<S, C> Resolve<S, C> for OnceSingleton<S, C>
+where
+    S: Send + Sync + 'static,
+{
+    fn resolve(&self, container: &C) -> Option<Arc<S>> {
+        if let Some(s) = self.service.get() {
+            return Some(Arc::clone(s));
+        }
+
+        let _guard = cycle::enter::<S>()?;
+
+        Some(Arc::clone(
+            self.service.get_or_init(|| Arc::new((self.f)(container))),
+        ))
+    }
+
+    fn resolve_ref(&self) -> Option<&Arc<S>> {
+        self.service.get()
+    }
+
+    fn kind(&self) -> ResolutionKind {
+        ResolutionKind::LazySingleton
+    }
+}
+
+/// Holds the service weakly, sharing it while it is in use outside the container, and recreating
+/// it by the function once all of its [`Arc`]s are dropped. See
+/// [`BindServices::bind_weak_cached`](crate::BindServices::bind_weak_cached).
+///
+/// Recreations are single-flight: concurrent resolutions wait for the one running the function
+/// and share its service. Resolving the service reentrantly from the function fails instead of
+/// deadlocking.
+pub struct WeakSingleton<S, C = ServiceContainer>
+where
+    S: ?Sized,
+{
+    #[allow(clippy::type_complexity)]
+    f: Box<dyn Fn(&C) -> Option<Arc<S>> + Send + Sync>,
+    service: Mutex<Option<Weak<S>>>,
+}
+
+impl<S, C, F> From<F> for WeakSingleton<S, C>
+where
+    S: ?Sized,
+    F: Fn(&C) -> Option<Arc<S>> + Send + Sync + 'static,
+{
+    fn from(value: F) -> Self {
+        Self {
+            f: Box::new(value),
+            service: Mutex::new(None),
+        }
+    }
+}
+
+impl<S, C> Resolve<S, C> for WeakSingleton<S, C>
+where
+    S: ?Sized + Send + Sync + 'static,
+{
+    fn resolve(&self, container: &C) -> Option<Arc<S>> {
+        let upgrade = |service: &Option<Weak<S>>| service.as_ref().and_then(Weak::upgrade);
+
+        if let Some(s) = upgrade(&self.service.lock().unwrap_or_else(PoisonError::into_inner)) {
+            return Some(s);
+        }
+
+        // Entered before locking, so a reentrant resolution fails rather than deadlocks.
+        let _guard = cycle::enter::<S>()?;
+        let mut service = self.service.lock().unwrap_or_else(PoisonError::into_inner);
+
+        // Recreated by another resolution while waiting for the lock.
+        if let Some(s) = upgrade(&service) {
+            return Some(s);
+        }
+
+        let created = (self.f)(container)?;
+        *service = Some(Arc::downgrade(&created));
+
+        Some(created)
+    }
 }
 
 pub trait SingletonServices: Services {
@@ -30,6 +361,262 @@ pub trait SingletonServices: Services {
     {
         self.put(Singleton::new(service));
     }
+
+    /// Puts a singleton service constructed on the first resolution.
+    fn lazy_singleton<S>(&mut self)
+    where
+        S: Construct<S, Self> + 'static,
+        Self: 'static,
+    {
+        self.put(LazySingleton::new(Constructor::<S>::new()));
+    }
+
+    /// Puts a singleton service created by the function on the first resolution, for one-off
+    /// services not worth implementing [`Construct`].
+    ///
+    /// The function is stored in the container, so it is [`Fn`] rather than [`FnOnce`], but it
+    /// runs only once unless it panics. As it can not fail, use [`SingletonServices::lazy_singleton`]
+    /// for services whose dependencies may be missing.
+    fn put_lazy<S, F>(&mut self, f: F)
+    where
+        S: Send + Sync + 'static,
+        F: Fn(&Self) -> S + Send + Sync + 'static,
+        Self: 'static,
+    {
+        self.put(OnceSingleton::from(f));
+    }
 }
 
 impl<C> SingletonServices for C where C: Services {}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+    use crate::bind::BindBy;
+    use crate::ResolutionError;
+
+    struct Foo;
+
+    impl Construct for Foo {
+        fn construct(_container: &ServiceContainer) -> Option<Self> {
+            Some(Self)
+        }
+    }
+
+    #[test]
+    fn weak_singleton() {
+        let container = ServiceContainer::default();
+        let creations = Arc::new(AtomicUsize::new(0));
+
+        let counter = Arc::clone(&creations);
+        let resolver = WeakSingleton::from(move |_: &ServiceContainer| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            Some(Arc::new(vec![0u8; 1024]))
+        });
+
+        // Shared while in use.
+        let buffer = resolver.resolve(&container).unwrap();
+        assert!(Arc::ptr_eq(&buffer, &resolver.resolve(&container).unwrap()));
+        assert_eq!(1, creations.load(Ordering::SeqCst));
+
+        // Recreated once dropped.
+        drop(buffer);
+        assert!(resolver.resolve(&container).is_some());
+        assert_eq!(2, creations.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn weak_singleton_concurrent() {
+        let container = Arc::new(ServiceContainer::default());
+        let creations = Arc::new(AtomicUsize::new(0));
+
+        let counter = Arc::clone(&creations);
+        let resolver = Arc::new(WeakSingleton::from(move |_: &ServiceContainer| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            std::thread::sleep(std::time::Duration::from_millis(10));
+            Some(Arc::new(42u64))
+        }));
+
+        let barrier = Arc::new(std::sync::Barrier::new(8));
+        let handles = (0..8)
+            .map(|_| {
+                let (container, resolver) = (Arc::clone(&container), Arc::clone(&resolver));
+                let barrier = Arc::clone(&barrier);
+
+                std::thread::spawn(move || {
+                    barrier.wait();
+                    resolver.resolve(&container).unwrap()
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let resolved = handles
+            .into_iter()
+            .map(|h| h.join().unwrap())
+            .collect::<Vec<_>>();
+
+        // Held by all the threads at once, so created exactly once.
+        assert!(resolved.iter().all(|n| Arc::ptr_eq(n, &resolved[0])));
+        assert_eq!(1, creations.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn lazy_singleton() {
+        let mut container = ServiceContainer::default();
+
+        container.lazy_singleton::<Foo>();
+
+        let foo = container.get::<Foo>().unwrap();
+        assert!(Arc::ptr_eq(&foo, &container.get::<Foo>().unwrap()));
+    }
+
+    #[test]
+    fn lazy_singleton_concurrent() {
+        let container = Arc::new(ServiceContainer::default());
+        let creations = Arc::new(AtomicUsize::new(0));
+
+        let counter = Arc::clone(&creations);
+        let resolver = Arc::new(LazySingleton::new(BindBy::from(
+            move |_: &ServiceContainer| {
+                counter.fetch_add(1, Ordering::SeqCst);
+                std::thread::sleep(std::time::Duration::from_millis(10));
+                Some(Arc::new(42u64))
+            },
+        )));
+
+        let barrier = Arc::new(std::sync::Barrier::new(8));
+        let handles = (0..8)
+            .map(|_| {
+                let (container, resolver) = (Arc::clone(&container), Arc::clone(&resolver));
+                let barrier = Arc::clone(&barrier);
+
+                std::thread::spawn(move || {
+                    barrier.wait();
+                    resolver.resolve(&container).unwrap()
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let resolved = handles
+            .into_iter()
+            .map(|h| h.join().unwrap())
+            .collect::<Vec<_>>();
+
+        // The other threads wait for the first resolution instead of running their own.
+        assert!(resolved.iter().all(|n| Arc::ptr_eq(n, &resolved[0])));
+        assert_eq!(1, creations.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn put_lazy() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let mut container = ServiceContainer::default();
+        let calls = Arc::new(AtomicUsize::new(0));
+
+        let counter = Arc::clone(&calls);
+        container.put_lazy(move |c: &ServiceContainer| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            format!("Hello, {}!", c.get::<&str>().unwrap())
+        });
+        container.singleton("Taro");
+        assert_eq!(0, calls.load(Ordering::SeqCst));
+
+        let greeting = container.get::<String>().unwrap();
+        assert_eq!("Hello, Taro!", greeting.as_str());
+        assert!(Arc::ptr_eq(&greeting, &container.get::<String>().unwrap()));
+        assert_eq!(1, calls.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn put_lazy_reentrant() {
+        let mut container = ServiceContainer::default();
+
+        // Resolving the service being created fails instead of deadlocking.
+        container.put_lazy(|c: &ServiceContainer| c.get::<String>().is_some());
+        container.put_lazy(|c: &ServiceContainer| {
+            c.get::<bool>()
+                .map_or("cycle".to_string(), |b| b.to_string())
+        });
+
+        assert!(*container.get::<bool>().unwrap());
+        assert_eq!("cycle", container.get::<String>().unwrap().as_str());
+    }
+
+    struct Chicken {
+        _egg: Arc<Egg>,
+    }
+
+    struct Egg {
+        _chicken: Arc<Chicken>,
+    }
+
+    impl Construct for Chicken {
+        fn construct(container: &ServiceContainer) -> Option<Self> {
+            Some(Self {
+                _egg: container.get()?,
+            })
+        }
+    }
+
+    impl Construct for Egg {
+        fn construct(container: &ServiceContainer) -> Option<Self> {
+            Some(Self {
+                _chicken: container.get()?,
+            })
+        }
+    }
+
+    #[test]
+    fn lazy_singleton_circular_dependency() {
+        let mut container = ServiceContainer::default();
+
+        container.lazy_singleton::<Chicken>();
+        container.lazy_singleton::<Egg>();
+
+        // Resolving a lazy singleton reentrantly does not deadlock, but reports the cycle.
+        let Err(ResolutionError::CircularDependency(cycle)) = container.try_get::<Chicken>() else {
+            panic!("the circular dependency must be detected");
+        };
+
+        assert_eq!(
+            vec![
+                std::any::type_name::<Chicken>(),
+                std::any::type_name::<Egg>(),
+                std::any::type_name::<Chicken>(),
+            ],
+            cycle,
+        );
+
+        assert_eq!(
+            Err(ResolutionError::NotRegistered {
+                type_name: std::any::type_name::<Foo>(),
+            }),
+            container.try_get::<Foo>().map(|_| ()),
+        );
+    }
+
+    struct Node {
+        _next: Arc<Node>,
+    }
+
+    #[test]
+    fn lazy_singleton_circular_dependency_by_function() {
+        let mut container = ServiceContainer::default();
+
+        // The inner resolver does not detect the cycle by itself.
+        container.put(LazySingleton::new(BindBy::from(|c: &ServiceContainer| {
+            Some(Arc::new(Node { _next: c.get()? }))
+        })));
+
+        assert_eq!(
+            Err(ResolutionError::CircularDependency(vec![
+                std::any::type_name::<Node>(),
+                std::any::type_name::<Node>(),
+            ])),
+            container.try_get::<Node>().map(|_| ()),
+        );
+    }
+}
@@ -0,0 +1,340 @@
+use std::any::{type_name, Any, TypeId};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, PoisonError};
+
+use async_trait::async_trait;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+
+use crate::{AsyncEntry, AsyncResolve, AsyncResolver, AsyncServices, ServiceContainer};
+
+type SpawnFn<S> =
+    dyn Fn(ServiceContainer) -> Pin<Box<dyn Future<Output = Option<Arc<S>>> + Send>> + Send + Sync;
+
+type Initialized<S> = watch::Receiver<Option<Option<Arc<S>>>>;
+
+/// The [`SpawnedSingleton`] kept by the container, with the entry it is put by.
+pub(crate) type Spawned = (Arc<dyn AsyncEntry>, Arc<dyn Any + Send + Sync>);
+
+/// The spawned task, or `None` while its initialization is being built, and its result.
+type Task<S> = Option<(Option<JoinHandle<()>>, Initialized<S>)>;
+
+/// Initializes the service on a spawned task on the first resolution, so the callers waiting for
+/// it do not drive the initialization themselves, and shares it afterwards.
+///
+/// Concurrent resolutions await the same in-flight task. A failed or panicked initialization is
+/// spawned again on the next resolution. Put by [`ServiceContainer::spawned_singleton`] and
+/// resolved by [`ServiceContainer::get_or_spawn_async`] or [`AsyncServices::get_async`].
+pub struct SpawnedSingleton<S>
+where
+    S: ?Sized,
+{
+    f: Arc<SpawnFn<S>>,
+    task: Arc<Mutex<Task<S>>>,
+}
+
+impl<S> Clone for SpawnedSingleton<S>
+where
+    S: ?Sized,
+{
+    fn clone(&self) -> Self {
+        Self {
+            f: Arc::clone(&self.f),
+            task: Arc::clone(&self.task),
+        }
+    }
+}
+
+impl<S, F, Fut> From<F> for SpawnedSingleton<S>
+where
+    S: ?Sized,
+    F: Fn(ServiceContainer) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Option<Arc<S>>> + Send + 'static,
+{
+    fn from(value: F) -> Self {
+        Self {
+            f: Arc::new(move |container| Box::pin(value(container))),
+            task: Arc::default(),
+        }
+    }
+}
+
+impl<S> SpawnedSingleton<S>
+where
+    S: ?Sized + Send + Sync + 'static,
+{
+    /// Spawns the initialization on the runtime of the container unless it is running or done
+    /// already, returning a future for its result. See [`ServiceContainer::runtime_handle`].
+    ///
+    /// Returns a future resolving to `None` without spawning anything if no runtime is available.
+    pub fn spawn(
+        &self,
+        container: &ServiceContainer,
+    ) -> impl Future<Output = Option<Arc<S>>> + Send + 'static {
+        let mut task = self.task.lock().unwrap_or_else(PoisonError::into_inner);
+
+        let initialized = match running(&task) {
+            Some(initialized) => Some(initialized),
+            None => match container.runtime_handle() {
+                Some(handle) => {
+                    // Reserved before building the initialization, so the concurrent resolutions
+                    // share it instead of building their own.
+                    let (sender, initialized) = watch::channel(None);
+                    *task = Some((None, initialized.clone()));
+                    drop(task);
+
+                    // Built without the lock, as the function may resolve services synchronously,
+                    // e.g. checking whether this one is resolved.
+                    let future = (self.f)(container.clone());
+                    let spawned = handle.spawn(async move {
+                        let _ = sender.send(Some(future.await));
+                    });
+
+                    let mut task = self.task.lock().unwrap_or_else(PoisonError::into_inner);
+                    if let Some((handle @ None, reserved)) = &mut *task {
+                        if reserved.same_channel(&initialized) {
+                            *handle = Some(spawned);
+                        }
+                    }
+
+                    Some(initialized)
+                }
+                None => {
+                    warn!(
+                        "could not spawn the initialization of service {} without a Tokio runtime",
+                        type_name::<S>(),
+                    );
+                    None
+                }
+            },
+        };
+
+        async move {
+            // Fails if the task panicked before sending the result.
+            let mut initialized = initialized?;
+            let resolved = initialized.wait_for(Option::is_some).await.ok()?;
+
+            resolved.clone().flatten()
+        }
+    }
+}
+
+/// Returns the initialization shared while running or once done, unless it failed, so it is
+/// spawned again.
+fn running<S>(task: &Task<S>) -> Option<Initialized<S>>
+where
+    S: ?Sized,
+{
+    let (handle, initialized) = task.as_ref()?;
+    let shared = match *initialized.borrow() {
+        Some(ref resolved) => resolved.is_some(),
+        // The sender is dropped if building the initialization panicked before spawning it.
+        None => handle
+            .as_ref()
+            .map_or(initialized.has_changed().is_ok(), |h| !h.is_finished()),
+    };
+
+    shared.then(|| initialized.clone())
+}
+
+#[async_trait]
+impl<S> AsyncResolve<S, ServiceContainer> for SpawnedSingleton<S>
+where
+    S: ?Sized + Send + Sync + 'static,
+{
+    async fn async_resolve(&self, container: &ServiceContainer) -> Option<Arc<S>> {
+        self.spawn(container).await
+    }
+
+    fn is_resolved(&self) -> bool {
+        self.task
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .as_ref()
+            .is_some_and(|(_, initialized)| matches!(*initialized.borrow(), Some(Some(_))))
+    }
+}
+
+impl ServiceContainer {
+    /// Puts a service initialized in the background on its first resolution.
+    /// See [`SpawnedSingleton`].
+    ///
+    /// The function receives a clone of the container to resolve the dependencies from, as the
+    /// initialization may outlive the resolution spawning it.
+    pub fn spawned_singleton<S, F, Fut>(&mut self, f: F)
+    where
+        S: ?Sized + Send + Sync + 'static,
+        F: Fn(ServiceContainer) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Option<Arc<S>>> + Send + 'static,
+    {
+        let spawned = SpawnedSingleton::from(f);
+        self.put_async(AsyncResolver::new(spawned.clone()));
+
+        // Kept aside rather than registered as a service, along with the entry it is put by, so
+        // it is not used once the service is replaced.
+        let id = TypeId::of::<S>();
+        if let Some(entry) = self.async_services.get(&id) {
+            let entry = Arc::clone(entry);
+            self.spawned.insert(id, (entry, Arc::new(spawned)));
+        }
+    }
+
+    /// Gets the service put by [`ServiceContainer::spawned_singleton`], spawning its
+    /// initialization right away if not running yet, without waiting for it.
+    ///
+    /// The returned future awaits the in-flight initialization shared with the other callers,
+    /// so it can be polled later or not at all, e.g. to warm up a cache on startup while the
+    /// first request waits only if it is not ready by then. Other services are resolved by
+    /// [`AsyncServices::get_async`] when the future is polled.
+    pub fn get_or_spawn_async<S>(&self) -> impl Future<Output = Option<Arc<S>>> + Send + 'static
+    where
+        S: ?Sized + Send + Sync + 'static,
+    {
+        let id = TypeId::of::<S>();
+        let spawned = self
+            .spawned
+            .get(&id)
+            .filter(|(entry, _)| {
+                self.async_services
+                    .get(&id)
+                    .is_some_and(|e| Arc::as_ptr(e) as *const () == Arc::as_ptr(entry) as *const ())
+            })
+            .and_then(|(_, spawned)| spawned.downcast_ref::<SpawnedSingleton<S>>())
+            .map(|spawned| spawned.spawn(self));
+        let container = spawned.is_none().then(|| self.clone());
+
+        async move {
+            match spawned {
+                Some(spawned) => spawned.await,
+                None => container?.get_async().await,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    use super::*;
+    use crate::{Bound, Services, SingletonServices};
+
+    struct Config;
+
+    struct Cache {
+        _config: Arc<Config>,
+    }
+
+    #[tokio::test]
+    async fn get_or_spawn_async() {
+        let mut container = ServiceContainer::default();
+        let initializations = Arc::new(AtomicUsize::new(0));
+
+        container.singleton(Config);
+        let counter = Arc::clone(&initializations);
+        container.spawned_singleton(move |c| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            async move {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+                Some(Arc::new(Cache { _config: c.get()? }))
+            }
+        });
+
+        // Spawned without being polled.
+        let first = container.get_or_spawn_async::<Cache>();
+        assert!(!container.is_resolved::<Cache>());
+
+        let (first, second) = tokio::join!(first, container.get_async::<Cache>());
+        assert!(Arc::ptr_eq(&first.unwrap(), &second.unwrap()));
+        assert!(container.is_resolved::<Cache>());
+        assert_eq!(1, initializations.load(Ordering::SeqCst));
+
+        // Others are resolved as usual.
+        assert!(container.get_or_spawn_async::<Config>().await.is_some());
+    }
+
+    #[tokio::test]
+    async fn respawned_after_failure() {
+        let mut container = ServiceContainer::default();
+        let initializations = Arc::new(AtomicUsize::new(0));
+
+        let counter = Arc::clone(&initializations);
+        container.spawned_singleton(move |_| {
+            let attempt = counter.fetch_add(1, Ordering::SeqCst);
+            async move { (attempt > 0).then(|| Arc::new(Config)) }
+        });
+
+        assert!(container.get_or_spawn_async::<Config>().await.is_none());
+        assert!(container.get_or_spawn_async::<Config>().await.is_some());
+        assert!(container.get_or_spawn_async::<Config>().await.is_some());
+        assert_eq!(2, initializations.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn spawn_resolving_synchronously() {
+        let mut container = ServiceContainer::default();
+
+        // Checks the service itself while building the initialization, without deadlocking.
+        container.spawned_singleton(|c: ServiceContainer| {
+            let resolved = c.is_resolved::<Config>();
+            async move { (!resolved).then(|| Arc::new(Config)) }
+        });
+
+        assert!(container.get_async::<Config>().await.is_some());
+        assert!(container.is_resolved::<Config>());
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn spawned_once_under_contention() {
+        let mut container = ServiceContainer::default();
+        let builds = Arc::new(AtomicUsize::new(0));
+
+        let counter = Arc::clone(&builds);
+        container.spawned_singleton(move |_| {
+            // Widens the window between reserving the initialization and spawning it.
+            counter.fetch_add(1, Ordering::SeqCst);
+            std::thread::sleep(Duration::from_millis(10));
+            async { Some(Arc::new(Config)) }
+        });
+
+        let resolutions: Vec<_> = (0..8)
+            .map(|_| {
+                let container = container.clone();
+                tokio::spawn(async move { container.get_async::<Config>().await })
+            })
+            .collect();
+        for resolution in resolutions {
+            assert!(resolution.await.unwrap().is_some());
+        }
+
+        assert_eq!(1, builds.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn not_registered_as_service() {
+        let mut container = ServiceContainer::default();
+        let initializations = Arc::new(AtomicUsize::new(0));
+
+        let counter = Arc::clone(&initializations);
+        container.spawned_singleton(move |_| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            async { Some(Arc::new(Config)) }
+        });
+
+        assert_eq!(1, container.len());
+        assert_eq!(
+            vec![type_name::<Config>()],
+            container.registered_type_names()
+        );
+        assert!(!container.has::<SpawnedSingleton<Config>>());
+
+        // Not spawned once replaced.
+        let config = Arc::new(Config);
+        container.put_async(AsyncResolver::new(Bound::from(Arc::clone(&config))));
+        let resolved = container.get_or_spawn_async::<Config>().await.unwrap();
+        assert!(Arc::ptr_eq(&config, &resolved));
+        assert_eq!(0, initializations.load(Ordering::SeqCst));
+    }
+}
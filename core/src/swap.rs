@@ -0,0 +1,120 @@
+//! Bindings swappable at runtime with lock-free reads, for hot-reloaded services such as
+//! configurations read on every request.
+
+use std::fmt;
+use std::sync::Arc;
+
+use arc_swap::ArcSwap;
+
+use crate::{Resolve, Services};
+
+/// A handle to replace the service bound by [`SwapServices::bind_swappable`].
+///
+/// The service is swapped atomically, so every resolution sees either the old or the new one as
+/// a whole. The services already resolved are kept by their holders, so re-resolve the service
+/// to observe the replacement.
+pub struct ArcSwapHandle<Interface>
+where
+    Interface: ?Sized,
+{
+    // `ArcSwap` supports only sized types, so the `Arc` of an unsized one is boxed in another.
+    service: Arc<ArcSwap<Arc<Interface>>>,
+}
+
+impl<Interface> ArcSwapHandle<Interface>
+where
+    Interface: ?Sized,
+{
+    /// Gets the current service.
+    pub fn load(&self) -> Arc<Interface> {
+        Arc::clone(&self.service.load())
+    }
+
+    /// Replaces the service, which the resolutions from now on get.
+    pub fn store(&self, service: Arc<Interface>) {
+        self.service.store(Arc::new(service));
+    }
+}
+
+impl<Interface> Clone for ArcSwapHandle<Interface>
+where
+    Interface: ?Sized,
+{
+    fn clone(&self) -> Self {
+        Self {
+            service: Arc::clone(&self.service),
+        }
+    }
+}
+
+impl<Interface> fmt::Debug for ArcSwapHandle<Interface>
+where
+    Interface: ?Sized,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ArcSwapHandle")
+    }
+}
+
+impl<Interface, C> Resolve<Interface, C> for ArcSwapHandle<Interface>
+where
+    Interface: ?Sized + Send + Sync,
+{
+    fn resolve(&self, _container: &C) -> Option<Arc<Interface>> {
+        Some(self.load())
+    }
+}
+
+pub trait SwapServices: Services {
+    /// Binds the service, returning a handle to replace it at runtime. Resolving the service
+    /// takes no lock, so it suits the services read far more often than replaced.
+    fn bind_swappable<Interface>(&mut self, initial: Arc<Interface>) -> ArcSwapHandle<Interface>
+    where
+        Interface: ?Sized + Send + Sync + 'static,
+    {
+        let handle = ArcSwapHandle {
+            service: Arc::new(ArcSwap::from_pointee(initial)),
+        };
+        self.put(handle.clone());
+
+        handle
+    }
+}
+
+impl<C> SwapServices for C where C: Services {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ServiceContainer;
+
+    trait Config: Send + Sync {
+        fn timeout(&self) -> u64;
+    }
+
+    struct FileConfig {
+        timeout: u64,
+    }
+
+    impl Config for FileConfig {
+        fn timeout(&self) -> u64 {
+            self.timeout
+        }
+    }
+
+    #[test]
+    fn bind_swappable() {
+        let mut container = ServiceContainer::default();
+
+        let handle = container.bind_swappable::<dyn Config>(Arc::new(FileConfig { timeout: 30 }));
+        let cloned = container.clone();
+
+        let old = container.get::<dyn Config>().unwrap();
+        handle.store(Arc::new(FileConfig { timeout: 60 }));
+
+        // The clones of the container share the binding, while the resolved one is kept.
+        assert_eq!(60, container.get::<dyn Config>().unwrap().timeout());
+        assert_eq!(60, cloned.get::<dyn Config>().unwrap().timeout());
+        assert_eq!(30, old.timeout());
+    }
+}
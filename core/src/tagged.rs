@@ -1,16 +1,56 @@
+use std::any::{type_name, Any};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt;
+use std::future::{poll_fn, Future};
+use std::pin::Pin;
 use std::sync::Arc;
+use std::task::Poll;
 
-use crate::Services;
+use async_trait::async_trait;
 
-#[derive(Debug)]
-pub struct Tagged<Tag>
+use crate::bind::{BindBy, Bound};
+use crate::{
+    report, AsyncConstruct, AsyncConstructor, AsyncLazySingleton, AsyncResolve, AsyncResolveExt,
+    AsyncServices, Construct, Constructor, LazySingleton, ResolutionError, ResolutionKind, Resolve,
+    ServiceContainer, Services,
+};
+
+/// The metadata attached to a member of the tagged collection, such as its name or the route
+/// patterns of a handler. The members put without metadata have it empty.
+pub type TagMetadata = HashMap<&'static str, String>;
+
+/// The collection of services put under the same tag.
+///
+/// Members are held as resolvers, so they are either eagerly-provided instances or constructed
+/// on the first resolution of the collection.
+pub struct Tagged<Tag, C = ServiceContainer>
 where
     Tag: ?Sized,
 {
-    services: Vec<Arc<Tag>>,
+    #[allow(clippy::type_complexity)]
+    services: Vec<(Arc<TagMetadata>, Arc<dyn Resolve<Tag, C>>)>,
 }
 
-impl<Tag> Clone for Tagged<Tag>
+impl<Tag, C> fmt::Debug for Tagged<Tag, C>
+where
+    Tag: ?Sized,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Tagged")
+            .field(
+                "services",
+                &self
+                    .services
+                    .iter()
+                    .map(|(_, s)| s.kind())
+                    .collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+impl<Tag, C> Clone for Tagged<Tag, C>
 where
     Tag: ?Sized,
 {
@@ -21,7 +61,7 @@ where
     }
 }
 
-impl<Tag> Default for Tagged<Tag>
+impl<Tag, C> Default for Tagged<Tag, C>
 where
     Tag: ?Sized,
 {
@@ -30,37 +70,480 @@ where
     }
 }
 
-unsafe impl<Tag> Send for Tagged<Tag> where Tag: ?Sized {}
-unsafe impl<Tag> Sync for Tagged<Tag> where Tag: ?Sized {}
-
 pub trait TaggedServices: Services {
+    /// Gets all the services put under the tag, in the order they were put.
+    /// Members failing to resolve are skipped with a warning; use
+    /// [`TaggedServices::try_get_tagged`] to fail the whole collection instead.
     fn get_tagged<Tag>(&self) -> Vec<Arc<Tag>>
     where
-        Tag: ?Sized + 'static,
+        Tag: ?Sized + Send + Sync + 'static,
+        Self: 'static,
+    {
+        self.get::<Tagged<Tag, Self>>()
+            .map(|t| {
+                t.services
+                    .iter()
+                    .filter_map(|(_, s)| resolve_member(self, s.as_ref()))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Gets all the services put under the tag like [`TaggedServices::get_tagged`], but fails
+    /// with the error of the first member failing to resolve, for the collections usable only
+    /// as a whole.
+    fn try_get_tagged<Tag>(&self) -> Result<Vec<Arc<Tag>>, ResolutionError>
+    where
+        Tag: ?Sized + Send + Sync + 'static,
+        Self: 'static,
+    {
+        let Some(tagged) = self.get::<Tagged<Tag, Self>>() else {
+            return Ok(vec![]);
+        };
+
+        tagged
+            .services
+            .iter()
+            .map(|(_, s)| report::scope(type_name::<Tag>(), || s.resolve(self)))
+            .collect()
+    }
+
+    /// Iterates over the services put under the tag, in the order they were put, resolving each
+    /// one only when the iterator is advanced, so short-circuiting, e.g. by
+    /// [`Iterator::find`] over a chain of handlers, leaves the rest unresolved.
+    /// Members failing to resolve are skipped with a warning.
+    fn tagged_iter<Tag>(&self) -> impl Iterator<Item = Arc<Tag>> + '_
+    where
+        Tag: ?Sized + Send + Sync + 'static,
+        Self: 'static,
     {
-        self.get::<Tagged<Tag>>()
-            .map(|t| t.services.iter().map(Arc::clone).collect())
+        let tagged = self.get::<Tagged<Tag, Self>>();
+        let len = tagged.as_ref().map_or(0, |t| t.services.len());
+
+        (0..len).filter_map(move |i| resolve_member(self, tagged.as_ref()?.services[i].1.as_ref()))
+    }
+
+    /// Gets all the services put under the tag with their metadata, in the order they were put.
+    /// Members failing to resolve are skipped with a warning.
+    fn get_tagged_meta<Tag>(&self) -> Vec<(Arc<TagMetadata>, Arc<Tag>)>
+    where
+        Tag: ?Sized + Send + Sync + 'static,
+        Self: 'static,
+    {
+        self.get::<Tagged<Tag, Self>>()
+            .map(|t| {
+                t.services
+                    .iter()
+                    .filter_map(|(m, s)| Some((Arc::clone(m), resolve_member(self, s.as_ref())?)))
+                    .collect()
+            })
             .unwrap_or_default()
     }
 
+    /// Gets all the services put under the tag, in the reverse order they were put, e.g. for
+    /// running outbound middlewares in the reverse order of the inbound ones.
+    fn get_tagged_rev<Tag>(&self) -> Vec<Arc<Tag>>
+    where
+        Tag: ?Sized + Send + Sync + 'static,
+        Self: 'static,
+    {
+        let mut services = self.get_tagged::<Tag>();
+        services.reverse();
+        services
+    }
+
+    /// Gets all the services put under the tag, sorted by the comparator.
+    ///
+    /// Trait objects can not be compared by themselves, so compare them by what the tag
+    /// exposes, e.g. a priority method defined on the trait. The sort is stable, so the services
+    /// comparing equal keep the order they were put.
+    fn get_tagged_sorted_by<Tag, F>(&self, f: F) -> Vec<Arc<Tag>>
+    where
+        Tag: ?Sized + Send + Sync + 'static,
+        F: FnMut(&Arc<Tag>, &Arc<Tag>) -> Ordering,
+        Self: 'static,
+    {
+        let mut services = self.get_tagged::<Tag>();
+        services.sort_by(f);
+        services
+    }
+
     fn put_tagged<Tag>(&mut self, service: Arc<Tag>)
     where
-        Tag: ?Sized + 'static,
+        Tag: ?Sized + Send + Sync + 'static,
+        Self: 'static,
     {
-        self.replace::<Tagged<Tag>, _>(|tagged| {
-            let mut tagged = tagged.map(Tagged::<_>::clone).unwrap_or_default();
-            tagged.services.push(service);
-            tagged
-        });
+        push_tagged(self, TagMetadata::new(), Arc::new(Bound::from(service)));
+    }
+
+    /// Puts the service to the tagged collection with the metadata, retrieved along with it by
+    /// [`TaggedServices::get_tagged_meta`].
+    fn put_tagged_meta<Tag>(&mut self, metadata: TagMetadata, service: Arc<Tag>)
+    where
+        Tag: ?Sized + Send + Sync + 'static,
+        Self: 'static,
+    {
+        push_tagged(self, metadata, Arc::new(Bound::from(service)));
+    }
+
+    /// Puts the service to the tagged collection unless the same instance is already in it.
+    /// Returns whether the service was inserted.
+    fn put_tagged_unique<Tag>(&mut self, service: Arc<Tag>) -> bool
+    where
+        Tag: ?Sized + Send + Sync + 'static,
+        Self: 'static,
+    {
+        // Only eagerly-provided members are compared, not to construct the others here.
+        let tagged = self.get::<Tagged<Tag, Self>>();
+        if tagged
+            .iter()
+            .flat_map(|t| &t.services)
+            .map(|(_, s)| s)
+            .any(|s| s.resolve_ref().is_some_and(|s| Arc::ptr_eq(s, &service)))
+        {
+            return false;
+        }
+
+        self.put_tagged(service);
+        true
+    }
+
+    /// Puts a service constructed on the first resolution of the tagged collection, and shared
+    /// afterwards.
+    ///
+    /// `cast` converts the constructed service to the tag, which is usually a trait object:
+    /// `container.construct_tagged::<dyn Greet, FooGreeter>(|s| s)`.
+    fn construct_tagged<Tag, S>(&mut self, cast: fn(Arc<S>) -> Arc<Tag>)
+    where
+        Tag: ?Sized + Send + Sync + 'static,
+        S: Construct<S, Self> + 'static,
+        Self: 'static,
+    {
+        push_tagged(
+            self,
+            TagMetadata::new(),
+            Arc::new(LazySingleton::new(BindBy::from(move |container: &Self| {
+                Constructor::<S>::new().resolve(container).map(cast)
+            }))),
+        );
     }
 }
 
 impl<C> TaggedServices for C where C: Services {}
 
+/// Resolves the member, warning with its kind if it failed, as the collection goes on without it.
+fn resolve_member<Tag, C>(container: &C, member: &dyn Resolve<Tag, C>) -> Option<Arc<Tag>>
+where
+    Tag: ?Sized,
+{
+    let service = member.resolve(container);
+    if service.is_none() {
+        warn!(
+            "a member of the services tagged {} resolved by {:?} failed to resolve",
+            type_name::<Tag>(),
+            member.kind(),
+        );
+    }
+
+    service
+}
+
+fn push_tagged<Tag, C>(container: &mut C, metadata: TagMetadata, service: Arc<dyn Resolve<Tag, C>>)
+where
+    Tag: ?Sized + Send + Sync + 'static,
+    C: Services + 'static,
+{
+    push(container, |tagged: &mut Tagged<Tag, C>| {
+        tagged.services.push((Arc::new(metadata), service));
+    });
+}
+
+/// A collection of members, merged with the one of the same type registered by a group.
+trait Merge: Clone + Default + Send + Sync + 'static {
+    fn merge(&mut self, other: &Self, remove: bool);
+}
+
+impl<Tag, C> Merge for Tagged<Tag, C>
+where
+    Tag: ?Sized + 'static,
+    C: 'static,
+{
+    fn merge(&mut self, other: &Self, remove: bool) {
+        merge_members(
+            &mut self.services,
+            &other.services,
+            remove,
+            |(_, a), (_, b)| Arc::ptr_eq(a, b),
+        );
+    }
+}
+
+impl<Tag, C> Merge for AsyncTagged<Tag, C>
+where
+    Tag: ?Sized + 'static,
+    C: 'static,
+{
+    fn merge(&mut self, other: &Self, remove: bool) {
+        merge_members(&mut self.services, &other.services, remove, Arc::ptr_eq);
+    }
+}
+
+fn merge_members<T, F>(members: &mut Vec<T>, other: &[T], remove: bool, eq: F)
+where
+    T: Clone,
+    F: Fn(&T, &T) -> bool,
+{
+    if remove {
+        members.retain(|m| !other.iter().any(|o| eq(m, o)));
+    } else {
+        let added = other
+            .iter()
+            .filter(|o| !members.iter().any(|m| eq(m, o)))
+            .cloned()
+            .collect::<Vec<_>>();
+        members.extend(added);
+    }
+}
+
+/// Merges the other collection into the first one, or removes its members if `remove`.
+pub(crate) type MergeFn<T> = fn(&Arc<T>, &Arc<T>, bool) -> Arc<T>;
+
+/// Holds the collection as a singleton, merging it with the one registered by a group when the
+/// group is enabled, instead of replacing the members put outside of the group.
+pub(crate) struct Collection<T>
+where
+    T: ?Sized,
+{
+    collection: Arc<T>,
+    merge: MergeFn<T>,
+}
+
+impl<T> Collection<T>
+where
+    T: ?Sized,
+{
+    pub(crate) fn new(collection: Arc<T>, merge: MergeFn<T>) -> Self {
+        Self { collection, merge }
+    }
+}
+
+impl<T, C> Resolve<T, C> for Collection<T>
+where
+    T: ?Sized + Send + Sync,
+{
+    fn resolve(&self, _container: &C) -> Option<Arc<T>> {
+        Some(Arc::clone(&self.collection))
+    }
+
+    fn resolve_ref(&self) -> Option<&Arc<T>> {
+        Some(&self.collection)
+    }
+
+    fn kind(&self) -> ResolutionKind {
+        ResolutionKind::Singleton
+    }
+
+    fn is_pure(&self) -> bool {
+        true
+    }
+}
+
+/// Returns how to merge the collection held by the resolver, if it is a [`Collection`].
+pub(crate) fn merge_fn<S, C, R>(resolver: &R) -> Option<MergeFn<S>>
+where
+    S: ?Sized + 'static,
+    R: Resolve<S, C> + 'static,
+{
+    (resolver as &dyn Any)
+        .downcast_ref::<Collection<S>>()
+        .map(|c| c.merge)
+}
+
+fn merge<T>(collection: &Arc<T>, other: &Arc<T>, remove: bool) -> Arc<T>
+where
+    T: Merge,
+{
+    let mut merged = T::clone(collection);
+    merged.merge(other, remove);
+
+    Arc::new(merged)
+}
+
+/// Adds members to the collection, putting the copy of it.
+fn push<T, C, F>(container: &mut C, f: F)
+where
+    T: Merge,
+    C: Services,
+    F: FnOnce(&mut T),
+{
+    let mut collection = container
+        .get::<T>()
+        .map(|c| T::clone(&c))
+        .unwrap_or_default();
+    f(&mut collection);
+
+    container.put(Collection::new(Arc::new(collection), merge::<T>));
+}
+
+/// The collection of services put under the same tag, resolved asynchronously.
+pub struct AsyncTagged<Tag, C = ServiceContainer>
+where
+    Tag: ?Sized,
+{
+    services: Vec<Arc<dyn AsyncResolve<Tag, C>>>,
+}
+
+impl<Tag, C> fmt::Debug for AsyncTagged<Tag, C>
+where
+    Tag: ?Sized,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AsyncTagged")
+            .field("len", &self.services.len())
+            .finish()
+    }
+}
+
+impl<Tag, C> Clone for AsyncTagged<Tag, C>
+where
+    Tag: ?Sized,
+{
+    fn clone(&self) -> Self {
+        Self {
+            services: self.services.to_vec(),
+        }
+    }
+}
+
+impl<Tag, C> Default for AsyncTagged<Tag, C>
+where
+    Tag: ?Sized,
+{
+    fn default() -> Self {
+        Self { services: vec![] }
+    }
+}
+
+type Resolving<'a, Tag> = Pin<Box<dyn Future<Output = Option<Arc<Tag>>> + Send + 'a>>;
+
+/// Resolves all the members concurrently, keeping the order they were put.
+async fn resolve_all<Tag>(mut resolving: Vec<Resolving<'_, Tag>>) -> Vec<Option<Arc<Tag>>>
+where
+    Tag: ?Sized,
+{
+    let mut resolved = resolving.iter().map(|_| None).collect::<Vec<_>>();
+    let mut pending = (0..resolving.len()).collect::<Vec<_>>();
+
+    poll_fn(|cx| {
+        pending.retain(|&i| match resolving[i].as_mut().poll(cx) {
+            Poll::Ready(service) => {
+                resolved[i] = Some(service);
+                false
+            }
+            Poll::Pending => true,
+        });
+
+        if pending.is_empty() {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    })
+    .await;
+
+    resolved.into_iter().map(Option::flatten).collect()
+}
+
+#[async_trait]
+pub trait AsyncTaggedServices: AsyncServices + Services {
+    /// Gets all the services put under the tag asynchronously, resolving them concurrently, in
+    /// the order they were put. Members failing to resolve are skipped; use
+    /// [`AsyncTaggedServices::try_get_tagged_async`] to fail the whole collection instead.
+    ///
+    /// Only the members put by the asynchronous methods are included. The ones put by
+    /// [`TaggedServices`] are resolved by [`TaggedServices::get_tagged`].
+    async fn get_tagged_async<Tag>(&self) -> Vec<Arc<Tag>>
+    where
+        Tag: ?Sized + Send + Sync + 'static,
+        Self: 'static,
+    {
+        let Some(tagged) = self.get::<AsyncTagged<Tag, Self>>() else {
+            return vec![];
+        };
+
+        let resolving = tagged.services.iter().map(|s| s.async_resolve(self));
+        let resolved = resolve_all(resolving.collect()).await;
+        if resolved.iter().any(Option::is_none) {
+            warn!(
+                "some of the services tagged {} failed to resolve",
+                type_name::<Tag>()
+            );
+        }
+
+        resolved.into_iter().flatten().collect()
+    }
+
+    /// Gets all the services put under the tag asynchronously like
+    /// [`AsyncTaggedServices::get_tagged_async`], but fails if any of them failed to resolve, for
+    /// the collections usable only as a whole.
+    async fn try_get_tagged_async<Tag>(&self) -> Result<Vec<Arc<Tag>>, ResolutionError>
+    where
+        Tag: ?Sized + Send + Sync + 'static,
+        Self: 'static,
+    {
+        let Some(tagged) = self.get::<AsyncTagged<Tag, Self>>() else {
+            return Ok(vec![]);
+        };
+
+        let resolving = tagged.services.iter().map(|s| s.async_resolve(self));
+
+        resolve_all(resolving.collect())
+            .await
+            .into_iter()
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(ResolutionError::returned_none::<Tag>)
+    }
+
+    /// Puts a member resolved asynchronously by the resolver on every resolution of the
+    /// collection.
+    fn put_tagged_async<Tag, R>(&mut self, resolver: R)
+    where
+        Tag: ?Sized + Send + Sync + 'static,
+        R: AsyncResolve<Tag, Self> + 'static,
+        Self: 'static,
+    {
+        let resolver: Arc<dyn AsyncResolve<Tag, Self>> = Arc::new(resolver);
+
+        push(self, |tagged: &mut AsyncTagged<Tag, Self>| {
+            tagged.services.push(resolver);
+        });
+    }
+
+    /// Puts a member constructed asynchronously on the first resolution of the collection, and
+    /// shared afterwards. Failed constructions are retried on the next resolution.
+    ///
+    /// `cast` converts the constructed service to the tag, like
+    /// [`TaggedServices::construct_tagged`].
+    fn construct_tagged_async<Tag, S>(&mut self, cast: fn(Arc<S>) -> Arc<Tag>)
+    where
+        Tag: ?Sized + Send + Sync + 'static,
+        S: AsyncConstruct<S, Self> + Send + Sync + 'static,
+        Self: 'static,
+    {
+        let constructor = AsyncResolveExt::<S, Self>::map(AsyncConstructor::<S>::new(), cast);
+        self.put_tagged_async(AsyncLazySingleton::new(constructor));
+    }
+}
+
+impl<C> AsyncTaggedServices for C where C: AsyncServices + Services {}
+
 #[cfg(test)]
 mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering as AtomicOrdering};
+
     use super::*;
-    use crate::ServiceContainer;
+    use crate::bind::AsyncBindBy;
+    use crate::SingletonServices;
 
     trait Greet: Send + Sync {
         fn greet(&self) -> String;
@@ -84,6 +567,64 @@ mod tests {
         }
     }
 
+    #[test]
+    fn tagged_iter() {
+        let mut container = ServiceContainer::default();
+        let constructed = Arc::new(AtomicUsize::new(0));
+
+        container.put_tagged::<GreeterTag>(Arc::new(FooGreeter));
+        container.put_tagged::<GreeterTag>(Arc::new(BarGreeter));
+        for _ in 0..2 {
+            let counter = Arc::clone(&constructed);
+            push_tagged(
+                &mut container,
+                TagMetadata::new(),
+                Arc::new(BindBy::from(move |_: &ServiceContainer| {
+                    counter.fetch_add(1, AtomicOrdering::SeqCst);
+                    Some(Arc::new(FooGreeter) as Arc<GreeterTag>)
+                })),
+            );
+        }
+
+        // Stops at the first match, leaving the rest unresolved.
+        let bar = container
+            .tagged_iter::<GreeterTag>()
+            .find(|g| g.greet() == "Hello from Bar!");
+        assert!(bar.is_some());
+        assert_eq!(0, constructed.load(AtomicOrdering::SeqCst));
+
+        assert_eq!(4, container.tagged_iter::<GreeterTag>().count());
+        assert_eq!(2, constructed.load(AtomicOrdering::SeqCst));
+        assert_eq!(0, container.tagged_iter::<String>().count());
+    }
+
+    #[test]
+    fn tagged_meta() {
+        let mut container = ServiceContainer::default();
+
+        container.put_tagged_meta::<GreeterTag>(
+            TagMetadata::from([("name", "foo".to_string()), ("path", "/foo".to_string())]),
+            Arc::new(FooGreeter),
+        );
+        container.put_tagged::<GreeterTag>(Arc::new(BarGreeter));
+
+        let routes = container
+            .get_tagged_meta::<GreeterTag>()
+            .into_iter()
+            .map(|(meta, greeter)| (meta.get("path").cloned(), greeter.greet()))
+            .collect::<Vec<_>>();
+
+        // The members put without metadata have it empty.
+        assert_eq!(
+            vec![
+                (Some("/foo".to_string()), "Hello from Foo!".to_string()),
+                (None, "Hello from Bar!".to_string()),
+            ],
+            routes,
+        );
+        assert_eq!(2, container.get_tagged::<GreeterTag>().len());
+    }
+
     #[test]
     fn tag() {
         let mut container = ServiceContainer::default();
@@ -102,4 +643,161 @@ mod tests {
             greetings,
         )
     }
+
+    #[test]
+    fn tag_order() {
+        let mut container = ServiceContainer::default();
+
+        container.put_tagged::<GreeterTag>(Arc::new(FooGreeter));
+        container.put_tagged::<GreeterTag>(Arc::new(BarGreeter));
+
+        let greetings = container
+            .get_tagged_rev::<GreeterTag>()
+            .into_iter()
+            .map(|g| g.greet())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            vec!["Hello from Bar!".to_string(), "Hello from Foo!".to_string()],
+            greetings,
+        );
+
+        let greetings = container
+            .get_tagged_sorted_by::<GreeterTag, _>(|a, b| a.greet().cmp(&b.greet()))
+            .into_iter()
+            .map(|g| g.greet())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            vec!["Hello from Bar!".to_string(), "Hello from Foo!".to_string()],
+            greetings,
+        );
+    }
+
+    #[test]
+    fn tag_unique() {
+        let mut container = ServiceContainer::default();
+
+        let foo: Arc<GreeterTag> = Arc::new(FooGreeter);
+        assert!(container.put_tagged_unique(Arc::clone(&foo)));
+        assert!(!container.put_tagged_unique(Arc::clone(&foo)));
+
+        // Distinct instances of the same type are still inserted.
+        assert!(container.put_tagged_unique::<GreeterTag>(Arc::new(FooGreeter)));
+
+        assert_eq!(2, container.get_tagged::<GreeterTag>().len());
+    }
+
+    struct BazGreeter {
+        name: Arc<String>,
+    }
+
+    impl Construct for BazGreeter {
+        fn construct(container: &ServiceContainer) -> Option<Self> {
+            Some(Self {
+                name: container.get()?,
+            })
+        }
+    }
+
+    impl Greet for BazGreeter {
+        fn greet(&self) -> String {
+            format!("Hello from {}!", self.name)
+        }
+    }
+
+    #[test]
+    fn construct_tagged() {
+        let mut container = ServiceContainer::default();
+
+        // The member is not constructed until the collection is resolved,
+        // so its dependencies can be put later.
+        container.put_tagged::<GreeterTag>(Arc::new(FooGreeter));
+        container.construct_tagged::<GreeterTag, BazGreeter>(|s| s);
+        container.put_tagged::<GreeterTag>(Arc::new(BarGreeter));
+        container.singleton("Baz".to_string());
+
+        let greeters = container.get_tagged::<GreeterTag>();
+        assert_eq!(
+            vec![
+                "Hello from Foo!".to_string(),
+                "Hello from Baz!".to_string(),
+                "Hello from Bar!".to_string(),
+            ],
+            greeters.iter().map(|g| g.greet()).collect::<Vec<_>>(),
+        );
+
+        // The constructed member is shared afterwards.
+        assert!(Arc::ptr_eq(
+            &greeters[1],
+            &container.get_tagged::<GreeterTag>()[1],
+        ));
+    }
+
+    #[test]
+    fn try_get_tagged() {
+        let mut container = ServiceContainer::default();
+
+        container.put_tagged::<GreeterTag>(Arc::new(FooGreeter));
+        container.construct_tagged::<GreeterTag, BazGreeter>(|s| s);
+        assert_eq!(0, container.try_get_tagged::<String>().unwrap().len());
+
+        // Skipped by get_tagged, but fails the whole collection.
+        assert_eq!(1, container.get_tagged::<GreeterTag>().len());
+        assert_eq!(
+            Err(ResolutionError::ResolverReturnedNone {
+                type_name: std::any::type_name::<GreeterTag>(),
+            }),
+            container.try_get_tagged::<GreeterTag>().map(|_| ()),
+        );
+
+        container.singleton("Baz".to_string());
+        assert_eq!(2, container.try_get_tagged::<GreeterTag>().unwrap().len());
+    }
+
+    struct Plugin {
+        name: &'static str,
+    }
+
+    #[async_trait]
+    impl AsyncConstruct for Plugin {
+        async fn construct_async(container: &ServiceContainer) -> Option<Self> {
+            tokio::task::yield_now().await;
+
+            Some(Self {
+                name: *container.get::<&'static str>()?,
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn get_tagged_async() {
+        let mut container = ServiceContainer::default();
+
+        container.construct_tagged_async::<Plugin, Plugin>(|p| p);
+        container.put_tagged_async::<Plugin, _>(AsyncBindBy::from(|_: &ServiceContainer| async {
+            Some(Arc::new(Plugin { name: "static" }))
+        }));
+
+        // The constructed member fails to resolve until its dependency is put.
+        assert_eq!(1, container.get_tagged_async::<Plugin>().await.len());
+        assert_eq!(
+            Err(ResolutionError::ResolverReturnedNone {
+                type_name: std::any::type_name::<Plugin>(),
+            }),
+            container.try_get_tagged_async::<Plugin>().await.map(|_| ()),
+        );
+
+        container.singleton("connected");
+
+        let plugins = container.try_get_tagged_async::<Plugin>().await.unwrap();
+        assert_eq!(
+            vec!["connected", "static"],
+            plugins.iter().map(|p| p.name).collect::<Vec<_>>(),
+        );
+        assert!(Arc::ptr_eq(
+            &plugins[0],
+            &container.get_tagged_async::<Plugin>().await[0],
+        ));
+    }
 }
@@ -0,0 +1,156 @@
+use std::any::type_name;
+use std::cell::Cell;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+
+use crate::{report, AsyncServices, ResolutionError, ServiceContainer};
+
+struct Timeout {
+    duration: Duration,
+    elapsed: Cell<bool>,
+}
+
+tokio::task_local! {
+    static TIMEOUT: Timeout;
+}
+
+impl ServiceContainer {
+    /// Limits every asynchronous resolution by the container to the duration.
+    /// A resolution exceeding the limit is treated as if the resolver returned `None`.
+    ///
+    /// The timeout applies per resolution: each nested resolution (A awaiting B) is limited
+    /// individually, and as nested resolutions run within the outer one, the outermost timeout
+    /// bounds the whole tree. Timeouts require the time driver of the Tokio runtime.
+    pub fn set_default_async_timeout(&mut self, timeout: Duration) {
+        self.default_async_timeout = Some(timeout);
+    }
+
+    /// Resolves the service by the future, limited by the timeout in effect.
+    pub(crate) async fn within_timeout<S, F>(&self, future: F) -> Option<Arc<S>>
+    where
+        S: ?Sized,
+        F: Future<Output = Option<Arc<S>>>,
+    {
+        let timeout = TIMEOUT.try_with(|t| t.duration).ok();
+        let Some(timeout) = timeout.or(self.default_async_timeout) else {
+            return future.await;
+        };
+
+        match tokio::time::timeout(timeout, future).await {
+            Ok(resolved) => resolved,
+            Err(_) => {
+                warn!("resolution of service {} timed out", type_name::<S>());
+                let _ = TIMEOUT.try_with(|t| t.elapsed.set(true));
+                report::record(ResolutionError::TimedOut {
+                    type_name: type_name::<S>(),
+                });
+                None
+            }
+        }
+    }
+}
+
+#[async_trait]
+pub trait TimeoutServices: AsyncServices {
+    /// Gets the service asynchronously, limiting the resolution to the duration.
+    ///
+    /// The timeout overrides the default one of the container, including for the nested
+    /// resolutions during this call. Fails with [`ResolutionError::TimedOut`] if the service
+    /// could not be resolved because any of the resolutions timed out.
+    async fn get_async_timeout<S>(&self, timeout: Duration) -> Result<Arc<S>, ResolutionError>
+    where
+        S: ?Sized + Send + Sync + 'static,
+    {
+        let timeout = Timeout {
+            duration: timeout,
+            elapsed: Cell::new(false),
+        };
+
+        let (resolved, elapsed) = TIMEOUT
+            .scope(timeout, async {
+                let resolved = self.get_async::<S>().await;
+                (resolved, TIMEOUT.with(|t| t.elapsed.get()))
+            })
+            .await;
+
+        match resolved {
+            Some(s) => Ok(s),
+            None if elapsed => Err(ResolutionError::TimedOut {
+                type_name: type_name::<S>(),
+            }),
+            None => Err(ResolutionError::returned_none::<S>()),
+        }
+    }
+}
+
+impl<C> TimeoutServices for C where C: AsyncServices {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bind::AsyncBindServices;
+
+    struct Connection;
+
+    struct Repository {
+        _connection: Arc<Connection>,
+    }
+
+    fn container() -> ServiceContainer {
+        let mut container = ServiceContainer::default();
+
+        container.bind_by_async(|_| async {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            Some(Arc::new(Connection))
+        });
+        container.bind_by_async(|c: &ServiceContainer| {
+            let c = c.clone();
+            async move {
+                Some(Arc::new(Repository {
+                    _connection: c.get_async().await?,
+                }))
+            }
+        });
+
+        container
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn default_async_timeout() {
+        let mut container = container();
+        assert!(container.get_async::<Repository>().await.is_some());
+
+        // The nested resolution of the connection times out.
+        container.set_default_async_timeout(Duration::from_millis(10));
+        assert!(container.get_async::<Repository>().await.is_none());
+        assert!(matches!(
+            container.try_get_async::<Repository>().await,
+            Err(ResolutionError::TimedOut { .. }),
+        ));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn get_async_timeout() {
+        let mut container = container();
+        container.set_default_async_timeout(Duration::from_millis(10));
+
+        // The per-call timeout wins over the default one.
+        assert!(container
+            .get_async_timeout::<Repository>(Duration::from_millis(100))
+            .await
+            .is_ok());
+
+        assert_eq!(
+            Some(ResolutionError::TimedOut {
+                type_name: type_name::<Repository>(),
+            }),
+            container
+                .get_async_timeout::<Repository>(Duration::from_millis(20))
+                .await
+                .err(),
+        );
+    }
+}
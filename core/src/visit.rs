@@ -0,0 +1,52 @@
+//! Traverses the registrations of a container, for tooling such as exporting the dependency
+//! graph, validating the wiring, or aggregating the health of the services.
+
+use crate::ResolutionKind;
+
+/// Visited by [`Services::visit`](crate::Services::visit) once for every registration of the
+/// container, in arbitrary order.
+///
+/// A service registered both synchronously and asynchronously is visited once for each. Closures
+/// taking the same arguments as [`ServiceVisitor::visit_service`] are visitors too.
+///
+/// ```
+/// use std::sync::Arc;
+///
+/// use ruice::visit::ServiceVisitor;
+/// use ruice::{BindServices, ResolutionKind, ServiceContainer, Services, SingletonServices};
+///
+/// #[derive(Default)]
+/// struct Counter {
+///     singletons: usize,
+/// }
+///
+/// impl ServiceVisitor for Counter {
+///     fn visit_service(&mut self, _: &'static str, kind: ResolutionKind, _: bool) {
+///         if kind == ResolutionKind::Singleton {
+///             self.singletons += 1;
+///         }
+///     }
+/// }
+///
+/// let mut container = ServiceContainer::default();
+/// container.singleton(42u32);
+/// container.bind(Arc::new("Taro".to_string()));
+///
+/// let mut counter = Counter::default();
+/// container.visit(&mut counter);
+/// assert_eq!(1, counter.singletons);
+/// ```
+pub trait ServiceVisitor {
+    /// Visits a registration of the service, resolved by the resolver of the kind.
+    /// Asynchronous resolvers are reported as [`ResolutionKind::Async`].
+    fn visit_service(&mut self, type_name: &'static str, kind: ResolutionKind, is_async: bool);
+}
+
+impl<F> ServiceVisitor for F
+where
+    F: FnMut(&'static str, ResolutionKind, bool),
+{
+    fn visit_service(&mut self, type_name: &'static str, kind: ResolutionKind, is_async: bool) {
+        self(type_name, kind, is_async)
+    }
+}
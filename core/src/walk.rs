@@ -0,0 +1,334 @@
+//! Walks the singleton services held by the container, for cross-cutting operations such as
+//! running the health checks of every service supporting them.
+
+use std::any::{Any, TypeId};
+use std::fmt;
+use std::sync::Arc;
+
+use crate::ServiceContainer;
+
+type CastFn =
+    dyn Fn(Box<dyn Any + Send + Sync>) -> Option<Box<dyn Any + Send + Sync>> + Send + Sync;
+
+/// A type-erased cast from `Arc<S>` into `Arc<T>`, boxing them both.
+#[derive(Clone)]
+pub(crate) struct Cast(Arc<CastFn>);
+
+impl fmt::Debug for Cast {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Cast")
+    }
+}
+
+/// Rust can not tell at runtime whether a type-erased service implements a trait, so the
+/// services are viewed as a trait object only when registered as it, or when the cast into it
+/// is declared by [`ServiceContainer::register_cast`].
+impl ServiceContainer {
+    /// Declares that the service `S` can be viewed as `T`, usually a trait implemented by it, for
+    /// [`ServiceContainer::for_each_singleton_as`].
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use ruice::{ServiceContainer, SingletonServices};
+    ///
+    /// trait HealthCheck: Send + Sync {
+    ///     fn check(&self) -> bool;
+    /// }
+    ///
+    /// struct Database;
+    ///
+    /// impl HealthCheck for Database {
+    ///     fn check(&self) -> bool {
+    ///         true
+    ///     }
+    /// }
+    ///
+    /// let mut container = ServiceContainer::default();
+    /// container.singleton(Database);
+    /// container.register_cast::<Database, dyn HealthCheck>(|s| s);
+    ///
+    /// let mut healthy = true;
+    /// container.for_each_singleton_as::<dyn HealthCheck, _>(|s| healthy &= s.check());
+    /// assert!(healthy);
+    /// ```
+    pub fn register_cast<S, T>(&mut self, cast: fn(Arc<S>) -> Arc<T>)
+    where
+        S: ?Sized + Send + Sync + 'static,
+        T: ?Sized + Send + Sync + 'static,
+    {
+        let cast = move |s: Box<dyn Any + Send + Sync>| {
+            let s = s.downcast::<Arc<S>>().ok()?;
+            Some(Box::new(cast(*s)) as Box<dyn Any + Send + Sync>)
+        };
+
+        self.casts
+            .insert((TypeId::of::<S>(), TypeId::of::<T>()), Cast(Arc::new(cast)));
+    }
+
+    /// Calls the closure on every singleton service viewable as `T`: the one registered as `T`
+    /// itself, and the ones declared castable into it by [`ServiceContainer::register_cast`].
+    /// The services are visited in arbitrary order.
+    ///
+    /// Only the services held by their resolvers are visited, i.e. the ones put by
+    /// [`SingletonServices::singleton`](crate::SingletonServices::singleton),
+    /// [`BindServices::bind`](crate::BindServices::bind), and
+    /// [`SingletonServices::put_lazy`](crate::SingletonServices::put_lazy) once created. The
+    /// other resolvers are skipped rather than resolved, so walking the services never creates
+    /// them; this includes lazy singletons, which can not be inspected without resolving them.
+    pub fn for_each_singleton_as<T, F>(&self, mut f: F)
+    where
+        T: ?Sized + Send + Sync + 'static,
+        F: FnMut(Arc<T>),
+    {
+        let target = TypeId::of::<T>();
+
+        for (id, entry) in &self.services {
+            let cast = self.casts.get(&(*id, target));
+            if *id != target && cast.is_none() {
+                continue;
+            }
+
+            let Some(service) = entry.singleton_any() else {
+                continue;
+            };
+
+            let service = match cast {
+                Some(Cast(cast)) => cast(service),
+                None => Some(service),
+            };
+
+            if let Some(service) = service.and_then(|s| s.downcast::<Arc<T>>().ok()) {
+                f(*service);
+            }
+        }
+    }
+
+    /// Declares that the service `S` has the capability `T`, usually a trait implemented by it,
+    /// for [`ServiceContainer::get_all_capabilities`]. The cast is required as Rust can not
+    /// coerce `Arc<S>` into `Arc<T>` generically; pass `|s| s` for the trait objects.
+    ///
+    /// This shares the declarations with [`ServiceContainer::register_cast`].
+    pub fn register_capability<T, S>(&mut self, cast: fn(Arc<S>) -> Arc<T>)
+    where
+        T: ?Sized + Send + Sync + 'static,
+        S: ?Sized + Send + Sync + 'static,
+    {
+        self.register_cast::<S, T>(cast);
+    }
+
+    /// Resolves every service with the capability `T`, sorted by their type names: the one
+    /// registered as `T` itself, and the ones declared by
+    /// [`ServiceContainer::register_capability`]. The services failing to resolve are skipped.
+    ///
+    /// Unlike [`ServiceContainer::for_each_singleton_as`], the services are resolved, creating
+    /// the lazy singletons and constructing the others, e.g. for checking the health of all the
+    /// services or shutting them down gracefully.
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use ruice::{BindServices, ServiceContainer, Services, SingletonServices};
+    ///
+    /// trait HealthCheck: Send + Sync {
+    ///     fn check(&self) -> bool;
+    /// }
+    ///
+    /// struct Database;
+    ///
+    /// impl HealthCheck for Database {
+    ///     fn check(&self) -> bool {
+    ///         true
+    ///     }
+    /// }
+    ///
+    /// trait Cache: Send + Sync {}
+    ///
+    /// struct Redis;
+    ///
+    /// impl Cache for Redis {}
+    ///
+    /// impl HealthCheck for Redis {
+    ///     fn check(&self) -> bool {
+    ///         true
+    ///     }
+    /// }
+    ///
+    /// let mut container = ServiceContainer::default();
+    /// container.singleton(Database);
+    /// container.register_capability::<dyn HealthCheck, Database>(|s| s);
+    /// container.put_lazy(|_| Redis);
+    /// container.bind_by(|c| Some(c.get::<Redis>()? as Arc<dyn Cache>));
+    /// container.register_capability::<dyn HealthCheck, Redis>(|s| s);
+    ///
+    /// let checks = container.get_all_capabilities::<dyn HealthCheck>();
+    /// assert_eq!(2, checks.len());
+    /// assert!(checks.iter().all(|c| c.check()));
+    /// ```
+    pub fn get_all_capabilities<T>(&self) -> Vec<Arc<T>>
+    where
+        T: ?Sized + Send + Sync + 'static,
+    {
+        let target = TypeId::of::<T>();
+
+        let mut services = self
+            .services
+            .iter()
+            .filter_map(|(id, entry)| {
+                let cast = self.casts.get(&(*id, target));
+                if *id != target && cast.is_none() {
+                    return None;
+                }
+
+                let service = entry.resolve_any(self)?;
+                let service = match cast {
+                    Some(Cast(cast)) => cast(service)?,
+                    None => service,
+                };
+
+                Some((entry.type_name(), *service.downcast::<Arc<T>>().ok()?))
+            })
+            .collect::<Vec<_>>();
+        services.sort_by_key(|&(name, _)| name);
+
+        services.into_iter().map(|(_, service)| service).collect()
+    }
+
+    /// Returns the strong counts of the [`Arc`]s of the singleton services held by their
+    /// resolvers, sorted by the type names, for hunting down leaks in long-running processes.
+    ///
+    /// A count of 1 means only the container holds the service; the clones of the container
+    /// share the resolvers, so they do not add to it. A count that keeps growing across the
+    /// requests, while they should release what they resolved, points to something cloning the
+    /// service and holding it, such as a cycle of [`Arc`]s through the container. Like
+    /// [`ServiceContainer::for_each_singleton_as`], only the services held by their resolvers are
+    /// reported, and nothing is resolved.
+    pub fn arc_strong_counts(&self) -> Vec<(&'static str, usize)> {
+        let mut counts = self
+            .services
+            .values()
+            .filter_map(|e| Some((e.type_name(), e.strong_count()?)))
+            .collect::<Vec<_>>();
+        counts.sort_unstable();
+
+        counts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::any::type_name;
+
+    use super::*;
+    use crate::{BindServices, Construct, ConstructServices, Services, SingletonServices};
+
+    trait HealthCheck: Send + Sync {
+        fn name(&self) -> &'static str;
+    }
+
+    struct Database;
+
+    impl HealthCheck for Database {
+        fn name(&self) -> &'static str {
+            "database"
+        }
+    }
+
+    struct Cache;
+
+    impl HealthCheck for Cache {
+        fn name(&self) -> &'static str {
+            "cache"
+        }
+    }
+
+    struct Mailer;
+
+    impl HealthCheck for Mailer {
+        fn name(&self) -> &'static str {
+            "mailer"
+        }
+    }
+
+    impl Construct for Mailer {
+        fn construct(_container: &ServiceContainer) -> Option<Self> {
+            Some(Self)
+        }
+    }
+
+    struct Queue;
+
+    impl HealthCheck for Queue {
+        fn name(&self) -> &'static str {
+            "queue"
+        }
+    }
+
+    #[test]
+    fn for_each_singleton_as() {
+        let mut container = ServiceContainer::default();
+
+        container.singleton(Database);
+        container.register_cast::<Database, dyn HealthCheck>(|s| s);
+        container.bind::<dyn HealthCheck>(Arc::new(Cache));
+
+        // Not created by the resolver yet, so skipped.
+        container.put_lazy(|_| Queue);
+        container.register_cast::<Queue, dyn HealthCheck>(|s| s);
+
+        // Created on every resolution, so skipped.
+        container.construct::<Mailer>();
+        container.register_cast::<Mailer, dyn HealthCheck>(|s| s);
+
+        let names = || {
+            let mut names = vec![];
+            container.for_each_singleton_as::<dyn HealthCheck, _>(|s| names.push(s.name()));
+            names.sort();
+            names
+        };
+        assert_eq!(vec!["cache", "database"], names());
+
+        container.get::<Queue>().unwrap();
+        assert_eq!(vec!["cache", "database", "queue"], names());
+    }
+
+    #[test]
+    fn get_all_capabilities() {
+        let mut container = ServiceContainer::default();
+
+        container.singleton(Database);
+        container.register_capability::<dyn HealthCheck, Database>(|s| s);
+        container.put_lazy(|_| Queue);
+        container.register_capability::<dyn HealthCheck, Queue>(|s| s);
+        container.construct::<Mailer>();
+        container.register_capability::<dyn HealthCheck, Mailer>(|s| s);
+
+        // Registered without the capability.
+        container.singleton(Cache);
+
+        let names = container
+            .get_all_capabilities::<dyn HealthCheck>()
+            .iter()
+            .map(|s| s.name())
+            .collect::<Vec<_>>();
+        assert_eq!(vec!["database", "mailer", "queue"], names);
+    }
+
+    #[test]
+    fn arc_strong_counts() {
+        let mut container = ServiceContainer::default();
+
+        container.singleton(Database);
+        container.construct::<Mailer>();
+        let database = type_name::<Database>();
+        assert_eq!(vec![(database, 1)], container.arc_strong_counts());
+
+        // Held outside the container, e.g. leaked by a request.
+        let leaked = container.get::<Database>().unwrap();
+        let _clone = container.clone();
+        assert_eq!(vec![(database, 2)], container.arc_strong_counts());
+
+        drop(leaked);
+        assert_eq!(vec![(database, 1)], container.arc_strong_counts());
+    }
+}
@@ -0,0 +1,278 @@
+//! Exercises containers shared across threads with randomized interleavings of resolutions.
+//!
+//! The interleavings are driven by a seeded generator, so a failing seed can be reproduced.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Barrier};
+use std::thread;
+
+use ruice::bind::BindBy;
+use ruice::prelude::*;
+use ruice::LazySingleton;
+
+const THREADS: usize = 8;
+const STEPS: usize = 1_000;
+
+/// A xorshift generator, good enough for shuffling the operations.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> usize {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0 as usize
+    }
+}
+
+struct Config {
+    name: String,
+}
+
+struct Repository {
+    config: Arc<Config>,
+}
+
+impl Construct for Repository {
+    fn construct(container: &ServiceContainer) -> Option<Self> {
+        Some(Self {
+            config: container.get()?,
+        })
+    }
+}
+
+struct Cache {
+    _config: Arc<Config>,
+}
+
+impl Construct for Cache {
+    fn construct(container: &ServiceContainer) -> Option<Self> {
+        Some(Self {
+            _config: container.get()?,
+        })
+    }
+}
+
+trait Plugin: Send + Sync {}
+
+struct Token;
+
+impl Plugin for Token {}
+
+fn container() -> ServiceContainer {
+    let mut container = ServiceContainer::default();
+
+    container.singleton(Config {
+        name: "app".to_string(),
+    });
+    container.construct::<Repository>();
+    container.lazy_singleton::<Cache>();
+    container.put_once(Token);
+    container.put_tagged::<dyn Plugin>(Arc::new(Token));
+    container.put_tagged::<dyn Plugin>(Arc::new(Token));
+    container.bind_by_async_once(|_| async { Some(Arc::new(42u64)) });
+
+    container
+}
+
+/// Runs the closure on all the threads at once, each with its own generator.
+fn run<F>(seed: u64, f: F)
+where
+    F: Fn(&mut Rng) + Send + Sync + 'static,
+{
+    let f = Arc::new(f);
+    let barrier = Arc::new(Barrier::new(THREADS));
+
+    let handles = (0..THREADS as u64)
+        .map(|i| {
+            let f = Arc::clone(&f);
+            let barrier = Arc::clone(&barrier);
+
+            thread::spawn(move || {
+                let mut rng = Rng(seed.wrapping_mul(31).wrapping_add(i + 1));
+                barrier.wait();
+                f(&mut rng);
+            })
+        })
+        .collect::<Vec<_>>();
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+}
+
+#[test]
+fn shared_container_is_send_and_sync() {
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    assert_send_sync::<ServiceContainer>();
+    assert_send_sync::<Arc<ServiceContainer>>();
+}
+
+#[test]
+fn concurrent_get() {
+    for seed in 0..8 {
+        let container = Arc::new(container());
+        let config = container.get::<Config>().unwrap();
+        let tokens = Arc::new(AtomicUsize::new(0));
+
+        let (c, t) = (Arc::clone(&container), Arc::clone(&tokens));
+        run(seed, move |rng| {
+            let cache = c.get::<Cache>().unwrap();
+
+            for _ in 0..STEPS {
+                match rng.next() % 5 {
+                    0 => assert_eq!("app", c.get::<Config>().unwrap().name),
+                    1 => assert_eq!("app", c.get::<Repository>().unwrap().config.name),
+                    2 => assert!(Arc::ptr_eq(&cache, &c.get::<Cache>().unwrap())),
+                    3 => {
+                        if c.get::<Token>().is_some() {
+                            t.fetch_add(1, Ordering::SeqCst);
+                        }
+                    }
+                    _ => assert_eq!(2, c.get_tagged::<dyn Plugin>().len()),
+                }
+            }
+        });
+
+        // The one-shot service is taken by exactly one resolution.
+        assert_eq!(1, tokens.load(Ordering::SeqCst));
+        assert!(Arc::ptr_eq(&config, &container.get::<Config>().unwrap()));
+    }
+}
+
+#[test]
+fn concurrent_put_on_clones() {
+    for seed in 0..8 {
+        let container = Arc::new(container());
+
+        let c = Arc::clone(&container);
+        run(seed, move |rng| {
+            // Mutations happen on clones sharing the resolvers with the original container.
+            let mut local = (*c).clone();
+
+            for step in 0..STEPS {
+                match rng.next() % 6 {
+                    0 => local.singleton(step),
+                    1 => local.put_tagged::<dyn Plugin>(Arc::new(Token)),
+                    2 => assert!(local.get::<Repository>().is_some()),
+                    3 => {
+                        local.remove::<usize>();
+                        assert!(local.get::<usize>().is_none());
+                    }
+                    4 => {
+                        local.remove::<Cache>();
+                        assert!(local.get::<Cache>().is_none());
+                        assert!(c.get::<Cache>().is_some());
+                    }
+                    _ => assert_eq!(2, c.get_tagged::<dyn Plugin>().len()),
+                }
+            }
+        });
+
+        assert!(container.get::<usize>().is_none());
+        assert!(container.get::<Cache>().is_some());
+        assert_eq!(2, container.get_tagged::<dyn Plugin>().len());
+    }
+}
+
+#[test]
+fn concurrent_lazy_initialization() {
+    for seed in 0..8 {
+        let mut container = ServiceContainer::default();
+        let attempts = Arc::new(AtomicUsize::new(0));
+        let failures = seed as usize % 4;
+
+        // The first attempts fail, handing the initialization over to the waiting threads.
+        let counter = Arc::clone(&attempts);
+        container.put(LazySingleton::new(BindBy::from(
+            move |_: &ServiceContainer| {
+                let attempt = counter.fetch_add(1, Ordering::SeqCst);
+                thread::yield_now();
+                (attempt >= failures).then(|| Arc::new(attempt))
+            },
+        )));
+
+        let container = Arc::new(container);
+        let resolved = Arc::new(std::sync::Mutex::new(vec![]));
+
+        let (c, r) = (Arc::clone(&container), Arc::clone(&resolved));
+        run(seed, move |rng| {
+            for _ in 0..rng.next() % 3 {
+                thread::yield_now();
+            }
+
+            if let Some(n) = c.get::<usize>() {
+                r.lock().unwrap().push(n);
+            }
+        });
+
+        let resolved = resolved.lock().unwrap();
+        let n = container.get::<usize>().unwrap();
+        assert!(resolved.iter().all(|r| Arc::ptr_eq(r, &n)));
+        assert_eq!(failures + 1, attempts.load(Ordering::SeqCst));
+    }
+}
+
+#[cfg(feature = "arc-swap")]
+#[test]
+fn concurrent_swap() {
+    /// Both fields are stored together, so a torn read would see them differ.
+    struct Pair(usize, usize);
+
+    for seed in 0..8 {
+        let mut container = ServiceContainer::default();
+        let handle = container.bind_swappable(Arc::new(Pair(0, 0)));
+        let container = Arc::new(container);
+
+        let c = Arc::clone(&container);
+        run(seed, move |rng| {
+            for step in 0..STEPS {
+                match rng.next() % 4 {
+                    0 => handle.store(Arc::new(Pair(step, step))),
+                    _ => {
+                        let pair = c.get::<Pair>().unwrap();
+                        assert_eq!(pair.0, pair.1);
+                    }
+                }
+            }
+        });
+
+        let pair = container.get::<Pair>().unwrap();
+        assert_eq!(pair.0, pair.1);
+    }
+}
+
+#[test]
+fn concurrent_get_async() {
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(4)
+        .build()
+        .unwrap();
+
+    let container = Arc::new(container());
+
+    runtime.block_on(async {
+        let tasks = (0..THREADS * 4)
+            .map(|i| {
+                let c = Arc::clone(&container);
+                tokio::spawn(async move {
+                    for _ in 0..i % 7 {
+                        tokio::task::yield_now().await;
+                    }
+
+                    c.get_async::<u64>().await.unwrap()
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let mut resolved = vec![];
+        for task in tasks {
+            resolved.push(task.await.unwrap());
+        }
+
+        assert!(resolved.iter().all(|n| Arc::ptr_eq(n, &resolved[0])));
+    });
+
+    assert!(container.is_resolved::<u64>());
+}
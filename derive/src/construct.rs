@@ -0,0 +1,118 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::{
+    parse_quote, Data, DeriveInput, Error, Field, Fields, GenericArgument, PathArguments, Result,
+    Type,
+};
+
+pub fn derive(input: DeriveInput) -> Result<TokenStream> {
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new_spanned(
+            &input,
+            "Construct can only be derived for structs",
+        ));
+    };
+
+    let fields = match &data.fields {
+        Fields::Named(fields) => fields
+            .named
+            .iter()
+            .map(|f| f.ident.to_token_stream())
+            .collect::<Vec<_>>(),
+        Fields::Unnamed(fields) => (0..fields.unnamed.len())
+            .map(|i| syn::Index::from(i).to_token_stream())
+            .collect(),
+        Fields::Unit => vec![],
+    };
+
+    // Optional fields are resolved as they are, leaving them `None` instead of failing.
+    let resolutions = data
+        .fields
+        .iter()
+        .map(|f| {
+            Ok(if is_optional(f)? {
+                quote!(::ruice::Services::get(container))
+            } else {
+                quote!(::ruice::__private::resolve_field(container)?)
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let name = &input.ident;
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+
+    // The container type is added to the generics of the struct, named not to collide with them.
+    let mut generics = input.generics.clone();
+    generics.params.push(parse_quote!(__C));
+    let where_clause = generics.make_where_clause();
+    where_clause
+        .predicates
+        .push(parse_quote!(__C: ::ruice::Services));
+    where_clause
+        .predicates
+        .push(parse_quote!(Self: ::std::marker::Send + ::std::marker::Sync));
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+
+    // Tuple structs are initialized by their indices, e.g. `Self { 0: ... }`.
+    Ok(quote! {
+        impl #impl_generics ::ruice::Construct<Self, __C> for #name #ty_generics #where_clause {
+            fn construct(container: &__C) -> ::std::option::Option<Self> {
+                ::std::option::Option::Some(Self {
+                    #(
+                        #fields: #resolutions,
+                    )*
+                })
+            }
+        }
+    })
+}
+
+/// Returns whether the field is an optional dependency: either marked by `#[construct(optional)]`,
+/// or typed `Option<Arc<...>>`.
+fn is_optional(field: &Field) -> Result<bool> {
+    let mut optional = false;
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|a| a.path().is_ident("construct"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("optional") {
+                optional = true;
+                Ok(())
+            } else {
+                Err(meta.error("unsupported construct attribute, expected `optional`"))
+            }
+        })?;
+    }
+
+    Ok(optional || is_option_of_arc(&field.ty))
+}
+
+/// Detects `Option<Arc<...>>` by the last segments of the paths, so the qualified ones such as
+/// `std::option::Option<std::sync::Arc<...>>` are detected too. Aliases are not.
+fn is_option_of_arc(ty: &Type) -> bool {
+    let Some(argument) = last_segment_argument(ty, "Option") else {
+        return false;
+    };
+
+    matches!(argument, GenericArgument::Type(ty) if last_segment_argument(ty, "Arc").is_some())
+}
+
+fn last_segment_argument<'a>(ty: &'a Type, name: &str) -> Option<&'a GenericArgument> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+
+    let segment = path.path.segments.last()?;
+    if segment.ident != name {
+        return None;
+    }
+
+    match &segment.arguments {
+        PathArguments::AngleBracketed(arguments) if arguments.args.len() == 1 => {
+            arguments.args.first()
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,50 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::{parse_quote, Data, DeriveInput, Error, Fields, Result};
+
+pub fn derive(input: DeriveInput) -> Result<TokenStream> {
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new_spanned(
+            &input,
+            "Inject can only be derived for structs",
+        ));
+    };
+
+    let fields = match &data.fields {
+        Fields::Named(fields) => fields
+            .named
+            .iter()
+            .map(|f| f.ident.to_token_stream())
+            .collect::<Vec<_>>(),
+        Fields::Unnamed(fields) => (0..fields.unnamed.len())
+            .map(|i| syn::Index::from(i).to_token_stream())
+            .collect(),
+        Fields::Unit => vec![],
+    };
+
+    let name = &input.ident;
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+
+    // The container type is added to the generics of the struct, named not to collide with them.
+    let mut generics = input.generics.clone();
+    generics.params.push(parse_quote!(__C));
+    generics
+        .make_where_clause()
+        .predicates
+        .push(parse_quote!(__C: ::ruice::Services));
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+
+    // Refers to the injector trait of the core crate, not the extractor of ruice-axum.
+    Ok(quote! {
+        impl #impl_generics ::ruice::inject::Inject<__C> for #name #ty_generics #where_clause {
+            fn inject(&self, container: &mut __C) {
+                #(
+                    ::ruice::BindServices::bind(
+                        container,
+                        ::std::sync::Arc::clone(&self.#fields),
+                    );
+                )*
+            }
+        }
+    })
+}
@@ -0,0 +1,49 @@
+//! Derive macros for ruice.
+
+mod construct;
+mod inject;
+mod services;
+
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput};
+
+/// Implements `Services`, `AsyncServices` and `DynServices` for a custom container by delegating
+/// to its `ServiceContainer` field marked with `#[services]`.
+#[proc_macro_derive(Services, attributes(services))]
+pub fn derive_services(input: TokenStream) -> TokenStream {
+    services::derive(parse_macro_input!(input as DeriveInput))
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Implements `Construct` for a bundle of services, resolving each field of it from the
+/// container. All the fields must be [`Arc`](std::sync::Arc)s, e.g. `Arc<dyn Trait>`, resolved
+/// by their pointee types.
+///
+/// Fields typed `Option<Arc<...>>` are optional dependencies, left `None` when they could not be
+/// resolved instead of failing the whole bundle. Mark the fields typed by aliases of them with
+/// `#[construct(optional)]`, as the types are detected syntactically.
+///
+/// The bundle can be registered by `construct`, or resolved at once by `resolve_bundle`. Both
+/// `resolve_bundle` and `try_get` on the registered bundle report the error of the first field
+/// failed to resolve, e.g. `ResolutionError::NotRegistered` naming its type, rather than the
+/// bundle returning no service.
+#[proc_macro_derive(Construct, attributes(construct))]
+pub fn derive_construct(input: TokenStream) -> TokenStream {
+    construct::derive(parse_macro_input!(input as DeriveInput))
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Implements the injector trait `ruice::inject::Inject` for a bundle of services, binding each
+/// field of it into the container. All the fields must be [`Arc`](std::sync::Arc)s, e.g.
+/// `Arc<dyn Trait>`, which are bound onto their pointee types.
+///
+/// This is unrelated to the `Inject` extractor of ruice-axum, which resolves services for
+/// handlers.
+#[proc_macro_derive(Inject)]
+pub fn derive_inject(input: TokenStream) -> TokenStream {
+    inject::derive(parse_macro_input!(input as DeriveInput))
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
@@ -0,0 +1,121 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::{Data, DeriveInput, Error, Fields, Result};
+
+pub fn derive(input: DeriveInput) -> Result<TokenStream> {
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new_spanned(
+            &input,
+            "Services can only be derived for structs",
+        ));
+    };
+
+    let mut fields = match &data.fields {
+        Fields::Named(fields) => fields
+            .named
+            .iter()
+            .filter(|f| f.attrs.iter().any(|a| a.path().is_ident("services")))
+            .map(|f| f.ident.to_token_stream())
+            .collect::<Vec<_>>(),
+        Fields::Unnamed(fields) => fields
+            .unnamed
+            .iter()
+            .enumerate()
+            .filter(|(_, f)| f.attrs.iter().any(|a| a.path().is_ident("services")))
+            .map(|(i, _)| syn::Index::from(i).to_token_stream())
+            .collect(),
+        Fields::Unit => vec![],
+    };
+
+    let field = match (fields.pop(), fields.is_empty()) {
+        (Some(field), true) => field,
+        _ => {
+            return Err(Error::new_spanned(
+                &input,
+                "exactly one field must be marked with #[services]",
+            ))
+        }
+    };
+
+    // The generic parameters of the methods are named not to shadow the ones of the container.
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::ruice::Services for #name #ty_generics #where_clause {
+            fn has<__RuiceS>(&self) -> bool
+            where
+                __RuiceS: ?Sized + 'static,
+            {
+                ::ruice::Services::has::<__RuiceS>(&self.#field)
+            }
+
+            fn get<__RuiceS>(&self) -> ::std::option::Option<::std::sync::Arc<__RuiceS>>
+            where
+                __RuiceS: ?Sized + Send + Sync + 'static,
+            {
+                self.#field.get_for::<__RuiceS, Self>(self)
+            }
+
+            fn try_get<__RuiceS>(
+                &self,
+            ) -> ::std::result::Result<::std::sync::Arc<__RuiceS>, ::ruice::ResolutionError>
+            where
+                __RuiceS: ?Sized + Send + Sync + 'static,
+            {
+                self.#field.try_get_for::<__RuiceS, Self>(self)
+            }
+
+            fn put<__RuiceS, __RuiceR>(&mut self, resolver: __RuiceR)
+            where
+                __RuiceS: ?Sized + Send + Sync + 'static,
+                __RuiceR: ::ruice::Resolve<__RuiceS, Self> + 'static,
+            {
+                self.#field.put_for::<__RuiceS, Self, __RuiceR>(resolver)
+            }
+
+            fn visit(&self, visitor: &mut impl ::ruice::ServiceVisitor) {
+                ::ruice::Services::visit(&self.#field, visitor)
+            }
+        }
+
+        impl #impl_generics ::ruice::DynServices for #name #ty_generics #where_clause {
+            fn has_any(&self, id: ::std::any::TypeId) -> bool {
+                ::ruice::DynServices::has_any(&self.#field, id)
+            }
+
+            fn get_any(
+                &self,
+                id: ::std::any::TypeId,
+            ) -> ::std::option::Option<
+                ::std::boxed::Box<dyn ::std::any::Any + ::std::marker::Send + ::std::marker::Sync>,
+            > {
+                self.#field.get_any_for(id, self)
+            }
+        }
+
+        #[::ruice::__private::async_trait]
+        impl #impl_generics ::ruice::AsyncServices for #name #ty_generics #where_clause {
+            async fn get_async<__RuiceS>(&self) -> ::std::option::Option<::std::sync::Arc<__RuiceS>>
+            where
+                __RuiceS: ?Sized + Send + Sync + 'static,
+            {
+                self.#field.get_async_for::<__RuiceS, Self>(self).await
+            }
+
+            fn put_async<__RuiceS>(&mut self, resolver: ::ruice::AsyncResolver<__RuiceS, Self>)
+            where
+                __RuiceS: ?Sized + Send + Sync + 'static,
+            {
+                self.#field.put_async_for::<__RuiceS, Self>(resolver)
+            }
+
+            fn is_resolved<__RuiceS>(&self) -> bool
+            where
+                __RuiceS: ?Sized + 'static,
+            {
+                ::ruice::AsyncServices::is_resolved::<__RuiceS>(&self.#field)
+            }
+        }
+    })
+}
@@ -0,0 +1,209 @@
+use std::sync::Arc;
+
+use ruice::{
+    BindServices, Construct, ConstructServices, ResolutionError, ServiceContainer, Services,
+    SingletonServices,
+};
+
+trait Clock: Send + Sync {
+    fn now(&self) -> u64;
+}
+
+struct FixedClock;
+
+impl Clock for FixedClock {
+    fn now(&self) -> u64 {
+        42
+    }
+}
+
+struct Config {
+    name: String,
+}
+
+struct Mailer;
+
+#[derive(Construct)]
+struct Handler {
+    clock: Arc<dyn Clock>,
+    config: Arc<Config>,
+    _mailer: Arc<Mailer>,
+}
+
+#[derive(Construct)]
+struct TupleHandler(Arc<Config>);
+
+type SharedMailer = Arc<Mailer>;
+
+#[derive(Construct)]
+struct NotifyingHandler {
+    clock: Arc<dyn Clock>,
+    cache: Option<Arc<dyn Cache>>,
+    #[construct(optional)]
+    mailer: Option<SharedMailer>,
+}
+
+trait Cache: Send + Sync {}
+
+struct MemoryCache;
+
+impl Cache for MemoryCache {}
+
+fn container() -> ServiceContainer {
+    let mut container = ServiceContainer::default();
+
+    container.bind::<dyn Clock>(Arc::new(FixedClock));
+    container.singleton(Config {
+        name: "app".to_string(),
+    });
+
+    container
+}
+
+#[test]
+fn resolve_bundle() {
+    let mut container = container();
+    container.singleton(Mailer);
+
+    let handler = container.resolve_bundle::<Handler>().unwrap();
+    assert_eq!(42, handler.clock.now());
+    assert_eq!("app", handler.config.name);
+
+    let handler = container.resolve_bundle::<TupleHandler>().unwrap();
+    assert!(Arc::ptr_eq(&handler.0, &container.get::<Config>().unwrap()));
+}
+
+#[test]
+fn resolve_bundle_missing_field() {
+    let container = container();
+
+    // The missing field is named, rather than the bundle.
+    assert_eq!(
+        Err(ResolutionError::NotRegistered {
+            type_name: std::any::type_name::<Mailer>(),
+        }),
+        container.resolve_bundle::<Handler>().map(|_| ()),
+    );
+}
+
+#[test]
+fn construct_bundle() {
+    let mut container = container();
+
+    container.construct::<TupleHandler>();
+    assert_eq!("app", container.get::<TupleHandler>().unwrap().0.name);
+}
+
+#[test]
+fn try_get_missing_field() {
+    let mut container = container();
+    container.construct::<Handler>();
+
+    // Points at the missing dependency, rather than the bundle returning no service.
+    assert_eq!(
+        Err(ResolutionError::NotRegistered {
+            type_name: std::any::type_name::<Mailer>(),
+        }),
+        container.try_get::<Handler>().map(|_| ()),
+    );
+
+    container.singleton(Mailer);
+    assert!(container.try_get::<Handler>().is_ok());
+}
+
+#[test]
+fn ensure_missing_field() {
+    let mut container = container();
+
+    // Points at the missing dependency, and registers nothing.
+    assert_eq!(
+        Err(ResolutionError::NotRegistered {
+            type_name: std::any::type_name::<Mailer>(),
+        }),
+        container.ensure::<Handler>().map(|_| ()),
+    );
+    assert!(!container.has::<Handler>());
+
+    container.singleton(Mailer);
+    let handler = container.ensure::<Handler>().unwrap();
+    assert!(container.same_instance(&handler));
+}
+
+#[test]
+fn optional_fields() {
+    let mut container = container();
+
+    // The optional dependencies absent degrade gracefully.
+    let handler = container.resolve_bundle::<NotifyingHandler>().unwrap();
+    assert_eq!(42, handler.clock.now());
+    assert!(handler.cache.is_none());
+    assert!(handler.mailer.is_none());
+
+    container.bind::<dyn Cache>(Arc::new(MemoryCache));
+    container.singleton(Mailer);
+    let handler = container.resolve_bundle::<NotifyingHandler>().unwrap();
+    assert!(handler.cache.is_some());
+    assert!(handler.mailer.is_some());
+
+    // The required one is still required.
+    let container = ServiceContainer::default();
+    assert!(container.resolve_bundle::<NotifyingHandler>().is_err());
+}
+
+struct Queue;
+
+#[derive(Construct)]
+struct Repository {
+    _config: Arc<Config>,
+    _mailer: Arc<Mailer>,
+}
+
+struct Dashboard {
+    _repository: Arc<Repository>,
+    queue: Result<Arc<Queue>, ResolutionError>,
+}
+
+impl Construct for Dashboard {
+    fn construct(container: &ServiceContainer) -> Option<Self> {
+        let repository = container.get::<Repository>();
+
+        // Probes an optional dependency failing too, whose own error must not replace the one
+        // of the repository.
+        let queue = container.try_get::<Queue>();
+
+        Some(Self {
+            _repository: repository?,
+            queue,
+        })
+    }
+}
+
+#[test]
+fn try_get_nested_failures() {
+    let mut container = container();
+    container.construct::<Repository>();
+    container.construct::<Dashboard>();
+    container.bind_by(|_| None::<Arc<Queue>>);
+
+    // Reports the missing field of the nested bundle, rather than the bundle returning nothing.
+    assert_eq!(
+        Err(ResolutionError::NotRegistered {
+            type_name: std::any::type_name::<Mailer>(),
+        }),
+        container.try_get::<Dashboard>().map(|_| ()),
+    );
+
+    // The failure of the repository does not leak into the probe either.
+    container.singleton(Mailer);
+    assert_eq!(
+        Err(ResolutionError::ResolverReturnedNone {
+            type_name: std::any::type_name::<Queue>(),
+        }),
+        container
+            .try_get::<Dashboard>()
+            .unwrap()
+            .queue
+            .clone()
+            .map(|_| ()),
+    );
+}
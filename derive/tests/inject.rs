@@ -0,0 +1,64 @@
+use std::sync::Arc;
+
+use ruice::{Inject, InjectServices, ServiceContainer, Services};
+
+trait Clock: Send + Sync {
+    fn now(&self) -> u64;
+}
+
+trait Greet: Send + Sync {
+    fn greet(&self) -> String;
+}
+
+struct FixedClock;
+
+impl Clock for FixedClock {
+    fn now(&self) -> u64 {
+        42
+    }
+}
+
+struct Greeter;
+
+impl Greet for Greeter {
+    fn greet(&self) -> String {
+        "Hello, world!".to_string()
+    }
+}
+
+#[derive(Inject)]
+struct Bundle {
+    clock: Arc<dyn Clock>,
+    greeter: Arc<dyn Greet>,
+}
+
+#[derive(Inject)]
+struct TupleBundle(Arc<dyn Clock>);
+
+#[test]
+fn inject_bundle() {
+    let mut container = ServiceContainer::default();
+
+    let bundle = Bundle {
+        clock: Arc::new(FixedClock),
+        greeter: Arc::new(Greeter),
+    };
+    container.inject(bundle);
+
+    assert_eq!(42, container.get::<dyn Clock>().unwrap().now());
+    assert_eq!(
+        "Hello, world!",
+        container.get::<dyn Greet>().unwrap().greet()
+    );
+}
+
+#[test]
+fn inject_tuple_bundle() {
+    let mut container = ServiceContainer::default();
+
+    let clock: Arc<dyn Clock> = Arc::new(FixedClock);
+    container.inject(TupleBundle(Arc::clone(&clock)));
+
+    // The bundled instances are shared, not copied.
+    assert!(Arc::ptr_eq(&clock, &container.get::<dyn Clock>().unwrap()));
+}
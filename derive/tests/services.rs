@@ -0,0 +1,146 @@
+use std::sync::Arc;
+
+use ruice::bind::AsyncBindServices;
+use ruice::{
+    AsyncServices, BindServices, Construct, DynServices, ResolutionError, ServiceContainer,
+    Services, SingletonServices, TaggedServices,
+};
+
+#[derive(Default, Services)]
+struct RequestContainer {
+    #[services]
+    services: ServiceContainer,
+    request_id: String,
+}
+
+struct Greeter {
+    message: String,
+}
+
+#[tokio::test]
+async fn custom_container() {
+    let mut container = RequestContainer {
+        request_id: "42".to_string(),
+        ..Default::default()
+    };
+
+    container.singleton(Greeter {
+        message: "Hello, world!".to_string(),
+    });
+
+    // Resolvers receive the custom container, so they can read its extra fields.
+    container.bind_by(|c: &RequestContainer| Some(Arc::new(c.request_id.clone())));
+    container.bind_by_async(|c: &RequestContainer| {
+        let request_id = c.request_id.parse::<u32>().ok();
+        async move { request_id.map(Arc::new) }
+    });
+
+    assert!(container.has::<Greeter>());
+    assert_eq!("Hello, world!", container.get::<Greeter>().unwrap().message);
+    assert_eq!("42", container.get::<String>().unwrap().as_str());
+    assert_eq!(42, *container.get_async::<u32>().await.unwrap());
+    assert!(container.get::<u32>().is_none());
+
+    let mut registrations = 0;
+    container.visit(&mut |_, _, _| registrations += 1);
+    assert_eq!(3, registrations);
+
+    // The failures are reported as by the inner container.
+    assert_eq!(
+        Err(ResolutionError::WrongResolverKind {
+            type_name: std::any::type_name::<u32>(),
+        }),
+        container.try_get::<u32>().map(|_| ()),
+    );
+}
+
+trait Plugin: Send + Sync {
+    fn name(&self) -> String;
+}
+
+struct Static;
+
+impl Plugin for Static {
+    fn name(&self) -> String {
+        "static".to_string()
+    }
+}
+
+struct PerRequest {
+    request_id: String,
+}
+
+impl Plugin for PerRequest {
+    fn name(&self) -> String {
+        format!("request {}", self.request_id)
+    }
+}
+
+impl Construct<Self, RequestContainer> for PerRequest {
+    fn construct(container: &RequestContainer) -> Option<Self> {
+        Some(Self {
+            request_id: container.request_id.clone(),
+        })
+    }
+}
+
+#[test]
+fn convenience_traits_on_custom_container() {
+    let mut container = RequestContainer {
+        request_id: "42".to_string(),
+        ..Default::default()
+    };
+
+    // Tagged members and lazy services are resolved against the custom container.
+    container.put_tagged::<dyn Plugin>(Arc::new(Static));
+    container.construct_tagged::<dyn Plugin, PerRequest>(|p| p);
+    container.lazy_singleton::<PerRequest>();
+    container.put_lazy(|c: &RequestContainer| c.request_id.len());
+
+    let names = container
+        .get_tagged::<dyn Plugin>()
+        .iter()
+        .map(|p| p.name())
+        .collect::<Vec<_>>();
+    assert_eq!(vec!["static", "request 42"], names);
+
+    let plugin = container.get::<PerRequest>().unwrap();
+    assert!(Arc::ptr_eq(
+        &plugin,
+        &container.get::<PerRequest>().unwrap()
+    ));
+    assert_eq!(2, *container.get::<usize>().unwrap());
+
+    // The object-safe facade resolves against the custom container too.
+    let services: &dyn DynServices = &container;
+    assert_eq!("42", services.get::<PerRequest>().unwrap().request_id);
+}
+
+#[derive(Services)]
+struct StatefulContainer<S, R>
+where
+    S: Send + Sync + 'static,
+    R: Send + Sync + 'static,
+{
+    #[services]
+    services: ServiceContainer,
+    state: S,
+    _request: R,
+}
+
+#[tokio::test]
+async fn generic_container() {
+    let mut container = StatefulContainer {
+        services: ServiceContainer::default(),
+        state: "Hello".to_string(),
+        _request: (),
+    };
+
+    // The generic parameters of the container do not clash with the ones of the methods.
+    container.bind_by(|c: &StatefulContainer<String, ()>| Some(Arc::new(c.state.len())));
+    container.bind_by_async(|_| async { Some(Arc::new(42u32)) });
+
+    assert!(container.has::<usize>());
+    assert_eq!(5, *container.get::<usize>().unwrap());
+    assert_eq!(42, *container.get_async::<u32>().await.unwrap());
+}